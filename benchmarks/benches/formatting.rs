@@ -10,7 +10,7 @@ static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 struct Conf<'a> {
     name: &'a str,
     text: &'a str,
-    matching_words: MatcherBuilder<'a, Vec<u8>>,
+    matching_words: MatcherBuilder<'a>,
 }
 
 fn bench_formatting(c: &mut criterion::Criterion) {
@@ -29,7 +29,7 @@ fn bench_formatting(c: &mut criterion::Criterion) {
 	            (vec![Rc::new(MatchingWord::new("thedoord".to_string(), 1, true).unwrap())], vec![0, 1, 2]),
 	            (vec![Rc::new(MatchingWord::new("doord".to_string(), 1, true).unwrap())], vec![1, 2]),
         	]
-            ), TokenizerBuilder::default().build()),
+            ), TokenizerBuilder::default().into_tokenizer()),
 		},
     ];
 
@@ -4,6 +4,7 @@
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::PrimaryKey;
 use meilisearch_types::settings::Unchecked;
 use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
 use meilisearch_types::InstanceUid;
@@ -36,13 +37,21 @@ pub struct Metadata {
 #[serde(rename_all = "camelCase")]
 pub struct IndexMetadata {
     pub uid: String,
-    pub primary_key: Option<String>,
+    pub primary_key: Option<PrimaryKey>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
 }
 
+/// The version of a document of an index, the versions of the deleted documents are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentVersion {
+    pub id: String,
+    pub version: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Version {
     V1,
@@ -92,14 +101,26 @@ pub struct TaskDump {
 #[serde(rename_all = "camelCase")]
 pub enum KindDump {
     DocumentImport {
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         method: IndexDocumentsMethod,
         documents_count: u64,
         allow_index_creation: bool,
+        #[serde(default)]
+        autogenerate_ids: bool,
+        #[serde(default)]
+        skip_invalid_documents: bool,
     },
     DocumentDeletion {
         documents_ids: Vec<String>,
     },
+    DocumentDeletionByFilter {
+        filter: serde_json::Value,
+    },
+    DocumentEdition {
+        filter: Option<serde_json::Value>,
+        function: String,
+    },
+    DocumentExpiration,
     DocumentClear,
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
@@ -108,14 +129,15 @@ pub enum KindDump {
     },
     IndexDeletion,
     IndexCreation {
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
     },
     IndexUpdate {
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexCompaction,
     TaskCancelation {
         query: String,
         tasks: RoaringBitmap,
@@ -156,16 +178,27 @@ impl From<KindWithContent> for KindDump {
                 method,
                 documents_count,
                 allow_index_creation,
+                autogenerate_ids,
+                skip_invalid_documents,
                 ..
             } => KindDump::DocumentImport {
                 primary_key,
                 method,
                 documents_count,
                 allow_index_creation,
+                autogenerate_ids,
+                skip_invalid_documents,
             },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
             }
+            KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
+                KindDump::DocumentDeletionByFilter { filter: filter_expr }
+            }
+            KindWithContent::DocumentEdition { filter_expr, function, .. } => {
+                KindDump::DocumentEdition { filter: filter_expr, function }
+            }
+            KindWithContent::DocumentExpiration { .. } => KindDump::DocumentExpiration,
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -181,6 +214,7 @@ impl From<KindWithContent> for KindDump {
                 KindDump::IndexUpdate { primary_key }
             }
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
+            KindWithContent::IndexCompaction { .. } => KindDump::IndexCompaction,
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
             }
@@ -215,7 +249,9 @@ pub(crate) mod test {
     use uuid::Uuid;
 
     use crate::reader::Document;
-    use crate::{DumpReader, DumpWriter, IndexMetadata, KindDump, TaskDump, Version};
+    use crate::{
+        DocumentVersion, DumpReader, DumpWriter, IndexMetadata, KindDump, TaskDump, Version,
+    };
 
     pub fn create_test_instance_uid() -> Uuid {
         Uuid::parse_str("9e15e977-f2ae-4761-943f-1eaf75fd736d").unwrap()
@@ -230,6 +266,15 @@ pub(crate) mod test {
         }
     }
 
+    pub fn create_test_documents_versions() -> Vec<DocumentVersion> {
+        vec![
+            DocumentVersion { id: S("1"), version: 3 },
+            DocumentVersion { id: S("2"), version: 1 },
+            // the version of a deleted document
+            DocumentVersion { id: S("4"), version: 2 },
+        ]
+    }
+
     pub fn create_test_documents() -> Vec<Map<String, Value>> {
         vec![
             json!({ "id": 1, "race": "golden retriever", "name": "paul", "age": 4 })
@@ -253,13 +298,21 @@ pub(crate) mod test {
             searchable_attributes: Setting::Set(vec![S("name"), S("race")]),
             filterable_attributes: Setting::Set(btreeset! { S("race"), S("age") }),
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
+            date_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            rules: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            schema: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -275,13 +328,21 @@ pub(crate) mod test {
                     kind: KindDump::DocumentImport {
                         method: milli::update::IndexDocumentsMethod::UpdateDocuments,
                         allow_index_creation: true,
-                        primary_key: Some(S("bone")),
+                        autogenerate_ids: false,
+                        skip_invalid_documents: false,
+                        primary_key: Some(S("bone").into()),
                         documents_count: 12,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        conflicting_documents: Vec::new(),
+                        conflicting_documents_report: None,
+                        generated_documents_ids: Vec::new(),
+                        generated_documents_ids_report: None,
+                        invalid_documents: Vec::new(),
+                        invalid_documents_report: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -298,6 +359,8 @@ pub(crate) mod test {
                     kind: KindDump::DocumentImport {
                         method: milli::update::IndexDocumentsMethod::UpdateDocuments,
                         allow_index_creation: true,
+                        autogenerate_ids: false,
+                        skip_invalid_documents: false,
                         primary_key: None,
                         documents_count: 2,
                     },
@@ -305,6 +368,12 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        conflicting_documents: Vec::new(),
+                        conflicting_documents_report: None,
+                        generated_documents_ids: Vec::new(),
+                        generated_documents_ids_report: None,
+                        invalid_documents: Vec::new(),
+                        invalid_documents_report: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -381,6 +450,9 @@ pub(crate) mod test {
         for document in &documents {
             index.push_document(document).unwrap();
         }
+        for version in &create_test_documents_versions() {
+            index.push_document_version(version).unwrap();
+        }
         index.flush().unwrap();
         index.settings(&settings).unwrap();
 
@@ -433,6 +505,8 @@ pub(crate) mod test {
         for (document, expected) in index.documents().unwrap().zip(create_test_documents()) {
             assert_eq!(document.unwrap(), expected);
         }
+        let versions = index.documents_versions().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(versions, create_test_documents_versions());
         assert_eq!(index.settings().unwrap(), create_test_settings());
         assert_eq!(index.metadata(), &create_test_index_metadata());
 
@@ -79,10 +79,10 @@ impl CompatV5ToV6 {
                     },
                     kind: match task.content {
                         v5::tasks::TaskContent::IndexCreation { primary_key, .. } => {
-                            v6::Kind::IndexCreation { primary_key }
+                            v6::Kind::IndexCreation { primary_key: primary_key.map(Into::into) }
                         }
                         v5::tasks::TaskContent::IndexUpdate { primary_key, .. } => {
-                            v6::Kind::IndexUpdate { primary_key }
+                            v6::Kind::IndexUpdate { primary_key: primary_key.map(Into::into) }
                         }
                         v5::tasks::TaskContent::IndexDeletion { .. } => v6::Kind::IndexDeletion,
                         v5::tasks::TaskContent::DocumentAddition {
@@ -92,7 +92,7 @@ impl CompatV5ToV6 {
                             documents_count,
                             ..
                         } => v6::Kind::DocumentImport {
-                            primary_key,
+                            primary_key: primary_key.map(Into::into),
                             documents_count: documents_count as u64,
                             method: match merge_strategy {
                                 v5::tasks::IndexDocumentsMethod::ReplaceDocuments => {
@@ -103,6 +103,8 @@ impl CompatV5ToV6 {
                                 }
                             },
                             allow_index_creation,
+                            autogenerate_ids: false,
+                            skip_invalid_documents: false,
                         },
                         v5::tasks::TaskContent::DocumentDeletion { deletion, .. } => match deletion
                         {
@@ -132,13 +134,19 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                conflicting_documents: Vec::new(),
+                                conflicting_documents_report: None,
+                                generated_documents_ids: Vec::new(),
+                                generated_documents_ids_report: None,
+                                invalid_documents: Vec::new(),
+                                invalid_documents_report: None,
                             }
                         }
                         v5::Details::Settings { settings } => {
                             v6::Details::SettingsUpdate { settings: Box::new(settings.into()) }
                         }
                         v5::Details::IndexInfo { primary_key } => {
-                            v6::Details::IndexInfo { primary_key }
+                            v6::Details::IndexInfo { primary_key: primary_key.map(Into::into) }
                         }
                         v5::Details::DocumentDeletion {
                             received_document_ids,
@@ -317,6 +325,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             searchable_attributes: settings.searchable_attributes.into(),
             filterable_attributes: settings.filterable_attributes.into(),
             sortable_attributes: settings.sortable_attributes.into(),
+            date_attributes: v6::Setting::NotSet,
             ranking_rules: {
                 match settings.ranking_rules {
                     v5::settings::Setting::Set(ranking_rules) => {
@@ -338,6 +347,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 }
             },
             stop_words: settings.stop_words.into(),
+            separator_tokens: v6::Setting::NotSet,
+            non_separator_tokens: v6::Setting::NotSet,
+            dictionary: v6::Setting::NotSet,
             synonyms: settings.synonyms.into(),
             distinct_attribute: settings.distinct_attribute.into(),
             typo_tolerance: match settings.typo_tolerance {
@@ -353,6 +365,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    min_word_size_for_typos_per_attribute: v6::Setting::NotSet,
+                    disable_on_numbers: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -371,6 +385,10 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            rules: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            stemming_languages: v6::Setting::NotSet,
+            schema: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -9,7 +9,7 @@ use self::compat::v4_to_v5::CompatV4ToV5;
 use self::compat::v5_to_v6::{CompatIndexV5ToV6, CompatV5ToV6};
 use self::v5::V5Reader;
 use self::v6::{V6IndexReader, V6Reader};
-use crate::{Result, Version};
+use crate::{DocumentVersion, Result, Version};
 
 mod compat;
 
@@ -101,6 +101,15 @@ impl DumpReader {
         }
     }
 
+    /// Returns the report of a task identified by its uuid, the dumps older
+    /// than the v6 don't contain any report.
+    pub fn report(&self, uuid: uuid::Uuid) -> Result<Option<File>> {
+        match self {
+            DumpReader::Current(current) => current.report(uuid),
+            DumpReader::Compat(_) => Ok(None),
+        }
+    }
+
     pub fn keys(&mut self) -> Result<Box<dyn Iterator<Item = Result<v6::Key>> + '_>> {
         match self {
             DumpReader::Current(current) => Ok(current.keys()),
@@ -161,6 +170,18 @@ impl DumpIndexReader {
         }
     }
 
+    /// The versions of the documents, the dumps of the previous versions don't contain any.
+    pub fn documents_versions(
+        &mut self,
+    ) -> Result<Box<dyn Iterator<Item = Result<DocumentVersion>> + '_>> {
+        match self {
+            DumpIndexReader::Current(v6) => v6.documents_versions().map(|iter| {
+                Box::new(iter) as Box<dyn Iterator<Item = Result<DocumentVersion>> + '_>
+            }),
+            DumpIndexReader::Compat(_) => Ok(Box::new(std::iter::empty())),
+        }
+    }
+
     pub fn settings(&mut self) -> Result<v6::Settings<v6::Checked>> {
         match self {
             DumpIndexReader::Current(v6) => v6.settings(),
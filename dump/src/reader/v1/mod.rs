@@ -152,7 +152,7 @@ impl From<Index> for IndexMetadataV1 {
             name: index.name,
             metadata: crate::IndexMetadata {
                 uid: index.uid,
-                primary_key: index.primary_key,
+                primary_key: index.primary_key.map(Into::into),
                 created_at: index.created_at,
                 updated_at: index.updated_at,
             },
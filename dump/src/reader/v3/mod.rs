@@ -191,7 +191,7 @@ impl V3IndexReader {
 
         let metadata = IndexMetadata {
             uid: index_uuid.uid.clone(),
-            primary_key: meta.primary_key,
+            primary_key: meta.primary_key.map(Into::into),
             created_at: created_at.unwrap_or(current_time),
             updated_at: updated_at.unwrap_or(current_time),
         };
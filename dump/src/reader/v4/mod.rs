@@ -186,7 +186,7 @@ impl V4IndexReader {
 
         let metadata = IndexMetadata {
             uid: name,
-            primary_key: meta.primary_key,
+            primary_key: meta.primary_key.map(Into::into),
             created_at: created_at.unwrap_or(current_time),
             updated_at: updated_at.unwrap_or(current_time),
         };
@@ -223,7 +223,7 @@ impl V5IndexReader {
 
         let metadata = IndexMetadata {
             uid: name,
-            primary_key: meta.primary_key,
+            primary_key: meta.primary_key.map(Into::into),
             created_at: created_at.unwrap_or_else(OffsetDateTime::now_utc),
             updated_at: updated_at.unwrap_or_else(OffsetDateTime::now_utc),
         };
@@ -8,7 +8,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::Document;
-use crate::{Error, IndexMetadata, Result, Version};
+use crate::{DocumentVersion, Error, IndexMetadata, Result, Version};
 
 pub type Metadata = crate::Metadata;
 
@@ -125,6 +125,16 @@ impl V6Reader {
         }))
     }
 
+    /// Returns the report of a task identified by its uuid, if it was dumped.
+    pub fn report(&self, uuid: Uuid) -> Result<Option<File>> {
+        let path = self.dump.path().join("tasks").join("reports").join(format!("{}.jsonl", uuid));
+        match File::open(path) {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn keys(&mut self) -> Box<dyn Iterator<Item = Result<Key>> + '_> {
         Box::new(
             (&mut self.keys).lines().map(|line| -> Result<_> { Ok(serde_json::from_str(&line?)?) }),
@@ -159,16 +169,24 @@ impl Iterator for UpdateFile {
 pub struct V6IndexReader {
     metadata: IndexMetadata,
     documents: BufReader<File>,
+    versions: Option<BufReader<File>>,
     settings: BufReader<File>,
 }
 
 impl V6IndexReader {
     pub fn new(_name: String, path: &Path) -> Result<Self> {
         let metadata = File::open(path.join("metadata.json"))?;
+        // the dumps created before the documents versions existed don't contain them
+        let versions = match File::open(path.join("versions.jsonl")) {
+            Ok(file) => Some(BufReader::new(file)),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
 
         let ret = V6IndexReader {
             metadata: serde_json::from_reader(metadata)?,
             documents: BufReader::new(File::open(path.join("documents.jsonl"))?),
+            versions,
             settings: BufReader::new(File::open(path.join("settings.json"))?),
         };
 
@@ -185,6 +203,16 @@ impl V6IndexReader {
             .map(|line| -> Result<_> { Ok(serde_json::from_str(&line?)?) }))
     }
 
+    pub fn documents_versions(
+        &mut self,
+    ) -> Result<impl Iterator<Item = Result<DocumentVersion>> + '_> {
+        Ok(self
+            .versions
+            .iter_mut()
+            .flat_map(|versions| versions.lines())
+            .map(|line| -> Result<_> { Ok(serde_json::from_str(&line?)?) }))
+    }
+
     pub fn settings(&mut self) -> Result<Settings<Checked>> {
         let settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
         Ok(settings.check())
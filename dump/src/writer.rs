@@ -1,5 +1,5 @@
 use std::fs::{self, File};
-use std::io::{BufWriter, Write};
+use std::io::{self, BufWriter, Read, Write};
 use std::path::PathBuf;
 
 use flate2::write::GzEncoder;
@@ -12,7 +12,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::reader::Document;
-use crate::{IndexMetadata, Metadata, Result, TaskDump, CURRENT_DUMP_VERSION};
+use crate::{DocumentVersion, IndexMetadata, Metadata, Result, TaskDump, CURRENT_DUMP_VERSION};
 
 pub struct DumpWriter {
     dir: TempDir,
@@ -90,6 +90,7 @@ impl KeyWriter {
 pub struct TaskWriter {
     queue: BufWriter<File>,
     update_files: PathBuf,
+    reports: PathBuf,
 }
 
 impl TaskWriter {
@@ -100,7 +101,7 @@ impl TaskWriter {
         let update_files = path.join("update_files");
         std::fs::create_dir(&update_files)?;
 
-        Ok(TaskWriter { queue: BufWriter::new(queue), update_files })
+        Ok(TaskWriter { queue: BufWriter::new(queue), update_files, reports: path.join("reports") })
     }
 
     /// Pushes tasks in the dump.
@@ -112,6 +113,14 @@ impl TaskWriter {
         Ok(UpdateFile::new(self.update_files.join(format!("{}.jsonl", task.uid))))
     }
 
+    /// Pushes a report of a task in the dump, it uses the `uuid` of the report as its name.
+    pub fn push_report(&mut self, uuid: Uuid, mut report: impl Read) -> Result<()> {
+        fs::create_dir_all(&self.reports)?;
+        let mut file = File::create(self.reports.join(format!("{}.jsonl", uuid)))?;
+        io::copy(&mut report, &mut file)?;
+        Ok(())
+    }
+
     pub fn flush(mut self) -> Result<()> {
         self.queue.flush()?;
         Ok(())
@@ -150,6 +159,7 @@ impl UpdateFile {
 
 pub struct IndexWriter {
     documents: BufWriter<File>,
+    versions: BufWriter<File>,
     settings: File,
 }
 
@@ -161,9 +171,14 @@ impl IndexWriter {
         serde_json::to_writer(metadata_file, metadata)?;
 
         let documents = File::create(path.join("documents.jsonl"))?;
+        let versions = File::create(path.join("versions.jsonl"))?;
         let settings = File::create(path.join("settings.json"))?;
 
-        Ok(IndexWriter { documents: BufWriter::new(documents), settings })
+        Ok(IndexWriter {
+            documents: BufWriter::new(documents),
+            versions: BufWriter::new(versions),
+            settings,
+        })
     }
 
     pub fn push_document(&mut self, document: &Map<String, Value>) -> Result<()> {
@@ -172,8 +187,15 @@ impl IndexWriter {
         Ok(())
     }
 
+    pub fn push_document_version(&mut self, version: &DocumentVersion) -> Result<()> {
+        serde_json::to_writer(&mut self.versions, version)?;
+        self.versions.write_all(b"\n")?;
+        Ok(())
+    }
+
     pub fn flush(&mut self) -> Result<()> {
         self.documents.flush()?;
+        self.versions.flush()?;
         Ok(())
     }
 
@@ -279,7 +301,8 @@ pub(crate) mod test {
         │    └---- doggos/
         │    │    ├---- documents.jsonl
         │    │    ├---- metadata.json
-        │    │    └---- settings.json
+        │    │    ├---- settings.json
+        │    │    └---- versions.jsonl
         ├---- tasks/
         │    ├---- update_files/
         │    │    └---- 1.jsonl
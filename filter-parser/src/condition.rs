@@ -3,6 +3,10 @@
 //! ```text
 //! condition      = value ("==" | ">" ...) value
 //! to             = value value TO value
+//! is_null        = value "IS" WS+ "NULL"
+//! is_empty       = value "IS" WS+ "EMPTY"
+//! contains       = value "CONTAINS" value
+//! starts_with    = value "STARTS" WS+ "WITH" value
 //! ```
 
 use nom::branch::alt;
@@ -12,6 +16,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
+use crate::value::word_exact;
 use crate::{parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,9 +26,13 @@ pub enum Condition<'a> {
     Equal(Token<'a>),
     NotEqual(Token<'a>),
     Exists,
+    Null,
+    Empty,
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
     Between { from: Token<'a>, to: Token<'a> },
+    Contains(Token<'a>),
+    StartsWith(Token<'a>),
 }
 
 /// condition      = value ("==" | ">" ...) value
@@ -58,6 +67,50 @@ pub fn parse_not_exists(input: Span) -> IResult<FilterCondition> {
     Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Exists }))))
 }
 
+/// is_null        = value "IS" WS+ "NULL"
+pub fn parse_is_null(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _)) =
+        tuple((parse_value, word_exact("IS"), multispace1, word_exact("NULL")))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid: key, op: Null }))
+}
+
+/// is_not_null    = value "IS" WS+ "NOT" WS+ "NULL"
+pub fn parse_is_not_null(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _, _, _)) = tuple((
+        parse_value,
+        word_exact("IS"),
+        multispace1,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("NULL"),
+    ))(input)?;
+
+    Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Null }))))
+}
+
+/// is_empty       = value "IS" WS+ "EMPTY"
+pub fn parse_is_empty(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _)) =
+        tuple((parse_value, word_exact("IS"), multispace1, word_exact("EMPTY")))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid: key, op: Empty }))
+}
+
+/// is_not_empty   = value "IS" WS+ "NOT" WS+ "EMPTY"
+pub fn parse_is_not_empty(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _, _, _)) = tuple((
+        parse_value,
+        word_exact("IS"),
+        multispace1,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("EMPTY"),
+    ))(input)?;
+
+    Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Empty }))))
+}
+
 /// to             = value value "TO" WS+ value
 pub fn parse_to(input: Span) -> IResult<FilterCondition> {
     let (input, (key, from, _, _, to)) =
@@ -65,3 +118,54 @@ pub fn parse_to(input: Span) -> IResult<FilterCondition> {
 
     Ok((input, FilterCondition::Condition { fid: key, op: Between { from, to } }))
 }
+
+/// contains       = value "CONTAINS" value
+pub fn parse_contains(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _, value)) =
+        tuple((parse_value, word_exact("CONTAINS"), cut(parse_value)))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid, op: Contains(value) }))
+}
+
+/// not_contains   = value "NOT" WS+ "CONTAINS" value
+pub fn parse_not_contains(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("CONTAINS"),
+        cut(parse_value),
+    ))(input)?;
+
+    let condition = FilterCondition::Condition { fid, op: Contains(value) };
+    Ok((input, FilterCondition::Not(Box::new(condition))))
+}
+
+/// starts_with    = value "STARTS" WS+ "WITH" value
+pub fn parse_starts_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("STARTS"),
+        multispace1,
+        word_exact("WITH"),
+        cut(parse_value),
+    ))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid, op: StartsWith(value) }))
+}
+
+/// not_starts_with = value "NOT" WS+ "STARTS" WS+ "WITH" value
+pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, _, _, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("STARTS"),
+        multispace1,
+        word_exact("WITH"),
+        cut(parse_value),
+    ))(input)?;
+
+    let condition = FilterCondition::Condition { fid, op: StartsWith(value) };
+    Ok((input, FilterCondition::Not(Box::new(condition))))
+}
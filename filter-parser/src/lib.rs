@@ -6,12 +6,20 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | is_null | is_not_null | is_empty | is_not_empty | to | contains | not_contains | starts_with | not_starts_with
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
+//! is_null        = value "IS" WS+ "NULL"
+//! is_not_null    = value "IS" WS+ "NOT" WS+ "NULL"
+//! is_empty       = value "IS" WS+ "EMPTY"
+//! is_not_empty   = value "IS" WS+ "NOT" WS+ "EMPTY"
 //! to             = value value "TO" WS+ value
+//! contains       = value "CONTAINS" value
+//! not_contains   = value "NOT" WS+ "CONTAINS" value
+//! starts_with    = value "STARTS" WS+ "WITH" value
+//! not_starts_with = value "NOT" WS+ "STARTS" WS+ "WITH" value
 //! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
@@ -46,7 +54,10 @@ mod value;
 use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
-use condition::{parse_exists, parse_not_exists};
+use condition::{
+    parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
+    parse_is_null, parse_not_contains, parse_not_exists, parse_not_starts_with, parse_starts_with,
+};
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
 use nom::branch::alt;
@@ -352,7 +363,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | is_null | is_not_null | is_empty | is_not_empty | to | contains | not_contains | starts_with | not_starts_with
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -372,7 +383,15 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_condition,
         parse_exists,
         parse_not_exists,
+        parse_is_null,
+        parse_is_not_null,
+        parse_is_empty,
+        parse_is_not_empty,
         parse_to,
+        parse_contains,
+        parse_not_contains,
+        parse_starts_with,
+        parse_not_starts_with,
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
         parse_geo_point,
         parse_error_reserved_keyword,
@@ -461,6 +480,27 @@ pub mod tests {
         insta::assert_display_snapshot!(p("subscribers NOT   EXISTS"), @"NOT ({subscribers} EXISTS)");
         insta::assert_display_snapshot!(p("NOT subscribers 100 TO 1000"), @"NOT ({subscribers} {100} TO {1000})");
 
+        // Test IS NULL + IS EMPTY
+        insta::assert_display_snapshot!(p("subscribers IS NULL"), @"{subscribers} IS NULL");
+        insta::assert_display_snapshot!(p("subscribers IS NOT NULL"), @"NOT ({subscribers} IS NULL)");
+        insta::assert_display_snapshot!(p("NOT subscribers IS NULL"), @"NOT ({subscribers} IS NULL)");
+        insta::assert_display_snapshot!(p("NOT subscribers IS NOT NULL"), @"{subscribers} IS NULL");
+        insta::assert_display_snapshot!(p("subscribers  IS   NOT   NULL"), @"NOT ({subscribers} IS NULL)");
+        insta::assert_display_snapshot!(p("subscribers IS EMPTY"), @"{subscribers} IS EMPTY");
+        insta::assert_display_snapshot!(p("subscribers IS NOT EMPTY"), @"NOT ({subscribers} IS EMPTY)");
+        insta::assert_display_snapshot!(p("NOT subscribers IS EMPTY"), @"NOT ({subscribers} IS EMPTY)");
+        insta::assert_display_snapshot!(p("subscribers IS NULL OR subscribers IS EMPTY"), @"OR[{subscribers} IS NULL, {subscribers} IS EMPTY, ]");
+
+        // Test CONTAINS + STARTS WITH
+        insta::assert_display_snapshot!(p("title CONTAINS kebab"), @"{title} CONTAINS {kebab}");
+        insta::assert_display_snapshot!(p("title CONTAINS 'super kebab'"), @"{title} CONTAINS {super kebab}");
+        insta::assert_display_snapshot!(p("title NOT CONTAINS kebab"), @"NOT ({title} CONTAINS {kebab})");
+        insta::assert_display_snapshot!(p("NOT title CONTAINS kebab"), @"NOT ({title} CONTAINS {kebab})");
+        insta::assert_display_snapshot!(p("title STARTS WITH kebab"), @"{title} STARTS WITH {kebab}");
+        insta::assert_display_snapshot!(p("title STARTS   WITH \"super kebab\""), @"{title} STARTS WITH {super kebab}");
+        insta::assert_display_snapshot!(p("title NOT STARTS WITH kebab"), @"NOT ({title} STARTS WITH {kebab})");
+        insta::assert_display_snapshot!(p("title STARTS WITH kebab AND title CONTAINS salad"), @"AND[{title} STARTS WITH {kebab}, {title} CONTAINS {salad}, ]");
+
         // Test nested NOT
         insta::assert_display_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
         insta::assert_display_snapshot!(p("NOT NOT (NOT NOT x = 5)"), @"{x} = {5}");
@@ -526,7 +566,7 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, or `_geoRadius` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -536,12 +576,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, or `_geoRadius` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_display_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, or `_geoRadius` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -591,12 +631,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, or `_geoRadius` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_display_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, or `_geoRadius` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -630,6 +670,31 @@ pub mod tests {
         12:24 colour IN ['blue, green
         "###);
 
+        insta::assert_display_snapshot!(p("title CONTAINS"), @r###"
+        Was expecting a value but instead got nothing.
+        15:15 title CONTAINS
+        "###);
+
+        insta::assert_display_snapshot!(p("title STARTS kebab"), @r###"
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `title STARTS kebab`.
+        1:19 title STARTS kebab
+        "###);
+
+        insta::assert_display_snapshot!(p("colour IS NUL"), @r###"
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, or `_geoRadius` at `colour IS NUL`.
+        1:14 colour IS NUL
+        "###);
+
+        insta::assert_display_snapshot!(p("x = NULL"), @r###"
+        Was expecting a value but instead got `NULL`, which is a reserved keyword. To use `NULL` as a field name or a value, surround it by quotes.
+        5:9 x = NULL
+        "###);
+
+        insta::assert_display_snapshot!(p("x = CONTAINS"), @r###"
+        Was expecting a value but instead got `CONTAINS`, which is a reserved keyword. To use `CONTAINS` as a field name or a value, surround it by quotes.
+        5:13 x = CONTAINS
+        "###);
+
         insta::assert_display_snapshot!(p("x = EXISTS"), @r###"
         Was expecting a value but instead got `EXISTS`, which is a reserved keyword. To use `EXISTS` as a field name or a value, surround it by quotes.
         5:11 x = EXISTS
@@ -726,9 +791,13 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::Equal(token) => write!(f, "= {token}"),
             Condition::NotEqual(token) => write!(f, "!= {token}"),
             Condition::Exists => write!(f, "EXISTS"),
+            Condition::Null => write!(f, "IS NULL"),
+            Condition::Empty => write!(f, "IS EMPTY"),
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
+            Condition::Contains(token) => write!(f, "CONTAINS {token}"),
+            Condition::StartsWith(token) => write!(f, "STARTS WITH {token}"),
         }
     }
 }
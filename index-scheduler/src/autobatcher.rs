@@ -8,7 +8,7 @@ The main function of the autobatcher is [`next_autobatch`].
 use std::ops::ControlFlow::{self, Break, Continue};
 
 use meilisearch_types::milli::update::IndexDocumentsMethod::{
-    self, ReplaceDocuments, UpdateDocuments,
+    self, DeepMergeDocuments, ReplaceDocuments, UpdateDocuments,
 };
 use meilisearch_types::tasks::TaskId;
 
@@ -19,13 +19,23 @@ use crate::KindWithContent;
 ///
 /// Only the non-prioritised tasks that can be grouped in a batch have a corresponding [`AutobatchKind`]
 enum AutobatchKind {
-    DocumentImport { method: IndexDocumentsMethod, allow_index_creation: bool },
+    DocumentImport {
+        method: IndexDocumentsMethod,
+        allow_index_creation: bool,
+        autogenerate_ids: bool,
+    },
     DocumentDeletion,
+    DocumentDeletionByFilter,
+    DocumentEdition,
+    DocumentExpiration,
     DocumentClear,
-    Settings { allow_index_creation: bool },
+    Settings {
+        allow_index_creation: bool,
+    },
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexCompaction,
     IndexSwap,
 }
 
@@ -38,15 +48,30 @@ impl AutobatchKind {
             _ => None,
         }
     }
+
+    fn autogenerate_ids(&self) -> Option<bool> {
+        match self {
+            AutobatchKind::DocumentImport { autogenerate_ids, .. } => Some(*autogenerate_ids),
+            _ => None,
+        }
+    }
 }
 
 impl From<KindWithContent> for AutobatchKind {
     fn from(kind: KindWithContent) -> Self {
         match kind {
-            KindWithContent::DocumentAdditionOrUpdate { method, allow_index_creation, .. } => {
-                AutobatchKind::DocumentImport { method, allow_index_creation }
-            }
+            KindWithContent::DocumentAdditionOrUpdate {
+                method,
+                allow_index_creation,
+                autogenerate_ids,
+                ..
+            } => AutobatchKind::DocumentImport { method, allow_index_creation, autogenerate_ids },
             KindWithContent::DocumentDeletion { .. } => AutobatchKind::DocumentDeletion,
+            KindWithContent::DocumentDeletionByFilter { .. } => {
+                AutobatchKind::DocumentDeletionByFilter
+            }
+            KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
+            KindWithContent::DocumentExpiration { .. } => AutobatchKind::DocumentExpiration,
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
@@ -56,6 +81,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexDeletion { .. } => AutobatchKind::IndexDeletion,
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
+            KindWithContent::IndexCompaction { .. } => AutobatchKind::IndexCompaction,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -75,11 +101,21 @@ pub enum BatchKind {
     DocumentImport {
         method: IndexDocumentsMethod,
         allow_index_creation: bool,
+        autogenerate_ids: bool,
         import_ids: Vec<TaskId>,
     },
     DocumentDeletion {
         deletion_ids: Vec<TaskId>,
     },
+    DocumentDeletionByFilter {
+        id: TaskId,
+    },
+    DocumentEdition {
+        id: TaskId,
+    },
+    DocumentExpiration {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -89,6 +125,7 @@ pub enum BatchKind {
         settings_ids: Vec<TaskId>,
         method: IndexDocumentsMethod,
         allow_index_creation: bool,
+        autogenerate_ids: bool,
         import_ids: Vec<TaskId>,
     },
     Settings {
@@ -104,6 +141,9 @@ pub enum BatchKind {
     IndexUpdate {
         id: TaskId,
     },
+    IndexCompaction {
+        id: TaskId,
+    },
     IndexSwap {
         id: TaskId,
     },
@@ -120,6 +160,15 @@ impl BatchKind {
             _ => None,
         }
     }
+
+    #[rustfmt::skip]
+    fn autogenerate_ids(&self) -> Option<bool> {
+        match self {
+            BatchKind::DocumentImport { autogenerate_ids, .. }
+            | BatchKind::SettingsAndDocumentImport { autogenerate_ids, .. } => Some(*autogenerate_ids),
+            _ => None,
+        }
+    }
 }
 
 impl BatchKind {
@@ -138,12 +187,19 @@ impl BatchKind {
             K::IndexCreation => (Break(BatchKind::IndexCreation { id: task_id }), true),
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
+            K::IndexCompaction => (Break(BatchKind::IndexCompaction { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
+            K::DocumentDeletionByFilter => {
+                (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
+            }
+            K::DocumentEdition => (Break(BatchKind::DocumentEdition { id: task_id }), false),
+            K::DocumentExpiration => (Break(BatchKind::DocumentExpiration { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
-            K::DocumentImport { method, allow_index_creation } => (
+            K::DocumentImport { method, allow_index_creation, autogenerate_ids } => (
                 Continue(BatchKind::DocumentImport {
                     method,
                     allow_index_creation,
+                    autogenerate_ids,
                     import_ids: vec![task_id],
                 }),
                 allow_index_creation,
@@ -168,16 +224,20 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexCompaction | K::IndexSwap | K::DocumentDeletionByFilter | K::DocumentEdition | K::DocumentExpiration) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
             },
+            // We must not batch document imports that don't generate the missing documents ids the same way.
+            (this, kind) if matches!((this.autogenerate_ids(), kind.autogenerate_ids()), (Some(left), Some(right)) if left != right) => {
+                Break(this)
+            },
             // The index deletion can batch with everything but must stop after
             (
                 BatchKind::DocumentClear { mut ids }
                 | BatchKind::DocumentDeletion { deletion_ids: mut ids }
-                | BatchKind::DocumentImport { method: _, allow_index_creation: _, import_ids: mut ids }
+                | BatchKind::DocumentImport { method: _, allow_index_creation: _, autogenerate_ids: _, import_ids: mut ids }
                 | BatchKind::Settings { allow_index_creation: _, settings_ids: mut ids },
                 K::IndexDeletion,
             ) => {
@@ -186,7 +246,7 @@ impl BatchKind {
             }
             (
                 BatchKind::ClearAndSettings { settings_ids: mut ids, allow_index_creation: _, mut other }
-                | BatchKind::SettingsAndDocumentImport { import_ids: mut ids, method: _, allow_index_creation: _, settings_ids: mut other },
+                | BatchKind::SettingsAndDocumentImport { import_ids: mut ids, method: _, allow_index_creation: _, autogenerate_ids: _, settings_ids: mut other },
                 K::IndexDeletion,
             ) => {
                 ids.push(id);
@@ -206,7 +266,7 @@ impl BatchKind {
                 K::DocumentImport { .. } | K::Settings { .. },
             ) => Break(this),
             (
-                BatchKind::DocumentImport { method: _, allow_index_creation: _, import_ids: mut ids },
+                BatchKind::DocumentImport { method: _, allow_index_creation: _, autogenerate_ids: _, import_ids: mut ids },
                 K::DocumentClear,
             ) => {
                 ids.push(id);
@@ -215,24 +275,38 @@ impl BatchKind {
 
             // we can autobatch the same kind of document additions / updates
             (
-                BatchKind::DocumentImport { method: ReplaceDocuments, allow_index_creation, mut import_ids },
+                BatchKind::DocumentImport { method: ReplaceDocuments, allow_index_creation, autogenerate_ids, mut import_ids },
                 K::DocumentImport { method: ReplaceDocuments, .. },
             ) => {
                 import_ids.push(id);
                 Continue(BatchKind::DocumentImport {
                     method: ReplaceDocuments,
                     allow_index_creation,
+                    autogenerate_ids,
                     import_ids,
                 })
             }
             (
-                BatchKind::DocumentImport { method: UpdateDocuments, allow_index_creation, mut import_ids },
+                BatchKind::DocumentImport { method: UpdateDocuments, allow_index_creation, autogenerate_ids, mut import_ids },
                 K::DocumentImport { method: UpdateDocuments, .. },
             ) => {
                 import_ids.push(id);
                 Continue(BatchKind::DocumentImport {
                     method: UpdateDocuments,
                     allow_index_creation,
+                    autogenerate_ids,
+                    import_ids,
+                })
+            }
+            (
+                BatchKind::DocumentImport { method: method @ DeepMergeDocuments(_), allow_index_creation, autogenerate_ids, mut import_ids },
+                K::DocumentImport { method: other, .. },
+            ) if method == other => {
+                import_ids.push(id);
+                Continue(BatchKind::DocumentImport {
+                    method,
+                    allow_index_creation,
+                    autogenerate_ids,
                     import_ids,
                 })
             }
@@ -245,12 +319,13 @@ impl BatchKind {
             ) => Break(this),
 
             (
-                BatchKind::DocumentImport { method, allow_index_creation, import_ids },
+                BatchKind::DocumentImport { method, allow_index_creation, autogenerate_ids, import_ids },
                 K::Settings { .. },
             ) => Continue(BatchKind::SettingsAndDocumentImport {
                 settings_ids: vec![id],
                 method,
                 allow_index_creation,
+                autogenerate_ids,
                 import_ids,
             }),
 
@@ -327,7 +402,7 @@ impl BatchKind {
                 })
             }
             (
-                BatchKind::SettingsAndDocumentImport { settings_ids, method: _, import_ids: mut other, allow_index_creation },
+                BatchKind::SettingsAndDocumentImport { settings_ids, method: _, import_ids: mut other, allow_index_creation, autogenerate_ids: _ },
                 K::DocumentClear,
             ) => {
                 other.push(id);
@@ -339,7 +414,7 @@ impl BatchKind {
             }
 
             (
-                BatchKind::SettingsAndDocumentImport { settings_ids, method: ReplaceDocuments, mut import_ids, allow_index_creation },
+                BatchKind::SettingsAndDocumentImport { settings_ids, method: ReplaceDocuments, mut import_ids, allow_index_creation, autogenerate_ids },
                 K::DocumentImport { method: ReplaceDocuments, .. },
             ) => {
                 import_ids.push(id);
@@ -347,11 +422,12 @@ impl BatchKind {
                     settings_ids,
                     method: ReplaceDocuments,
                     allow_index_creation,
+                    autogenerate_ids,
                     import_ids,
                 })
             }
             (
-                BatchKind::SettingsAndDocumentImport { settings_ids, method: UpdateDocuments, allow_index_creation, mut import_ids },
+                BatchKind::SettingsAndDocumentImport { settings_ids, method: UpdateDocuments, allow_index_creation, autogenerate_ids, mut import_ids },
                 K::DocumentImport { method: UpdateDocuments, .. },
             ) => {
                 import_ids.push(id);
@@ -359,6 +435,20 @@ impl BatchKind {
                     settings_ids,
                     method: UpdateDocuments,
                     allow_index_creation,
+                    autogenerate_ids,
+                    import_ids,
+                })
+            }
+            (
+                BatchKind::SettingsAndDocumentImport { settings_ids, method: method @ DeepMergeDocuments(_), allow_index_creation, autogenerate_ids, mut import_ids },
+                K::DocumentImport { method: other, .. },
+            ) if method == other => {
+                import_ids.push(id);
+                Continue(BatchKind::SettingsAndDocumentImport {
+                    settings_ids,
+                    method,
+                    allow_index_creation,
+                    autogenerate_ids,
                     import_ids,
                 })
             }
@@ -369,7 +459,7 @@ impl BatchKind {
                 K::DocumentDeletion | K::DocumentImport { .. },
             ) => Break(this),
             (
-                BatchKind::SettingsAndDocumentImport { mut settings_ids, method, allow_index_creation, import_ids },
+                BatchKind::SettingsAndDocumentImport { mut settings_ids, method, allow_index_creation, autogenerate_ids, import_ids },
                 K::Settings { .. },
             ) => {
                 settings_ids.push(id);
@@ -377,6 +467,7 @@ impl BatchKind {
                     settings_ids,
                     method,
                     allow_index_creation,
+                    autogenerate_ids,
                     import_ids,
                 })
             }
@@ -384,7 +475,11 @@ impl BatchKind {
                 BatchKind::IndexCreation { .. }
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
-                | BatchKind::IndexSwap { .. },
+                | BatchKind::IndexCompaction { .. }
+                | BatchKind::IndexSwap { .. }
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentEdition { .. }
+                | BatchKind::DocumentExpiration { .. },
                 _,
             ) => {
                 unreachable!()
@@ -457,6 +552,21 @@ mod tests {
             content_file: Uuid::new_v4(),
             documents_count: 0,
             allow_index_creation,
+            autogenerate_ids: false,
+            skip_invalid_documents: false,
+        }
+    }
+
+    fn doc_imp_autogen(method: IndexDocumentsMethod) -> KindWithContent {
+        KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            method,
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+            allow_index_creation: true,
+            autogenerate_ids: true,
+            skip_invalid_documents: false,
         }
     }
 
@@ -467,6 +577,25 @@ mod tests {
         }
     }
 
+    fn doc_del_fil() -> KindWithContent {
+        KindWithContent::DocumentDeletionByFilter {
+            index_uid: String::from("doggo"),
+            filter_expr: serde_json::json!("cuteness > 100"),
+        }
+    }
+
+    fn doc_edit() -> KindWithContent {
+        KindWithContent::DocumentEdition {
+            index_uid: String::from("doggo"),
+            filter_expr: None,
+            function: String::from("doc.cuteness += 1"),
+        }
+    }
+
+    fn doc_exp() -> KindWithContent {
+        KindWithContent::DocumentExpiration { index_uid: String::from("doggo") }
+    }
+
     fn doc_clr() -> KindWithContent {
         KindWithContent::DocumentClear { index_uid: String::from("doggo") }
     }
@@ -488,6 +617,10 @@ mod tests {
         KindWithContent::IndexUpdate { index_uid: String::from("doggo"), primary_key: None }
     }
 
+    fn idx_compact() -> KindWithContent {
+        KindWithContent::IndexCompaction { index_uid: String::from("doggo") }
+    }
+
     fn idx_del() -> KindWithContent {
         KindWithContent::IndexDeletion { index_uid: String::from("doggo") }
     }
@@ -502,29 +635,29 @@ mod tests {
     fn autobatch_simple_operation_together() {
         // we can autobatch one or multiple `ReplaceDocuments` together.
         // if the index exists.
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1, 2] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp( ReplaceDocuments, false ), doc_imp(ReplaceDocuments, false )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0, 1, 2] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp( ReplaceDocuments, false ), doc_imp(ReplaceDocuments, false )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1, 2] }, false))");
 
         // if it doesn't exists.
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1, 2] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp( ReplaceDocuments, true ), doc_imp(ReplaceDocuments, true )]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
 
         // we can autobatch one or multiple `UpdateDocuments` together.
         // if the index exists.
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0, 1, 2] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0, 1, 2] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1, 2] }, false))");
 
         // if it doesn't exists.
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0, 1, 2] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0, 1, 2] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false), doc_imp(UpdateDocuments, false)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1, 2] }, false))");
 
         // we can autobatch one or multiple DocumentDeletion together
         debug_snapshot!(autobatch_from(true, [doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
@@ -544,54 +677,112 @@ mod tests {
         debug_snapshot!(autobatch_from(false, [settings(false), settings(false), settings(false)]), @"Some((Settings { allow_index_creation: false, settings_ids: [0, 1, 2] }, false))");
     }
 
+    #[test]
+    fn document_deletion_by_filter_never_autobatch() {
+        debug_snapshot!(autobatch_from(true, [doc_del_fil()]), @"Some((DocumentDeletionByFilter { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_del_fil(), doc_del_fil()]), @"Some((DocumentDeletionByFilter { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_del(), doc_del_fil()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_del_fil()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_clr(), doc_del_fil()]), @"Some((DocumentClear { ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [settings(true), doc_del_fil()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn document_edition_never_autobatch() {
+        debug_snapshot!(autobatch_from(true, [doc_edit()]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_edit(), doc_edit()]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_edit(), doc_del_fil()]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_del(), doc_edit()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_edit()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [settings(true), doc_edit()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn document_expiration_never_autobatch() {
+        debug_snapshot!(autobatch_from(true, [doc_exp()]), @"Some((DocumentExpiration { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_exp(), doc_exp()]), @"Some((DocumentExpiration { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_exp(), doc_del()]), @"Some((DocumentExpiration { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_del(), doc_exp()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_clr(), doc_exp()]), @"Some((DocumentClear { ids: [0] }, false))");
+    }
+
+    #[test]
+    fn index_compaction_never_autobatch() {
+        debug_snapshot!(autobatch_from(true, [idx_compact()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [idx_compact(), idx_compact()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [idx_compact(), idx_del()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_del(), idx_compact()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [settings(true), idx_compact()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
     #[test]
     fn simple_document_operation_dont_autobatch_with_other() {
         // addition, updates and deletion can't batch together
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, [doc_del(), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(true, [doc_del(), doc_imp(UpdateDocuments, true)]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
 
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_create()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_create()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_create()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_create()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, [doc_del(), idx_create()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
 
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_update()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_update()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_update()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_update()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, [doc_del(), idx_update()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
 
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_swap()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_swap()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), idx_swap()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), idx_swap()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, [doc_del(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
     }
 
+    #[test]
+    fn deep_merge_only_autobatch_with_the_same_array_strategy() {
+        use meilisearch_types::milli::update::ArrayMergeStrategy::{Append, Union};
+
+        debug_snapshot!(autobatch_from(true, [doc_imp(DeepMergeDocuments(Append), true), doc_imp(DeepMergeDocuments(Append), true)]), @"Some((DocumentImport { method: DeepMergeDocuments(Append), allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(DeepMergeDocuments(Append), true), doc_imp(DeepMergeDocuments(Union), true)]), @"Some((DocumentImport { method: DeepMergeDocuments(Append), allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(DeepMergeDocuments(Append), true), doc_imp(UpdateDocuments, true)]), @"Some((DocumentImport { method: DeepMergeDocuments(Append), allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp(DeepMergeDocuments(Append), true)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(DeepMergeDocuments(Union), true), settings(true), doc_imp(DeepMergeDocuments(Union), true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: DeepMergeDocuments(Union), allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 2] }, true))");
+    }
+
+    #[test]
+    fn autogenerated_ids_only_autobatch_together() {
+        debug_snapshot!(autobatch_from(true, [doc_imp_autogen(ReplaceDocuments), doc_imp_autogen(ReplaceDocuments)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: true, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp_autogen(ReplaceDocuments), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), doc_imp_autogen(UpdateDocuments)]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp_autogen(ReplaceDocuments), settings(true), doc_imp_autogen(ReplaceDocuments)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: true, import_ids: [0, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp_autogen(ReplaceDocuments), settings(true), doc_imp(ReplaceDocuments, true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: true, import_ids: [0] }, true))");
+    }
+
     #[test]
     fn document_addition_batch_with_settings() {
         // simple case
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
 
         // multiple settings and doc addition
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true), settings(true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [2, 3], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true), settings(true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [2, 3], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true), settings(true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [2, 3], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true), settings(true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [2, 3], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1] }, true))");
 
         // addition and setting unordered
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1, 3], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 2] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_imp(UpdateDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1, 3], method: UpdateDocuments, allow_index_creation: true, import_ids: [0, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1, 3], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_imp(UpdateDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1, 3], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 2] }, true))");
 
         // We ensure this kind of batch doesn't batch with forbidden operations
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_imp(UpdateDocuments, true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_imp(ReplaceDocuments, true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_del()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_del()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_create()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_create()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_update()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_update()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_swap()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_swap()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_imp(UpdateDocuments, true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_imp(ReplaceDocuments, true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), doc_del()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), doc_del()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_create()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_create()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_update()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_update()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true), idx_swap()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(UpdateDocuments, true), settings(true), idx_swap()]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: UpdateDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
     }
 
     #[test]
@@ -703,25 +894,25 @@ mod tests {
         debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), settings(false), doc_clr(), idx_del()]), @"Some((IndexDeletion { ids: [1, 3, 0, 2] }, false))");
         // The third and final case is when the first task doesn't create an index but is directly followed by a task creating an index. In this case we can't batch whith what
         // follows because we first need to process the erronous batch.
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments,false), settings(true), idx_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), settings(true), idx_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments,false), settings(true), doc_clr(), idx_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), settings(true), doc_clr(), idx_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments,false), settings(true), idx_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), settings(true), idx_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments,false), settings(true), doc_clr(), idx_del()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(UpdateDocuments, false), settings(true), doc_clr(), idx_del()]), @"Some((DocumentImport { method: UpdateDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
     }
 
     #[test]
     fn allowed_and_disallowed_index_creation() {
         // `DocumentImport` can't be mixed with those disallowed to do so except if the index already exists.
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0, 1] }, false))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0, 1] }, false))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0, 1] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0, 1] }, false))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
-        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), settings(true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1] }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, false), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), doc_imp(ReplaceDocuments, false)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0, 1] }, false))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, true), settings(true)]), @"Some((SettingsAndDocumentImport { settings_ids: [1], method: ReplaceDocuments, allow_index_creation: true, autogenerate_ids: false, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(false, [doc_imp(ReplaceDocuments, false), settings(true)]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: false, autogenerate_ids: false, import_ids: [0] }, false))");
     }
 }
@@ -20,22 +20,25 @@ one indexing operation.
 use std::collections::{BTreeSet, HashSet};
 use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{self, BufWriter, Write};
 
-use dump::IndexMetadata;
+use dump::{DocumentVersion, IndexMetadata};
 use log::{debug, error, info};
 use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
-    DocumentAdditionResult, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig,
+    Settings as MilliSettings, UpdateIndexingStep,
 };
-use meilisearch_types::milli::{self, BEU32};
+use meilisearch_types::milli::{self, PrimaryKey, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, InvalidDocument, Kind, KindWithContent, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
+use serde::Serialize;
 use time::macros::format_description;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -44,6 +47,18 @@ use crate::autobatcher::{self, BatchKind};
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
 
+/// The maximum number of conflicting documents ids listed in the details of a task,
+/// all of them can be found in the report of the task.
+const MAX_REPORTED_CONFLICTING_DOCUMENTS: usize = 100;
+
+/// The maximum number of invalid documents listed in the details of a task,
+/// all of them can be found in the report of the task.
+const MAX_REPORTED_INVALID_DOCUMENTS: usize = 100;
+
+/// The maximum number of generated documents ids listed in the details of a task,
+/// all of them can be found in the report of the task.
+const MAX_REPORTED_GENERATED_DOCUMENTS_IDS: usize = 100;
+
 /// Represents a combination of tasks that can all be processed at the same time.
 ///
 /// A batch contains the set of tasks that it represents (accessible through
@@ -68,12 +83,12 @@ pub(crate) enum Batch {
     },
     IndexCreation {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         task: Task,
     },
     IndexUpdate {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         task: Task,
     },
     IndexDeletion {
@@ -81,6 +96,10 @@ pub(crate) enum Batch {
         tasks: Vec<Task>,
         index_has_been_created: bool,
     },
+    IndexCompaction {
+        index_uid: String,
+        task: Task,
+    },
     IndexSwap {
         task: Task,
     },
@@ -91,8 +110,9 @@ pub(crate) enum Batch {
 pub(crate) enum IndexOperation {
     DocumentImport {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         method: IndexDocumentsMethod,
+        autogenerate_ids: bool,
         documents_counts: Vec<u64>,
         content_files: Vec<Uuid>,
         tasks: Vec<Task>,
@@ -103,6 +123,18 @@ pub(crate) enum IndexOperation {
         documents: Vec<Vec<String>>,
         tasks: Vec<Task>,
     },
+    DocumentDeletionByFilter {
+        index_uid: String,
+        task: Task,
+    },
+    DocumentEdition {
+        index_uid: String,
+        task: Task,
+    },
+    DocumentExpiration {
+        index_uid: String,
+        task: Task,
+    },
     DocumentClear {
         index_uid: String,
         tasks: Vec<Task>,
@@ -124,8 +156,9 @@ pub(crate) enum IndexOperation {
     SettingsAndDocumentImport {
         index_uid: String,
 
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         method: IndexDocumentsMethod,
+        autogenerate_ids: bool,
         documents_counts: Vec<u64>,
         content_files: Vec<Uuid>,
         document_import_tasks: Vec<Task>,
@@ -144,7 +177,8 @@ impl Batch {
             | Batch::TaskDeletion(task)
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => vec![task.uid],
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexCompaction { task, .. } => vec![task.uid],
             Batch::SnapshotCreation(tasks) | Batch::IndexDeletion { tasks, .. } => {
                 tasks.iter().map(|task| task.uid).collect()
             }
@@ -165,6 +199,9 @@ impl Batch {
                     settings_tasks: other,
                     ..
                 } => tasks.iter().chain(other).map(|task| task.uid).collect(),
+                IndexOperation::DocumentDeletionByFilter { task, .. }
+                | IndexOperation::DocumentEdition { task, .. }
+                | IndexOperation::DocumentExpiration { task, .. } => vec![task.uid],
             },
             Batch::IndexSwap { task } => vec![task.uid],
         }
@@ -176,6 +213,9 @@ impl IndexOperation {
         match self {
             IndexOperation::DocumentImport { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
+            | IndexOperation::DocumentDeletionByFilter { index_uid, .. }
+            | IndexOperation::DocumentEdition { index_uid, .. }
+            | IndexOperation::DocumentExpiration { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
             | IndexOperation::DocumentClearAndSetting { index_uid, .. }
@@ -206,7 +246,7 @@ impl IndexScheduler {
                 },
                 must_create_index,
             })),
-            BatchKind::DocumentImport { method, import_ids, .. } => {
+            BatchKind::DocumentImport { method, autogenerate_ids, import_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, import_ids)?;
                 let primary_key = match &tasks[0].kind {
                     KindWithContent::DocumentAdditionOrUpdate { primary_key, .. } => {
@@ -236,6 +276,7 @@ impl IndexScheduler {
                         index_uid,
                         primary_key,
                         method,
+                        autogenerate_ids,
                         documents_counts,
                         content_files,
                         tasks,
@@ -261,6 +302,30 @@ impl IndexScheduler {
                     must_create_index,
                 }))
             }
+            BatchKind::DocumentDeletionByFilter { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::DocumentDeletionByFilter { index_uid, task },
+                    must_create_index,
+                }))
+            }
+            BatchKind::DocumentEdition { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::DocumentEdition { index_uid, task },
+                    must_create_index,
+                }))
+            }
+            BatchKind::DocumentExpiration { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::DocumentExpiration { index_uid, task },
+                    must_create_index,
+                }))
+            }
             BatchKind::Settings { settings_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, settings_ids)?;
 
@@ -325,6 +390,7 @@ impl IndexScheduler {
                 settings_ids,
                 method,
                 allow_index_creation,
+                autogenerate_ids,
                 import_ids,
             } => {
                 let settings = self.create_next_batch_index(
@@ -337,7 +403,12 @@ impl IndexScheduler {
                 let document_import = self.create_next_batch_index(
                     rtxn,
                     index_uid.clone(),
-                    BatchKind::DocumentImport { method, allow_index_creation, import_ids },
+                    BatchKind::DocumentImport {
+                        method,
+                        allow_index_creation,
+                        autogenerate_ids,
+                        import_ids,
+                    },
                     must_create_index,
                 )?;
 
@@ -363,6 +434,7 @@ impl IndexScheduler {
                             index_uid,
                             primary_key,
                             method,
+                            autogenerate_ids,
                             documents_counts,
                             content_files,
                             document_import_tasks,
@@ -397,6 +469,10 @@ impl IndexScheduler {
                 index_has_been_created: must_create_index,
                 tasks: self.get_existing_tasks(rtxn, ids)?,
             })),
+            BatchKind::IndexCompaction { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexCompaction { index_uid, task }))
+            }
             BatchKind::IndexSwap { id } => {
                 let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
                 Ok(Some(Batch::IndexSwap { task }))
@@ -421,7 +497,7 @@ impl IndexScheduler {
         if let Some(task_id) = to_cancel.max() {
             // We retrieve the tasks that were processing before this tasks cancelation started.
             // We must *not* reset the processing tasks before calling this method.
-            let ProcessingTasks { started_at, processing } =
+            let ProcessingTasks { started_at, processing, .. } =
                 &*self.processing_tasks.read().unwrap();
             return Ok(Some(Batch::TaskCancelation {
                 task: self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
@@ -573,7 +649,8 @@ impl IndexScheduler {
                     };
 
                 let mut wtxn = self.env.write_txn()?;
-                let deleted_tasks_count = self.delete_matched_tasks(&mut wtxn, matched_tasks)?;
+                let (deleted_tasks_count, reports) =
+                    self.delete_matched_tasks(&mut wtxn, matched_tasks)?;
 
                 task.status = Status::Succeeded;
                 match &mut task.details {
@@ -587,6 +664,16 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
                 wtxn.commit()?;
+
+                // The reports of the deleted tasks are only removed once the deletion is committed.
+                for report in reports {
+                    if let Err(error) = self.delete_update_file(report) {
+                        error!(
+                            "We failed deleting the report file indentified as {}: {}",
+                            report, error
+                        )
+                    }
+                }
                 Ok(vec![task])
             }
             Batch::SnapshotCreation(mut tasks) => {
@@ -631,6 +718,16 @@ impl IndexScheduler {
                     }
                 }
 
+                // 2.5 Copy the reports of the document additions, whatever their status
+                for task_id in self.get_kind(&rtxn, Kind::DocumentAdditionOrUpdate)? {
+                    let task = self.get_task(&rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                    for report in task.details.iter().flat_map(Details::report_uuids) {
+                        let src = self.file_store.get_update_path(report);
+                        let dst = update_files_dir.join(report.to_string());
+                        fs::copy(src, dst)?;
+                    }
+                }
+
                 // 3. Snapshot every indexes
                 // TODO we are opening all of the indexes it can be too much we should unload all
                 //      of the indexes we are trying to open. It would be even better to only unload
@@ -704,6 +801,8 @@ impl IndexScheduler {
                     let (_, mut t) = ret?;
                     let status = t.status;
                     let content_file = t.content_uuid();
+                    let reports: Vec<_> =
+                        t.details.iter().flat_map(Details::report_uuids).collect();
 
                     // In the case we're dumping ourselves we want to be marked as finished
                     // to not loop over ourselves indefinitely.
@@ -741,6 +840,12 @@ impl IndexScheduler {
                             dump_content_file.flush()?;
                         }
                     }
+
+                    // 2.2. Dump the reports of the task, they are kept until the task is deleted.
+                    for report in reports {
+                        let report_file = self.file_store.get_update(report)?;
+                        dump_tasks.push_report(report, report_file)?;
+                    }
                 }
                 dump_tasks.flush()?;
 
@@ -749,7 +854,7 @@ impl IndexScheduler {
                     let rtxn = index.read_txn()?;
                     let metadata = IndexMetadata {
                         uid: uid.clone(),
-                        primary_key: index.primary_key(&rtxn)?.map(String::from),
+                        primary_key: index.primary_key(&rtxn)?,
                         created_at: index.created_at(&rtxn)?,
                         updated_at: index.updated_at(&rtxn)?,
                     };
@@ -765,7 +870,14 @@ impl IndexScheduler {
                         index_dumper.push_document(&document)?;
                     }
 
-                    // 3.2. Dump the settings
+                    // 3.2. Dump the versions of the documents, including the deleted ones
+                    for ret in index.documents_versions(&rtxn)? {
+                        let (id, version) = ret?;
+                        let version = DocumentVersion { id: id.to_string(), version };
+                        index_dumper.push_document_version(&version)?;
+                    }
+
+                    // 3.3. Dump the settings
                     let settings = meilisearch_types::settings::settings(&index, &rtxn)?;
                     index_dumper.settings(&settings)?;
                 }
@@ -795,9 +907,14 @@ impl IndexScheduler {
                 };
 
                 let mut index_wtxn = index.write_txn()?;
-                let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
+                let mut tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
                 index_wtxn.commit()?;
 
+                // The reports are only written once the documents they describe are committed.
+                for task in &mut tasks {
+                    self.write_task_reports(task);
+                }
+
                 Ok(tasks)
             }
             Batch::IndexCreation { index_uid, primary_key, task } => {
@@ -822,10 +939,8 @@ impl IndexScheduler {
                     );
                     builder.set_primary_key(primary_key);
                     let must_stop_processing = self.must_stop_processing.clone();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.get(),
-                    )?;
+                    builder
+                        .execute(self.indexing_step_callback(), || must_stop_processing.get())?;
                     index_wtxn.commit()?;
                 }
                 task.status = Status::Succeeded;
@@ -864,6 +979,18 @@ impl IndexScheduler {
 
                 Ok(tasks)
             }
+            Batch::IndexCompaction { index_uid, mut task } => {
+                let (pre_compaction_size, post_compaction_size) =
+                    self.index_mapper.compact_index(&self.env, &index_uid)?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexCompaction {
+                    pre_compaction_size: Some(pre_compaction_size),
+                    post_compaction_size: Some(post_compaction_size),
+                });
+
+                Ok(vec![task])
+            }
             Batch::IndexSwap { mut task } => {
                 let mut wtxn = self.env.write_txn()?;
                 let swaps = if let KindWithContent::IndexSwap { swaps } = &task.kind {
@@ -978,6 +1105,7 @@ impl IndexScheduler {
                 index_uid: _,
                 primary_key,
                 method,
+                autogenerate_ids,
                 documents_counts,
                 content_files,
                 mut tasks,
@@ -991,38 +1119,61 @@ impl IndexScheduler {
                         let mut builder =
                             milli::update::Settings::new(index_wtxn, index, indexer_config);
                         builder.set_primary_key(primary_key);
-                        builder.execute(
-                            |indexing_step| debug!("update: {:?}", indexing_step),
-                            || must_stop_processing.clone().get(),
-                        )?;
+                        builder.execute(self.indexing_step_callback(), || {
+                            must_stop_processing.clone().get()
+                        })?;
                         primary_key_has_been_set = true;
                     }
                 }
 
-                let config = IndexDocumentsConfig { update_method: method, ..Default::default() };
+                let config = IndexDocumentsConfig {
+                    update_method: method,
+                    autogenerate_docids: autogenerate_ids,
+                    ..Default::default()
+                };
 
                 let mut builder = milli::update::IndexDocuments::new(
                     index_wtxn,
                     index,
                     indexer_config,
                     config,
-                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    self.indexing_step_callback(),
                     || must_stop_processing.get(),
                 )?;
 
                 let mut results = Vec::new();
-                for content_uuid in content_files.into_iter() {
+                for (content_uuid, task) in content_files.into_iter().zip(&tasks) {
+                    if let KindWithContent::DocumentAdditionOrUpdate {
+                        skip_invalid_documents,
+                        ..
+                    } = task.kind
+                    {
+                        builder.set_skip_invalid_documents(skip_invalid_documents);
+                    }
                     let content_file = self.file_store.get_update(content_uuid)?;
                     let reader = DocumentsBatchReader::from_reader(content_file)
                         .map_err(milli::Error::from)?;
                     let (new_builder, user_result) = builder.add_documents(reader)?;
                     builder = new_builder;
+                    let conflicting_documents = builder.take_conflicting_documents();
+                    let generated_documents_ids = builder.take_generated_documents_ids();
+                    let invalid_documents: Vec<_> = builder
+                        .take_invalid_documents()
+                        .into_iter()
+                        .map(|invalid| InvalidDocument {
+                            offset: invalid.offset,
+                            document_id: invalid.document_id,
+                            error: milli::Error::from(invalid.error).into(),
+                        })
+                        .collect();
 
                     let user_result = match user_result {
-                        Ok(count) => Ok(DocumentAdditionResult {
-                            indexed_documents: count,
-                            number_of_documents: count, // TODO: this is wrong, we should use the value stored in the Details.
-                        }),
+                        Ok(count) => Ok((
+                            count,
+                            conflicting_documents,
+                            generated_documents_ids,
+                            invalid_documents,
+                        )),
                         Err(e) => Err(milli::Error::from(e)),
                     };
 
@@ -1032,27 +1183,59 @@ impl IndexScheduler {
                 if results.iter().any(|res| res.is_ok()) {
                     let addition = builder.execute()?;
                     info!("document addition done: {:?}", addition);
+                    // The documents missing a required field once merged are reported by the
+                    // task their last version comes from.
+                    for (batch, invalid) in addition.invalid_documents {
+                        if let Some(Ok((count, _, generated_documents_ids, invalid_documents))) =
+                            results.get_mut(batch)
+                        {
+                            *count = count.saturating_sub(1);
+                            if let Some(document_id) = invalid.document_id.as_ref() {
+                                generated_documents_ids.retain(|id| id != document_id);
+                            }
+                            invalid_documents.push(InvalidDocument {
+                                offset: invalid.offset,
+                                document_id: invalid.document_id,
+                                error: milli::Error::from(invalid.error).into(),
+                            });
+                        }
+                    }
+                    for (_, _, _, invalid_documents) in results.iter_mut().flatten() {
+                        invalid_documents.sort_unstable_by_key(|invalid| invalid.offset);
+                    }
                 } else if primary_key_has_been_set {
                     // Everything failed but we've set a primary key.
                     // We need to remove it.
                     let mut builder =
                         milli::update::Settings::new(index_wtxn, index, indexer_config);
                     builder.reset_primary_key();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.clone().get(),
-                    )?;
+                    builder.execute(self.indexing_step_callback(), || {
+                        must_stop_processing.clone().get()
+                    })?;
                 }
 
                 for (task, (ret, count)) in
                     tasks.iter_mut().zip(results.into_iter().zip(documents_counts))
                 {
                     match ret {
-                        Ok(DocumentAdditionResult { indexed_documents, number_of_documents }) => {
+                        Ok((
+                            indexed_documents,
+                            conflicting_documents,
+                            generated_documents_ids,
+                            invalid_documents,
+                        )) => {
                             task.status = Status::Succeeded;
+                            // The received documents are the ones counted when the task was registered.
+                            // The reports are written by `write_task_reports` once the index is committed.
                             task.details = Some(Details::DocumentAdditionOrUpdate {
-                                received_documents: number_of_documents,
+                                received_documents: count,
                                 indexed_documents: Some(indexed_documents),
+                                conflicting_documents,
+                                conflicting_documents_report: None,
+                                generated_documents_ids,
+                                generated_documents_ids_report: None,
+                                invalid_documents,
+                                invalid_documents_report: None,
                             });
                         }
                         Err(error) => {
@@ -1060,6 +1243,12 @@ impl IndexScheduler {
                             task.details = Some(Details::DocumentAdditionOrUpdate {
                                 received_documents: count,
                                 indexed_documents: Some(count),
+                                conflicting_documents: Vec::new(),
+                                conflicting_documents_report: None,
+                                generated_documents_ids: Vec::new(),
+                                generated_documents_ids_report: None,
+                                invalid_documents: Vec::new(),
+                                invalid_documents_report: None,
                             });
                             task.error = Some(error.into())
                         }
@@ -1086,6 +1275,90 @@ impl IndexScheduler {
 
                 Ok(tasks)
             }
+            IndexOperation::DocumentDeletionByFilter { index_uid: _, mut task } => {
+                let filter = match &task.kind {
+                    KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => filter_expr,
+                    _ => unreachable!(),
+                };
+                let original_filter = filter.to_string();
+                let deleted_documents = delete_documents_by_filter(index_wtxn, index, filter);
+
+                match deleted_documents {
+                    Ok(deleted_documents) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentDeletionByFilter {
+                            original_filter,
+                            deleted_documents: Some(deleted_documents),
+                        });
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentDeletionByFilter {
+                            original_filter,
+                            deleted_documents: Some(0),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
+            IndexOperation::DocumentEdition { index_uid: _, mut task } => {
+                let (filter, function) = match &task.kind {
+                    KindWithContent::DocumentEdition { filter_expr, function, .. } => {
+                        (filter_expr, function)
+                    }
+                    _ => unreachable!(),
+                };
+                let original_filter = filter.as_ref().map(|filter| filter.to_string());
+                let function = function.clone();
+                let must_stop_processing = self.must_stop_processing.clone();
+                let indexer_config = self.index_mapper.indexer_config();
+                let edited_documents = edit_documents_by_function(
+                    index_wtxn,
+                    index,
+                    indexer_config,
+                    filter.as_ref(),
+                    &function,
+                    self.indexing_step_callback(),
+                    || must_stop_processing.get(),
+                );
+
+                match edited_documents {
+                    Ok(edited_documents) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentEdition {
+                            original_filter,
+                            function,
+                            edited_documents: Some(edited_documents),
+                        });
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentEdition {
+                            original_filter,
+                            function,
+                            edited_documents: Some(0),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
+            IndexOperation::DocumentExpiration { index_uid: _, mut task } => {
+                let expired = index.expired_documents_ids(index_wtxn, OffsetDateTime::now_utc())?;
+                let mut builder = milli::update::DeleteDocuments::new(index_wtxn, index)?;
+                builder.delete_documents(&expired);
+                let DocumentDeletionResult { deleted_documents, .. } = builder.execute()?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::DocumentExpiration {
+                    deleted_documents: Some(deleted_documents),
+                });
+
+                Ok(vec![task])
+            }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
@@ -1101,10 +1374,7 @@ impl IndexScheduler {
                 }
 
                 let must_stop_processing = self.must_stop_processing.clone();
-                builder.execute(
-                    |indexing_step| debug!("update: {:?}", indexing_step),
-                    || must_stop_processing.get(),
-                )?;
+                builder.execute(self.indexing_step_callback(), || must_stop_processing.get())?;
 
                 Ok(tasks)
             }
@@ -1112,6 +1382,7 @@ impl IndexScheduler {
                 index_uid,
                 primary_key,
                 method,
+                autogenerate_ids,
                 documents_counts,
                 content_files,
                 document_import_tasks,
@@ -1135,6 +1406,7 @@ impl IndexScheduler {
                         index_uid,
                         primary_key,
                         method,
+                        autogenerate_ids,
                         documents_counts,
                         content_files,
                         tasks: document_import_tasks,
@@ -1173,10 +1445,95 @@ impl IndexScheduler {
         }
     }
 
-    /// Delete each given task from all the databases (if it is deleteable).
+    /// Returns the callback given to the indexer to record its progress on the processing tasks.
+    fn indexing_step_callback(&self) -> impl Fn(UpdateIndexingStep) + Sync {
+        let processing_tasks = self.processing_tasks.clone();
+        move |indexing_step| {
+            debug!("update: {:?}", indexing_step);
+            processing_tasks.write().unwrap().update_indexing_step(indexing_step);
+        }
+    }
+
+    /// Moves the conflicting and generated documents ids and the invalid documents of a
+    /// documents addition to reports, keeping only the first ones in the details of the task.
     ///
-    /// Return the number of tasks that were actually deleted.
-    fn delete_matched_tasks(&self, wtxn: &mut RwTxn, matched_tasks: &RoaringBitmap) -> Result<u64> {
+    /// When a report can't be written all its entries are kept in the details instead.
+    fn write_task_reports(&self, task: &mut Task) {
+        if let Some(Details::DocumentAdditionOrUpdate {
+            conflicting_documents,
+            conflicting_documents_report,
+            generated_documents_ids,
+            generated_documents_ids_report,
+            invalid_documents,
+            invalid_documents_report,
+            ..
+        }) = &mut task.details
+        {
+            if conflicting_documents.len() > MAX_REPORTED_CONFLICTING_DOCUMENTS {
+                match self.write_report(conflicting_documents) {
+                    Ok(report) => {
+                        conflicting_documents.truncate(MAX_REPORTED_CONFLICTING_DOCUMENTS);
+                        *conflicting_documents_report = Some(report);
+                    }
+                    Err(e) => error!(
+                        "Failed to write the conflicting documents report of task {}: {e}",
+                        task.uid
+                    ),
+                }
+            }
+
+            if generated_documents_ids.len() > MAX_REPORTED_GENERATED_DOCUMENTS_IDS {
+                match self.write_report(generated_documents_ids) {
+                    Ok(report) => {
+                        generated_documents_ids.truncate(MAX_REPORTED_GENERATED_DOCUMENTS_IDS);
+                        *generated_documents_ids_report = Some(report);
+                    }
+                    Err(e) => error!(
+                        "Failed to write the generated documents ids report of task {}: {e}",
+                        task.uid
+                    ),
+                }
+            }
+
+            if !invalid_documents.is_empty() {
+                match self.write_report(invalid_documents) {
+                    Ok(report) => {
+                        invalid_documents.truncate(MAX_REPORTED_INVALID_DOCUMENTS);
+                        *invalid_documents_report = Some(report);
+                    }
+                    Err(e) => error!(
+                        "Failed to write the invalid documents report of task {}: {e}",
+                        task.uid
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Writes the entries of a report of a documents addition, one JSON value per line,
+    /// in a new update file and returns its uuid.
+    fn write_report<T: Serialize>(&self, entries: &[T]) -> Result<Uuid> {
+        let (uuid, mut file) = self.file_store.new_update()?;
+        let mut writer = BufWriter::new(&mut *file);
+        for entry in entries {
+            serde_json::to_writer(&mut writer, entry).map_err(io::Error::from)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        drop(writer);
+        file.persist()?;
+        Ok(uuid)
+    }
+
+    /// Deletes the given tasks, if they are deletable, from all the databases.
+    ///
+    /// Returns the number of deleted tasks and the report files that the transaction owner
+    /// must delete if the commit is successful.
+    fn delete_matched_tasks(
+        &self,
+        wtxn: &mut RwTxn,
+        matched_tasks: &RoaringBitmap,
+    ) -> Result<(u64, Vec<Uuid>)> {
         // 1. Remove from this list the tasks that we are not allowed to delete
         let enqueued_tasks = self.get_status(wtxn, Status::Enqueued)?;
         let processing_tasks = &self.processing_tasks.read().unwrap().processing.clone();
@@ -1192,6 +1549,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut reports = Vec::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1213,6 +1571,7 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            reports.extend(task.details.iter().flat_map(Details::report_uuids));
         }
 
         for index in affected_indexes {
@@ -1242,7 +1601,7 @@ impl IndexScheduler {
             }
         }
 
-        Ok(to_delete_tasks.len())
+        Ok((to_delete_tasks.len(), reports))
     }
 
     /// Cancel each given task from all the databases (if it is cancelable).
@@ -1284,3 +1643,61 @@ impl IndexScheduler {
         Ok(content_files_to_delete)
     }
 }
+
+/// Deletes the documents matching the filter and returns the number of deleted documents.
+fn delete_documents_by_filter<'i>(
+    wtxn: &mut RwTxn<'i, '_>,
+    index: &'i Index,
+    filter: &serde_json::Value,
+) -> Result<u64> {
+    let filter = milli::Filter::from_json(filter)?;
+    let candidates = match filter {
+        Some(filter) => filter.evaluate(wtxn, index)?,
+        None => return Ok(0),
+    };
+
+    let mut builder = milli::update::DeleteDocuments::new(wtxn, index)?;
+    builder.delete_documents(&candidates);
+    let DocumentDeletionResult { deleted_documents, .. } = builder.execute()?;
+
+    Ok(deleted_documents)
+}
+
+/// Edits the documents matching the filter, or all the documents when there is no filter,
+/// with the given function and returns the number of edited documents.
+fn edit_documents_by_function<'i>(
+    wtxn: &mut RwTxn<'i, '_>,
+    index: &'i Index,
+    indexer_config: &IndexerConfig,
+    filter: Option<&serde_json::Value>,
+    function: &str,
+    indexing_step_callback: impl Fn(UpdateIndexingStep) + Sync,
+    must_stop_processing: impl Fn() -> bool + Sync,
+) -> Result<u64> {
+    let candidates = match filter.map(milli::Filter::from_json).transpose()?.flatten() {
+        Some(filter) => filter.evaluate(wtxn, index)?,
+        None => index.documents_ids(wtxn)?,
+    };
+
+    let config = IndexDocumentsConfig {
+        update_method: IndexDocumentsMethod::ReplaceDocuments,
+        ..Default::default()
+    };
+    let builder = milli::update::IndexDocuments::new(
+        wtxn,
+        index,
+        indexer_config,
+        config,
+        indexing_step_callback,
+        must_stop_processing,
+    )?;
+
+    let (builder, user_result) = builder.edit_documents(&candidates, function)?;
+    let edited_documents = user_result.map_err(milli::Error::from)?;
+    if edited_documents > 0 {
+        let edition = builder.execute()?;
+        info!("document edition done: {:?}", edition);
+    }
+
+    Ok(edited_documents)
+}
@@ -6,7 +6,7 @@ use std::{fs, thread};
 
 use log::error;
 use meilisearch_types::heed::types::Str;
-use meilisearch_types::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn};
+use meilisearch_types::heed::{CompactionOption, Database, Env, EnvOpenOptions, RoTxn, RwTxn};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::Index;
 use time::OffsetDateTime;
@@ -130,6 +130,67 @@ impl IndexMapper {
         assert!(self.index_mapping.delete(&mut wtxn, name)?);
 
         wtxn.commit()?;
+        self.close_and_remove_index(uuid, name);
+
+        Ok(())
+    }
+
+    /// Compacts the index environment into a new one and atomically replaces
+    /// the old environment with it in the mapping table.
+    ///
+    /// Returns the on-disk size of the index before and after the compaction.
+    pub fn compact_index(&self, env: &Env, name: &str) -> Result<(u64, u64)> {
+        let rtxn = env.read_txn()?;
+        let uuid = self
+            .index_mapping
+            .get(&rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        let index = self.index(&rtxn, name)?;
+        drop(rtxn);
+
+        let pre_compaction_size = index.on_disk_size()?;
+
+        let compacted_uuid = Uuid::new_v4();
+        let compacted_path = self.base_path.join(compacted_uuid.to_string());
+        fs::create_dir_all(&compacted_path)?;
+
+        // When we call copy_to_path, LMDB opens a read transaction by itself,
+        // the copy is consistent as we are the only one writing in the index.
+        let compacted_index = index
+            .copy_to_path(compacted_path.join("data.mdb"), CompactionOption::Enabled)
+            .map_err(Error::from)
+            .and_then(|_| self.create_or_open_index(&compacted_path, None));
+        let compacted_index = match compacted_index {
+            Ok(index) => index,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&compacted_path);
+                return Err(e);
+            }
+        };
+        drop(index);
+
+        let post_compaction_size = compacted_index.on_disk_size()?;
+
+        // We register the compacted index before exposing its uuid in the mapping table
+        // to make sure nobody opens its environment a second time in the meantime.
+        if let Some(BeingDeleted) =
+            self.index_map.write().unwrap().insert(compacted_uuid, Available(compacted_index))
+        {
+            panic!("Uuid v4 conflict.");
+        }
+
+        let mut wtxn = env.write_txn()?;
+        self.index_mapping.put(&mut wtxn, name, &compacted_uuid)?;
+        wtxn.commit()?;
+
+        self.close_and_remove_index(uuid, name);
+
+        Ok((pre_compaction_size, post_compaction_size))
+    }
+
+    /// Marks the index as being deleted in the in-memory index map and removes it
+    /// from disk once all the references to its environment have been dropped.
+    fn close_and_remove_index(&self, uuid: Uuid, name: &str) {
         // We remove the index from the in-memory index map.
         let mut lock = self.index_map.write().unwrap();
         let closing_event = match lock.insert(uuid, BeingDeleted) {
@@ -163,8 +224,6 @@ impl IndexMapper {
                 assert!(matches!(index_map.write().unwrap().remove(&uuid), Some(BeingDeleted)));
             })
             .unwrap();
-
-        Ok(())
     }
 
     pub fn exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
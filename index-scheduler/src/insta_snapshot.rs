@@ -167,8 +167,26 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            conflicting_documents,
+            conflicting_documents_report: _,
+            generated_documents_ids,
+            generated_documents_ids_report: _,
+            invalid_documents,
+            invalid_documents_report: _,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            let mut snap = format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}");
+            if !conflicting_documents.is_empty() {
+                write!(snap, ", conflicting_documents: {conflicting_documents:?}").unwrap();
+            }
+            if !generated_documents_ids.is_empty() {
+                write!(snap, ", generated_documents_ids: {generated_documents_ids:?}").unwrap();
+            }
+            if !invalid_documents.is_empty() {
+                let offsets: Vec<_> = invalid_documents.iter().map(|invalid| invalid.offset).collect();
+                write!(snap, ", invalid_documents: {offsets:?}").unwrap();
+            }
+            snap.push_str(" }");
+            snap
         }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
@@ -180,6 +198,15 @@ fn snapshot_details(d: &Details) -> String {
             provided_ids: received_document_ids,
             deleted_documents,
         } => format!("{{ received_document_ids: {received_document_ids}, deleted_documents: {deleted_documents:?} }}"),
+        Details::DocumentDeletionByFilter { original_filter, deleted_documents } => {
+            format!("{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}")
+        },
+        Details::DocumentEdition { original_filter, function, edited_documents } => {
+            format!("{{ original_filter: {original_filter:?}, function: {function:?}, edited_documents: {edited_documents:?} }}")
+        },
+        Details::DocumentExpiration { deleted_documents } => {
+            format!("{{ deleted_documents: {deleted_documents:?} }}")
+        },
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
@@ -203,6 +230,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::IndexCompaction { pre_compaction_size, post_compaction_size } => {
+            format!("{{ pre_compaction_size: {pre_compaction_size:?}, post_compaction_size: {post_compaction_size:?} }}")
+        }
     }
 }
 
@@ -30,6 +30,7 @@ mod uuid_codec;
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
+use std::io::{self, Read};
 use std::ops::{Bound, RangeBounds};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
@@ -45,7 +46,7 @@ use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{self, Database, Env, RoTxn};
 use meilisearch_types::milli;
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, UpdateIndexingStep};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
 use roaring::RoaringBitmap;
@@ -135,29 +136,99 @@ impl Query {
     }
 }
 
+/// The progress of the tasks that are currently processing, as reported by the indexer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskProgress {
+    /// The name of the current indexing step.
+    pub current_step: &'static str,
+    /// The number of indexing steps that are finished.
+    pub finished_steps: usize,
+    /// The total number of indexing steps.
+    pub total_steps: usize,
+    /// The number of documents the current step processed so far.
+    pub processed_documents: Option<usize>,
+    /// The number of documents the current step must process.
+    pub total_documents: Option<usize>,
+    /// The estimated time before the end of the current step.
+    pub estimated_remaining_time: Option<time::Duration>,
+}
+
 #[derive(Debug, Clone)]
 struct ProcessingTasks {
     /// The date and time at which the indexation started.
     started_at: OffsetDateTime,
     /// The list of tasks ids that are currently running.
     processing: RoaringBitmap,
+    /// The last indexing step reported by the indexer and the date and time at which it started.
+    indexing_step: Option<(UpdateIndexingStep, OffsetDateTime)>,
 }
 
 impl ProcessingTasks {
     /// Creates an empty `ProcessingAt` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks {
+            started_at: OffsetDateTime::now_utc(),
+            processing: RoaringBitmap::new(),
+            indexing_step: None,
+        }
     }
 
     /// Stores the currently processing tasks, and the date time at which it started.
     fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
         self.started_at = started_at;
         self.processing = processing;
+        self.indexing_step = None;
     }
 
     /// Set the processing tasks to an empty list
     fn stop_processing(&mut self) {
         self.processing = RoaringBitmap::new();
+        self.indexing_step = None;
+    }
+
+    /// Stores the last indexing step reported by the indexer for the processing tasks.
+    fn update_indexing_step(&mut self, step: UpdateIndexingStep) {
+        let step_started_at = match self.indexing_step {
+            Some((previous, started_at)) if previous.step() == step.step() => started_at,
+            _ => OffsetDateTime::now_utc(),
+        };
+        self.indexing_step = Some((step, step_started_at));
+    }
+
+    /// Returns the progress of the processing tasks, if the indexer reported any.
+    fn progress(&self) -> Option<TaskProgress> {
+        let (step, step_started_at) = self.indexing_step?;
+        let (processed, total) = match step {
+            UpdateIndexingStep::RemapDocumentAddition { documents_seen } => (documents_seen, None),
+            UpdateIndexingStep::ComputeIdsAndMergeDocuments { documents_seen, total_documents }
+            | UpdateIndexingStep::IndexDocuments { documents_seen, total_documents } => {
+                (documents_seen, Some(total_documents))
+            }
+            UpdateIndexingStep::MergeDataIntoFinalDatabase { databases_seen, total_databases } => {
+                (databases_seen, Some(total_databases))
+            }
+        };
+
+        // The remaining time is extrapolated from the time already spent in the current step.
+        let estimated_remaining_time = match total {
+            Some(total) if processed > 0 => {
+                let elapsed = OffsetDateTime::now_utc() - step_started_at;
+                let remaining = total.saturating_sub(processed);
+                Some(elapsed * (remaining as f64 / processed as f64))
+            }
+            _ => None,
+        };
+
+        let counts_documents =
+            !matches!(step, UpdateIndexingStep::MergeDataIntoFinalDatabase { .. });
+        Some(TaskProgress {
+            current_step: step.name(),
+            finished_steps: step.step(),
+            total_steps: step.number_of_steps(),
+            processed_documents: counts_documents.then_some(processed),
+            total_documents: total.filter(|_| counts_documents),
+            estimated_remaining_time,
+        })
     }
 
     /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
@@ -685,6 +756,11 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the progress of the tasks that are currently processing, if the indexer reported any.
+    pub fn processing_tasks_progress(&self) -> Result<Option<TaskProgress>> {
+        Ok(self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?.progress())
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -751,6 +827,32 @@ impl IndexScheduler {
         Ok(task)
     }
 
+    /// Register a document expiration task for every index containing expired documents
+    /// that isn't already waiting for one, and return the registered tasks.
+    pub fn register_documents_expirations(&self) -> Result<Vec<Task>> {
+        let now = OffsetDateTime::now_utc();
+        let rtxn = self.env.read_txn()?;
+        let enqueued_expirations = self.get_status(&rtxn, Status::Enqueued)?
+            & self.get_kind(&rtxn, Kind::DocumentExpiration)?;
+
+        let mut expired_indexes = Vec::new();
+        for (index_uid, index) in self.index_mapper.indexes(&rtxn)? {
+            if self.index_tasks(&rtxn, &index_uid)?.intersection_len(&enqueued_expirations) > 0 {
+                continue;
+            }
+            let index_rtxn = index.read_txn()?;
+            if !index.expired_documents_ids(&index_rtxn, now)?.is_empty() {
+                expired_indexes.push(index_uid);
+            }
+        }
+        drop(rtxn);
+
+        expired_indexes
+            .into_iter()
+            .map(|index_uid| self.register(KindWithContent::DocumentExpiration { index_uid }))
+            .collect()
+    }
+
     /// Register a new task comming from a dump in the scheduler.
     /// By takinig a mutable ref we're pretty sure no one will ever import a dump while actix is running.
     pub fn register_dumped_task(
@@ -794,6 +896,8 @@ impl IndexScheduler {
                     method,
                     documents_count,
                     allow_index_creation,
+                    autogenerate_ids,
+                    skip_invalid_documents,
                 } => KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
@@ -801,11 +905,29 @@ impl IndexScheduler {
                     content_file: content_uuid.ok_or(Error::CorruptedDump)?,
                     documents_count,
                     allow_index_creation,
+                    autogenerate_ids,
+                    skip_invalid_documents,
                 },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
+                KindDump::DocumentDeletionByFilter { filter } => {
+                    KindWithContent::DocumentDeletionByFilter {
+                        filter_expr: filter,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
+                KindDump::DocumentEdition { filter, function } => {
+                    KindWithContent::DocumentEdition {
+                        filter_expr: filter,
+                        function,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
+                KindDump::DocumentExpiration => KindWithContent::DocumentExpiration {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
@@ -829,6 +951,9 @@ impl IndexScheduler {
                     primary_key,
                 },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
+                KindDump::IndexCompaction => KindWithContent::IndexCompaction {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
                 }
@@ -889,6 +1014,19 @@ impl IndexScheduler {
         Ok(self.file_store.new_update_with_uuid(uuid)?)
     }
 
+    /// Register a report of a task comming from a dump under its original uuid.
+    pub fn register_dumped_report(&self, uuid: Uuid, mut report: impl Read) -> Result<()> {
+        let (_, mut file) = self.file_store.new_update_with_uuid(uuid.as_u128())?;
+        io::copy(&mut report, file.as_file_mut())?;
+        file.persist()?;
+        Ok(())
+    }
+
+    /// Open a file of the index scheduler, e.g. the report of the invalid documents of a task.
+    pub fn get_update_file(&self, uuid: Uuid) -> Result<std::fs::File> {
+        Ok(self.file_store.get_update(uuid)?)
+    }
+
     /// Delete a file from the index scheduler.
     ///
     /// Counterpart to the [`create_update_file`](IndexScheduler::create_update_file) method.
@@ -1080,7 +1218,7 @@ mod tests {
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::tasks::{Details, IndexSwap};
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::{NamedTempFile, TempDir};
     use time::Duration;
@@ -1163,7 +1301,10 @@ mod tests {
 
     /// Return a `KindWithContent::IndexCreation` task
     fn index_creation_task(index: &'static str, primary_key: &'static str) -> KindWithContent {
-        KindWithContent::IndexCreation { index_uid: S(index), primary_key: Some(S(primary_key)) }
+        KindWithContent::IndexCreation {
+            index_uid: S(index),
+            primary_key: Some(primary_key.into()),
+        }
     }
     /// Create a `KindWithContent::DocumentImport` task that imports documents.
     ///
@@ -1181,11 +1322,13 @@ mod tests {
     ) -> KindWithContent {
         KindWithContent::DocumentAdditionOrUpdate {
             index_uid: S(index),
-            primary_key: primary_key.map(ToOwned::to_owned),
+            primary_key: primary_key.map(Into::into),
             method: ReplaceDocuments,
             content_file: Uuid::from_u128(content_file_uuid),
             documents_count,
             allow_index_creation: true,
+            autogenerate_ids: false,
+            skip_invalid_documents: false,
         }
     }
 
@@ -1604,11 +1747,13 @@ mod tests {
         index_scheduler
             .register(KindWithContent::DocumentAdditionOrUpdate {
                 index_uid: S("doggos"),
-                primary_key: Some(S("id")),
+                primary_key: Some(S("id").into()),
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_register");
@@ -1620,6 +1765,53 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "once_everything_is_processed");
     }
 
+    #[test]
+    fn register_documents_expirations() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let content = r#"[
+            { "id": 1, "_expiresAt": "2000-01-01T00:00:00Z" },
+            { "id": 2, "_expiresAt": 946684800 },
+            { "id": 3, "_expiresAt": "2100-01-01T00:00:00Z" },
+            { "id": 4 }
+        ]"#;
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id").into()),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let tasks = index_scheduler.register_documents_expirations().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].index_uid(), Some("doggos"));
+        // The index is already waiting for the expiration of its documents.
+        assert!(index_scheduler.register_documents_expirations().unwrap().is_empty());
+
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, tasks[0].uid).unwrap().unwrap();
+        assert_eq!(task.details, Some(Details::DocumentExpiration { deleted_documents: Some(2) }));
+        drop(rtxn);
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        drop(rtxn);
+        assert!(index_scheduler.register_documents_expirations().unwrap().is_empty());
+    }
+
     #[test]
     fn document_addition_and_index_deletion() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -1641,11 +1833,13 @@ mod tests {
         index_scheduler
             .register(KindWithContent::DocumentAdditionOrUpdate {
                 index_uid: S("doggos"),
-                primary_key: Some(S("id")),
+                primary_key: Some(S("id").into()),
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
@@ -1808,11 +2002,13 @@ mod tests {
         index_scheduler
             .register(KindWithContent::DocumentAdditionOrUpdate {
                 index_uid: S("doggos"),
-                primary_key: Some(S("id")),
+                primary_key: Some(S("id").into()),
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
             })
             .unwrap();
         index_scheduler
@@ -1966,11 +2162,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2013,11 +2211,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: UpdateDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2062,11 +2262,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2112,11 +2314,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2163,11 +2367,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: UpdateDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2208,6 +2414,53 @@ mod tests {
         crate::IndexScheduler::test(true, vec![]);
     }
 
+    #[test]
+    fn processing_tasks_progress() {
+        let mut processing_tasks = ProcessingTasks::new();
+        assert_eq!(processing_tasks.progress(), None);
+
+        processing_tasks
+            .start_processing_at(OffsetDateTime::now_utc(), RoaringBitmap::from_iter([0]));
+        processing_tasks
+            .update_indexing_step(UpdateIndexingStep::RemapDocumentAddition { documents_seen: 10 });
+        let progress = processing_tasks.progress().unwrap();
+        assert_eq!(progress.finished_steps, 0);
+        assert_eq!(progress.total_steps, 4);
+        assert_eq!(progress.processed_documents, Some(10));
+        assert_eq!(progress.total_documents, None);
+        assert_eq!(progress.estimated_remaining_time, None);
+
+        // the step started at the first report and is half done.
+        processing_tasks.update_indexing_step(UpdateIndexingStep::IndexDocuments {
+            documents_seen: 1,
+            total_documents: 2,
+        });
+        let (_, step_started_at) = processing_tasks.indexing_step.unwrap();
+        processing_tasks.indexing_step = Some((
+            UpdateIndexingStep::IndexDocuments { documents_seen: 1, total_documents: 2 },
+            step_started_at - Duration::seconds(60),
+        ));
+        let progress = processing_tasks.progress().unwrap();
+        assert_eq!(progress.finished_steps, 2);
+        assert_eq!(progress.processed_documents, Some(1));
+        assert_eq!(progress.total_documents, Some(2));
+        let remaining = progress.estimated_remaining_time.unwrap();
+        assert!(remaining >= Duration::seconds(60) && remaining < Duration::seconds(70));
+
+        // the databases merged are not reported as documents.
+        processing_tasks.update_indexing_step(UpdateIndexingStep::MergeDataIntoFinalDatabase {
+            databases_seen: 3,
+            total_databases: 3,
+        });
+        let progress = processing_tasks.progress().unwrap();
+        assert_eq!(progress.processed_documents, None);
+        assert_eq!(progress.total_documents, None);
+        assert_eq!(progress.estimated_remaining_time, Some(Duration::ZERO));
+
+        processing_tasks.stop_processing();
+        assert_eq!(processing_tasks.progress(), None);
+    }
+
     #[test]
     fn query_tasks_from_and_limit() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2610,11 +2863,13 @@ mod tests {
         index_scheduler
             .register(KindWithContent::DocumentAdditionOrUpdate {
                 index_uid: S("doggos"),
-                primary_key: Some(S("id")),
+                primary_key: Some(S("id").into()),
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -2648,11 +2903,13 @@ mod tests {
         index_scheduler
             .register(KindWithContent::DocumentAdditionOrUpdate {
                 index_uid: S("doggos"),
-                primary_key: Some(S("id")),
+                primary_key: Some(S("id").into()),
                 method: ReplaceDocuments,
                 content_file: uuid,
                 documents_count,
                 allow_index_creation: true,
+                autogenerate_ids: false,
+                skip_invalid_documents: false,
             })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
@@ -2704,11 +2961,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2752,11 +3011,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2806,11 +3067,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2865,11 +3128,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2929,11 +3194,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2982,11 +3249,13 @@ mod tests {
             index_scheduler
                 .register(KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: S("doggos"),
-                    primary_key: Some(S("id")),
+                    primary_key: Some(S("id").into()),
                     method: ReplaceDocuments,
                     content_file: uuid,
                     documents_count,
                     allow_index_creation,
+                    autogenerate_ids: false,
+                    skip_invalid_documents: false,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -239,11 +239,15 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentExpiration { index_uid } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexCompaction { index_uid } => index_uids.push(index_uid),
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -401,13 +405,34 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::IndexCompaction { pre_compaction_size, post_compaction_size } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexCompaction);
+                        assert_eq!(pre_compaction_size.is_some(), post_compaction_size.is_some());
+                        if pre_compaction_size.is_some() {
+                            assert_eq!(status, Status::Succeeded);
+                        }
+                    }
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        conflicting_documents,
+                        conflicting_documents_report: _,
+                        generated_documents_ids: _,
+                        generated_documents_ids_report: _,
+                        invalid_documents,
+                        invalid_documents_report: _,
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(0) => assert_ne!(status, Status::Enqueued),
                             Some(indexed_documents) => {
                                 assert_eq!(status, Status::Succeeded);
-                                assert!(indexed_documents <= received_documents);
+                                assert!(
+                                    indexed_documents
+                                        + conflicting_documents.len() as u64
+                                        + invalid_documents.len() as u64
+                                        <= received_documents
+                                );
                             }
                             None => {
                                 assert_ne!(status, Status::Succeeded);
@@ -451,6 +476,48 @@ impl IndexScheduler {
                             assert_ne!(status, Status::Succeeded);
                         }
                     }
+                    Details::DocumentDeletionByFilter { deleted_documents, original_filter: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentDeletion);
+                        match &kind {
+                            KindWithContent::DocumentDeletionByFilter { index_uid, .. } => {
+                                assert_eq!(&task_index_uid.unwrap(), index_uid);
+                            }
+                            _ => panic!(),
+                        }
+                        if deleted_documents.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        } else {
+                            assert_ne!(status, Status::Succeeded);
+                        }
+                    }
+                    Details::DocumentEdition { edited_documents, .. } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        match &kind {
+                            KindWithContent::DocumentEdition { index_uid, .. } => {
+                                assert_eq!(&task_index_uid.unwrap(), index_uid);
+                            }
+                            _ => panic!(),
+                        }
+                        if edited_documents.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        } else {
+                            assert_ne!(status, Status::Succeeded);
+                        }
+                    }
+                    Details::DocumentExpiration { deleted_documents } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentExpiration);
+                        match &kind {
+                            KindWithContent::DocumentExpiration { index_uid } => {
+                                assert_eq!(&task_index_uid.unwrap(), index_uid);
+                            }
+                            _ => panic!(),
+                        }
+                        if deleted_documents.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        } else {
+                            assert_ne!(status, Status::Succeeded);
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
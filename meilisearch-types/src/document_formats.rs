@@ -5,7 +5,7 @@ use std::io::{self, Seek, Write};
 use std::marker::PhantomData;
 
 use memmap2::MmapOptions;
-use milli::documents::{DocumentsBatchBuilder, Error};
+use milli::documents::{DocumentsBatchBuilder, Error, DEFAULT_CSV_ARRAY_SEPARATOR};
 use milli::Object;
 use serde::de::{SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
@@ -32,6 +32,31 @@ impl fmt::Display for PayloadType {
     }
 }
 
+/// How the fields, the quoted values and the array items of a CSV payload are delimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Whether the quotes are interpreted, when disabled they are read as any other character.
+    pub quoting: bool,
+    /// The character escaping the quotes inside of the quoted values, in addition to doubling them.
+    pub escape: Option<u8>,
+    /// The separator of the items of the `:array` columns.
+    pub array_separator: String,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect {
+            delimiter: b',',
+            quote: b'"',
+            quoting: true,
+            escape: None,
+            array_separator: DEFAULT_CSV_ARRAY_SEPARATOR.to_string(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum DocumentFormatError {
     Io(io::Error),
@@ -104,12 +129,19 @@ impl ErrorCode for DocumentFormatError {
     }
 }
 
-/// Reads CSV from input and write an obkv batch to writer.
-pub fn read_csv(file: &File, writer: impl Write + Seek) -> Result<u64> {
+/// Reads CSV written in the given dialect from input and write an obkv batch to writer.
+pub fn read_csv(file: &File, writer: impl Write + Seek, dialect: &CsvDialect) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(writer);
     let mmap = unsafe { MmapOptions::new().map(file)? };
-    let csv = csv::Reader::from_reader(mmap.as_ref());
-    builder.append_csv(csv).map_err(|e| (PayloadType::Csv, e))?;
+    let csv = csv::ReaderBuilder::new()
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .quoting(dialect.quoting)
+        .escape(dialect.escape)
+        .from_reader(mmap.as_ref());
+    builder
+        .append_csv_with_array_separator(csv, &dialect.array_separator)
+        .map_err(|e| (PayloadType::Csv, e))?;
 
     let count = builder.documents_count();
     let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
@@ -10,7 +10,7 @@ use deserr::{DeserializeError, IntoValue, MergeWithError, ValuePointerRef};
 use milli::heed::{Error as HeedError, MdbError};
 use serde::{Deserialize, Serialize};
 
-use self::deserr_codes::MissingIndexUid;
+use self::deserr_codes::{MissingDocumentEditFunction, MissingDocumentFilter, MissingIndexUid};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -176,6 +176,8 @@ ApiKeyNotFound                        , invalid       , NOT_FOUND ;
 BadParameter                          , invalid       , BAD_REQUEST;
 BadRequest                            , invalid       , BAD_REQUEST;
 DatabaseSizeLimitReached              , internal      , INTERNAL_SERVER_ERROR;
+DecompressedPayloadTooLarge           , invalid       , PAYLOAD_TOO_LARGE ;
+DocumentEditionFailed                 , invalid       , BAD_REQUEST ;
 DocumentNotFound                      , invalid       , NOT_FOUND;
 DumpAlreadyProcessing                 , invalid       , CONFLICT;
 DumpNotFound                          , invalid       , NOT_FOUND;
@@ -211,11 +213,31 @@ InvalidApiKeyOffset                   , invalid       , BAD_REQUEST ;
 InvalidApiKeyUid                      , invalid       , BAD_REQUEST ;
 InvalidApiKey                         , authentication, FORBIDDEN ;
 InvalidContentType                    , invalid       , UNSUPPORTED_MEDIA_TYPE ;
+InvalidDocumentArrayMergeStrategy     , invalid       , BAD_REQUEST ;
+InvalidDocumentAutogenerateIds        , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvArraySeparator      , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvDelimiter           , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvDialect             , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvEscape              , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvQuote               , invalid       , BAD_REQUEST ;
+InvalidDocumentCsvQuoting             , invalid       , BAD_REQUEST ;
+InvalidDocumentDeleteFilter           , invalid       , BAD_REQUEST ;
+InvalidDocumentEditFilter             , invalid       , BAD_REQUEST ;
+InvalidDocumentEditFunction           , invalid       , BAD_REQUEST ;
+InvalidDocumentExpiresAt              , invalid       , BAD_REQUEST ;
+InvalidDocumentExportFormat           , invalid       , BAD_REQUEST ;
 InvalidDocumentFields                 , invalid       , BAD_REQUEST ;
+InvalidDocumentFilter                 , invalid       , BAD_REQUEST ;
 InvalidDocumentGeoField               , invalid       , BAD_REQUEST ;
 InvalidDocumentId                     , invalid       , BAD_REQUEST ;
+InvalidDocumentIds                    , invalid       , BAD_REQUEST ;
 InvalidDocumentLimit                  , invalid       , BAD_REQUEST ;
+InvalidDocumentMergeStrategy          , invalid       , BAD_REQUEST ;
 InvalidDocumentOffset                 , invalid       , BAD_REQUEST ;
+InvalidDocumentRetrieveVersion        , invalid       , BAD_REQUEST ;
+InvalidDocumentSchema                 , invalid       , BAD_REQUEST ;
+InvalidDocumentSkipInvalidDocuments   , invalid       , BAD_REQUEST ;
+InvalidDocumentVersion                , invalid       , BAD_REQUEST ;
 InvalidIndexLimit                     , invalid       , BAD_REQUEST ;
 InvalidIndexOffset                    , invalid       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , invalid       , BAD_REQUEST ;
@@ -232,20 +254,29 @@ InvalidSearchHighlightPostTag         , invalid       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , invalid       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , invalid       , BAD_REQUEST ;
 InvalidSearchLimit                    , invalid       , BAD_REQUEST ;
+InvalidSearchLocales                  , invalid       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , invalid       , BAD_REQUEST ;
 InvalidSearchOffset                   , invalid       , BAD_REQUEST ;
 InvalidSearchPage                     , invalid       , BAD_REQUEST ;
 InvalidSearchQ                        , invalid       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , invalid       , BAD_REQUEST ;
 InvalidSearchSort                     , invalid       , BAD_REQUEST ;
+InvalidSettingsDateAttributes         , invalid       , BAD_REQUEST ;
+InvalidSettingsDictionary             , invalid       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , invalid       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , invalid       , BAD_REQUEST ;
 InvalidSettingsFaceting               , invalid       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , invalid       , BAD_REQUEST ;
+InvalidSettingsLocalizedAttributes    , invalid       , BAD_REQUEST ;
+InvalidSettingsNonSeparatorTokens     , invalid       , BAD_REQUEST ;
 InvalidSettingsPagination             , invalid       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , invalid       , BAD_REQUEST ;
+InvalidSettingsRules                  , invalid       , BAD_REQUEST ;
+InvalidSettingsSchema                 , invalid       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , invalid       , BAD_REQUEST ;
+InvalidSettingsSeparatorTokens        , invalid       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , invalid       , BAD_REQUEST ;
+InvalidSettingsStemmingLanguages      , invalid       , BAD_REQUEST ;
 InvalidSettingsStopWords              , invalid       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , invalid       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , invalid       , BAD_REQUEST ;
@@ -273,6 +304,8 @@ MissingApiKeyExpiresAt                , invalid       , BAD_REQUEST ;
 MissingApiKeyIndexes                  , invalid       , BAD_REQUEST ;
 MissingAuthorizationHeader            , authentication, UNAUTHORIZED ;
 MissingContentType                    , invalid       , UNSUPPORTED_MEDIA_TYPE ;
+MissingDocumentEditFunction           , invalid       , BAD_REQUEST ;
+MissingDocumentFilter                 , invalid       , BAD_REQUEST ;
 MissingDocumentId                     , invalid       , BAD_REQUEST ;
 MissingIndexUid                       , invalid       , BAD_REQUEST ;
 MissingMasterKey                      , authentication, UNAUTHORIZED ;
@@ -337,7 +370,9 @@ impl ErrorCode for milli::Error {
                     UserError::NoSpaceLeftOnDevice => Code::NoSpaceLeftOnDevice,
                     UserError::MaxDatabaseSizeReached => Code::DatabaseSizeLimitReached,
                     UserError::AttributeLimitReached => Code::MaxFieldsLimitExceeded,
-                    UserError::InvalidFilter(_) => Code::InvalidSearchFilter,
+                    UserError::InvalidFilter(_) | UserError::InvalidFilterExpression(..) => {
+                        Code::InvalidSearchFilter
+                    }
                     UserError::MissingDocumentId { .. } => Code::MissingDocumentId,
                     UserError::InvalidDocumentId { .. } | UserError::TooManyDocumentIds { .. } => {
                         Code::InvalidDocumentId
@@ -352,10 +387,20 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidDocumentVersion { .. } => Code::InvalidDocumentVersion,
+                    UserError::InvalidDocumentExpiresAt { .. } => Code::InvalidDocumentExpiresAt,
+                    UserError::InvalidDocumentSchema { .. } => Code::InvalidDocumentSchema,
+                    UserError::InvalidSchemaField { .. } => Code::InvalidSettingsSchema,
                     UserError::SortError(_) => Code::InvalidSearchSort,
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidMinWordLengthForTypo
                     }
+                    UserError::DocumentEditionCompilationError(_) => {
+                        Code::InvalidDocumentEditFunction
+                    }
+                    UserError::DocumentEditionRuntimeError(_)
+                    | UserError::DocumentEditionCannotModifyPrimaryKey
+                    | UserError::DocumentEditionDocumentMustBeObject => Code::DocumentEditionFailed,
                 }
             }
         }
@@ -467,6 +512,28 @@ impl DeserrError<MissingIndexUid> {
     }
 }
 
+impl DeserrError<MissingDocumentFilter> {
+    pub fn missing_document_filter(field: &str, location: ValuePointerRef) -> Self {
+        let x = unwrap_any(Self::error::<Infallible>(
+            None,
+            deserr::ErrorKind::MissingField { field },
+            location,
+        ));
+        Self { msg: x.msg, code: MissingDocumentFilter.error_code(), _phantom: PhantomData }
+    }
+}
+
+impl DeserrError<MissingDocumentEditFunction> {
+    pub fn missing_document_edit_function(field: &str, location: ValuePointerRef) -> Self {
+        let x = unwrap_any(Self::error::<Infallible>(
+            None,
+            deserr::ErrorKind::MissingField { field },
+            location,
+        ));
+        Self { msg: x.msg, code: MissingDocumentEditFunction.error_code(), _phantom: PhantomData }
+    }
+}
+
 impl<C: Default + ErrorCode> deserr::DeserializeError for DeserrError<C> {
     fn error<V: IntoValue>(
         _self_: Option<Self>,
@@ -7,8 +7,12 @@ use std::str::FromStr;
 
 use deserr::{DeserializeError, DeserializeFromValue, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
+use milli::index::MinWordLenForTypo;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, CurationRule, Index, LocalizedAttributesRule, Schema,
+    StemmingLanguage, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::error::deserr_codes::*;
@@ -87,6 +91,11 @@ pub struct TypoSettings {
     pub disable_on_words: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidMinWordLengthForTypo>)]
+    pub min_word_size_for_typos_per_attribute: Setting<BTreeMap<String, MinWordSizeTyposSetting>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    pub disable_on_numbers: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, DeserializeFromValue)]
@@ -153,12 +162,24 @@ pub struct Settings<T> {
     #[deserr(error = DeserrError<InvalidSettingsSortableAttributes>)]
     pub sortable_attributes: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsDateAttributes>)]
+    pub date_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(error = DeserrError<InvalidSettingsRankingRules>)]
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(error = DeserrError<InvalidSettingsStopWords>)]
     pub stop_words: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsSeparatorTokens>)]
+    pub separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsNonSeparatorTokens>)]
+    pub non_separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsDictionary>)]
+    pub dictionary: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(error = DeserrError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -173,6 +194,18 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(error = DeserrError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsRules>)]
+    pub rules: Setting<Vec<CurationRule>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRule>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsStemmingLanguages>)]
+    pub stemming_languages: Setting<Vec<StemmingLanguage>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(error = DeserrError<InvalidSettingsSchema>)]
+    pub schema: Setting<Schema>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -186,13 +219,21 @@ impl Settings<Checked> {
             searchable_attributes: Setting::Reset,
             filterable_attributes: Setting::Reset,
             sortable_attributes: Setting::Reset,
+            date_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
+            separator_tokens: Setting::Reset,
+            non_separator_tokens: Setting::Reset,
+            dictionary: Setting::Reset,
             synonyms: Setting::Reset,
             distinct_attribute: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            rules: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            stemming_languages: Setting::Reset,
+            schema: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -203,13 +244,21 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            date_attributes,
             ranking_rules,
             stop_words,
+            separator_tokens,
+            non_separator_tokens,
+            dictionary,
             synonyms,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            rules,
+            localized_attributes,
+            stemming_languages,
+            schema,
             ..
         } = self;
 
@@ -218,13 +267,21 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            date_attributes,
             ranking_rules,
             stop_words,
+            separator_tokens,
+            non_separator_tokens,
+            dictionary,
             synonyms,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            rules,
+            localized_attributes,
+            stemming_languages,
+            schema,
             _kind: PhantomData,
         }
     }
@@ -259,13 +316,21 @@ impl Settings<Unchecked> {
             searchable_attributes,
             filterable_attributes: self.filterable_attributes,
             sortable_attributes: self.sortable_attributes,
+            date_attributes: self.date_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
+            separator_tokens: self.separator_tokens,
+            non_separator_tokens: self.non_separator_tokens,
+            dictionary: self.dictionary,
             synonyms: self.synonyms,
             distinct_attribute: self.distinct_attribute,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            rules: self.rules,
+            localized_attributes: self.localized_attributes,
+            stemming_languages: self.stemming_languages,
+            schema: self.schema,
             _kind: PhantomData,
         }
     }
@@ -309,6 +374,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.date_attributes {
+        Setting::Set(ref fields) => builder.set_date_fields(fields.iter().cloned().collect()),
+        Setting::Reset => builder.reset_date_fields(),
+        Setting::NotSet => (),
+    }
+
     match settings.ranking_rules {
         Setting::Set(ref criteria) => {
             builder.set_criteria(criteria.iter().map(|c| c.clone().into()).collect())
@@ -323,6 +394,24 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.separator_tokens {
+        Setting::Set(ref tokens) => builder.set_separator_tokens(tokens.clone()),
+        Setting::Reset => builder.reset_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.non_separator_tokens {
+        Setting::Set(ref tokens) => builder.set_non_separator_tokens(tokens.clone()),
+        Setting::Reset => builder.reset_non_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.dictionary {
+        Setting::Set(ref words) => builder.set_dictionary(words.clone()),
+        Setting::Reset => builder.reset_dictionary(),
+        Setting::NotSet => (),
+    }
+
     match settings.synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
@@ -378,6 +467,30 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.min_word_size_for_typos_per_attribute {
+                Setting::Set(ref attributes) => {
+                    let attributes = attributes
+                        .iter()
+                        .map(|(attr, setting)| {
+                            let len = MinWordLenForTypo {
+                                one_typo: setting.one_typo.set(),
+                                two_typos: setting.two_typos.set(),
+                            };
+                            (attr.clone(), len)
+                        })
+                        .collect();
+                    builder.set_attributes_min_word_len_for_typo(attributes)
+                }
+                Setting::Reset => builder.reset_attributes_min_word_len_for_typo(),
+                Setting::NotSet => (),
+            }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -386,6 +499,8 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_attributes_min_word_len_for_typo();
+            builder.reset_disable_typos_on_numbers();
         }
         Setting::NotSet => (),
     }
@@ -409,6 +524,30 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.rules {
+        Setting::Set(ref rules) => builder.set_curation_rules(rules.clone()),
+        Setting::Reset => builder.reset_curation_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => builder.set_localized_attributes_rules(rules.clone()),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming_languages {
+        Setting::Set(ref languages) => builder.set_stemming_languages(languages.clone()),
+        Setting::Reset => builder.reset_stemming_languages(),
+        Setting::NotSet => (),
+    }
+
+    match settings.schema {
+        Setting::Set(ref schema) => builder.set_schema(schema.clone()),
+        Setting::Reset => builder.reset_schema(),
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -426,6 +565,8 @@ pub fn settings(
 
     let sortable_attributes = index.sortable_fields(rtxn)?.into_iter().collect();
 
+    let date_attributes = index.date_fields(rtxn)?.into_iter().collect();
+
     let criteria = index.criteria(rtxn)?;
 
     let stop_words = index
@@ -435,6 +576,13 @@ pub fn settings(
         })
         .transpose()?
         .unwrap_or_default();
+    let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
+    let separator_tokens: BTreeSet<_> = separator_tokens.into_iter().map(String::from).collect();
+    let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
+    let non_separator_tokens: BTreeSet<_> =
+        non_separator_tokens.into_iter().map(String::from).collect();
+    let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
+    let dictionary: BTreeSet<_> = dictionary.into_iter().map(String::from).collect();
     let distinct_field = index.distinct_field(rtxn)?.map(String::from);
 
     // in milli each word in the synonyms map were split on their separator. Since we lost
@@ -457,11 +605,25 @@ pub fn settings(
 
     let disabled_attributes = index.exact_attributes(rtxn)?.into_iter().map(String::from).collect();
 
+    let min_typo_word_len_per_attribute = index
+        .attributes_min_word_len_for_typo(rtxn)?
+        .into_iter()
+        .map(|(attr, len)| {
+            let setting = MinWordSizeTyposSetting {
+                one_typo: len.one_typo.map_or(Setting::NotSet, Setting::Set),
+                two_typos: len.two_typos.map_or(Setting::NotSet, Setting::Set),
+            };
+            (attr, setting)
+        })
+        .collect();
+
     let typo_tolerance = TypoSettings {
         enabled: Setting::Set(index.authorize_typos(rtxn)?),
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        min_word_size_for_typos_per_attribute: Setting::Set(min_typo_word_len_per_attribute),
+        disable_on_numbers: Setting::Set(index.disable_typos_on_numbers(rtxn)?),
     };
 
     let faceting = FacetingSettings {
@@ -476,6 +638,14 @@ pub fn settings(
         ),
     };
 
+    let rules = index.curation_rules(rtxn)?;
+
+    let localized_attributes = index.localized_attributes_rules(rtxn)?;
+
+    let stemming_languages = index.stemming_languages(rtxn)?;
+
+    let schema = index.schema(rtxn)?;
+
     Ok(Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -487,8 +657,12 @@ pub fn settings(
         },
         filterable_attributes: Setting::Set(filterable_attributes),
         sortable_attributes: Setting::Set(sortable_attributes),
+        date_attributes: Setting::Set(date_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
+        separator_tokens: Setting::Set(separator_tokens),
+        non_separator_tokens: Setting::Set(non_separator_tokens),
+        dictionary: Setting::Set(dictionary),
         distinct_attribute: match distinct_field {
             Some(field) => Setting::Set(field),
             None => Setting::Reset,
@@ -497,6 +671,10 @@ pub fn settings(
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        rules: Setting::Set(rules),
+        localized_attributes: Setting::Set(localized_attributes),
+        stemming_languages: Setting::Set(stemming_languages),
+        schema: Setting::Set(schema),
         _kind: PhantomData,
     })
 }
@@ -609,13 +787,21 @@ pub(crate) mod test {
             searchable_attributes: Setting::Set(vec![String::from("hello")]),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            rules: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            schema: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -630,13 +816,21 @@ pub(crate) mod test {
             searchable_attributes: Setting::Set(vec![String::from("hello"), String::from("*")]),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            rules: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            schema: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -4,6 +4,7 @@ use std::str::FromStr;
 
 use enum_iterator::Sequence;
 use milli::update::IndexDocumentsMethod;
+use milli::PrimaryKey;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
@@ -48,10 +49,14 @@ impl Task {
             | IndexSwap { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
+            | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
+            | DocumentExpiration { index_uid }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => Some(index_uid),
         }
     }
@@ -66,11 +71,15 @@ impl Task {
         match self.kind {
             KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
+            | KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentEdition { .. }
+            | KindWithContent::DocumentExpiration { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
+            | KindWithContent::IndexCompaction { .. }
             | KindWithContent::IndexSwap { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -85,16 +94,34 @@ impl Task {
 pub enum KindWithContent {
     DocumentAdditionOrUpdate {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
         method: IndexDocumentsMethod,
         content_file: Uuid,
         documents_count: u64,
         allow_index_creation: bool,
+        /// Generate a UUIDv4 for the documents missing the primary key.
+        #[serde(default)]
+        autogenerate_ids: bool,
+        /// Leave the invalid documents out instead of failing the whole task.
+        #[serde(default)]
+        skip_invalid_documents: bool,
     },
     DocumentDeletion {
         index_uid: String,
         documents_ids: Vec<String>,
     },
+    DocumentDeletionByFilter {
+        index_uid: String,
+        filter_expr: serde_json::Value,
+    },
+    DocumentEdition {
+        index_uid: String,
+        filter_expr: Option<serde_json::Value>,
+        function: String,
+    },
+    DocumentExpiration {
+        index_uid: String,
+    },
     DocumentClear {
         index_uid: String,
     },
@@ -109,11 +136,14 @@ pub enum KindWithContent {
     },
     IndexCreation {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
     },
     IndexUpdate {
         index_uid: String,
-        primary_key: Option<String>,
+        primary_key: Option<PrimaryKey>,
+    },
+    IndexCompaction {
+        index_uid: String,
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
@@ -139,16 +169,30 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// A document that was left out of a documents addition because it is invalid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidDocument {
+    /// The position of the document in the payload.
+    pub offset: u32,
+    pub document_id: Option<String>,
+    pub error: ResponseError,
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
+            KindWithContent::DocumentExpiration { .. } => Kind::DocumentExpiration,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
+            KindWithContent::IndexCompaction { .. } => Kind::IndexCompaction,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
@@ -167,10 +211,14 @@ impl KindWithContent {
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
+            | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
+            | DocumentExpiration { index_uid }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => vec![index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
@@ -191,6 +239,12 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    conflicting_documents: Vec::new(),
+                    conflicting_documents_report: None,
+                    generated_documents_ids: Vec::new(),
+                    generated_documents_ids_report: None,
+                    invalid_documents: Vec::new(),
+                    invalid_documents_report: None,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -199,6 +253,22 @@ impl KindWithContent {
                     deleted_documents: None,
                 })
             }
+            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+                Some(Details::DocumentDeletionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    deleted_documents: None,
+                })
+            }
+            KindWithContent::DocumentEdition { index_uid: _, filter_expr, function } => {
+                Some(Details::DocumentEdition {
+                    original_filter: filter_expr.as_ref().map(|filter| filter.to_string()),
+                    function: function.clone(),
+                    edited_documents: None,
+                })
+            }
+            KindWithContent::DocumentExpiration { .. } => {
+                Some(Details::DocumentExpiration { deleted_documents: None })
+            }
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -209,6 +279,10 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
@@ -233,6 +307,12 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    conflicting_documents: Vec::new(),
+                    conflicting_documents_report: None,
+                    generated_documents_ids: Vec::new(),
+                    generated_documents_ids_report: None,
+                    invalid_documents: Vec::new(),
+                    invalid_documents_report: None,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -241,6 +321,22 @@ impl KindWithContent {
                     deleted_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+                Some(Details::DocumentDeletionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    deleted_documents: Some(0),
+                })
+            }
+            KindWithContent::DocumentEdition { index_uid: _, filter_expr, function } => {
+                Some(Details::DocumentEdition {
+                    original_filter: filter_expr.as_ref().map(|filter| filter.to_string()),
+                    function: function.clone(),
+                    edited_documents: Some(0),
+                })
+            }
+            KindWithContent::DocumentExpiration { .. } => {
+                Some(Details::DocumentExpiration { deleted_documents: Some(0) })
+            }
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -252,6 +348,10 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
@@ -278,9 +378,31 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    conflicting_documents: Vec::new(),
+                    conflicting_documents_report: None,
+                    generated_documents_ids: Vec::new(),
+                    generated_documents_ids_report: None,
+                    invalid_documents: Vec::new(),
+                    invalid_documents_report: None,
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
+            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+                Some(Details::DocumentDeletionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    deleted_documents: None,
+                })
+            }
+            KindWithContent::DocumentEdition { index_uid: _, filter_expr, function } => {
+                Some(Details::DocumentEdition {
+                    original_filter: filter_expr.as_ref().map(|filter| filter.to_string()),
+                    function: function.clone(),
+                    edited_documents: None,
+                })
+            }
+            KindWithContent::DocumentExpiration { .. } => {
+                Some(Details::DocumentExpiration { deleted_documents: None })
+            }
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
@@ -292,6 +414,10 @@ impl From<&KindWithContent> for Option<Details> {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => Some(Details::IndexCompaction {
+                pre_compaction_size: None,
+                post_compaction_size: None,
+            }),
             KindWithContent::IndexSwap { .. } => None,
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
@@ -366,11 +492,14 @@ impl FromStr for Status {
 pub enum Kind {
     DocumentAdditionOrUpdate,
     DocumentDeletion,
+    DocumentEdition,
+    DocumentExpiration,
     SettingsUpdate,
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    IndexCompaction,
     TaskCancelation,
     TaskDeletion,
     DumpCreation,
@@ -382,10 +511,13 @@ impl Kind {
         match self {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentDeletion
+            | Kind::DocumentEdition
+            | Kind::DocumentExpiration
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexCompaction => true,
             Kind::IndexSwap
             | Kind::TaskCancelation
             | Kind::TaskDeletion
@@ -399,11 +531,14 @@ impl Display for Kind {
         match self {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
+            Kind::DocumentEdition => write!(f, "documentEdition"),
+            Kind::DocumentExpiration => write!(f, "documentExpiration"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
             Kind::IndexUpdate => write!(f, "indexUpdate"),
             Kind::IndexSwap => write!(f, "indexSwap"),
+            Kind::IndexCompaction => write!(f, "indexCompaction"),
             Kind::TaskCancelation => write!(f, "taskCancelation"),
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
@@ -421,12 +556,18 @@ impl FromStr for Kind {
             Ok(Kind::IndexUpdate)
         } else if kind.eq_ignore_ascii_case("indexSwap") {
             Ok(Kind::IndexSwap)
+        } else if kind.eq_ignore_ascii_case("indexCompaction") {
+            Ok(Kind::IndexCompaction)
         } else if kind.eq_ignore_ascii_case("indexDeletion") {
             Ok(Kind::IndexDeletion)
         } else if kind.eq_ignore_ascii_case("documentAdditionOrUpdate") {
             Ok(Kind::DocumentAdditionOrUpdate)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentEdition") {
+            Ok(Kind::DocumentEdition)
+        } else if kind.eq_ignore_ascii_case("documentExpiration") {
+            Ok(Kind::DocumentExpiration)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
@@ -459,15 +600,73 @@ impl FromStr for Kind {
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        /// The ids of the documents that were not applied because of a `_version` conflict.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        conflicting_documents: Vec<String>,
+        /// The update file listing all the conflicting ids, when there are too many to be kept here.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        conflicting_documents_report: Option<Uuid>,
+        /// The first ids that were generated for the documents missing the primary key.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        generated_documents_ids: Vec<String>,
+        /// The update file listing all the generated ids, when there are too many to be kept here.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        generated_documents_ids_report: Option<Uuid>,
+        /// The first documents that were left out because they are invalid.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        invalid_documents: Vec<InvalidDocument>,
+        /// The update file listing all the documents that were left out.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        invalid_documents_report: Option<Uuid>,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+    },
+    IndexInfo {
+        primary_key: Option<PrimaryKey>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    DocumentEdition {
+        original_filter: Option<String>,
+        function: String,
+        edited_documents: Option<u64>,
+    },
+    DocumentExpiration {
+        deleted_documents: Option<u64>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
+    IndexCompaction {
+        pre_compaction_size: Option<u64>,
+        post_compaction_size: Option<u64>,
+    },
 }
 
 impl Details {
@@ -478,17 +677,41 @@ impl Details {
                 *indexed_documents = Some(0)
             }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
+            Self::DocumentDeletionByFilter { deleted_documents, .. } => {
+                *deleted_documents = Some(0)
+            }
+            Self::DocumentEdition { edited_documents, .. } => *edited_documents = Some(0),
+            Self::DocumentExpiration { deleted_documents } => *deleted_documents = Some(0),
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexSwap { .. }
+            | Self::IndexCompaction { .. } => (),
         }
 
         details
     }
+
+    /// Returns the uuids of the report files of the task, if there are any.
+    pub fn report_uuids(&self) -> Vec<Uuid> {
+        match self {
+            Self::DocumentAdditionOrUpdate {
+                conflicting_documents_report,
+                generated_documents_ids_report,
+                invalid_documents_report,
+                ..
+            } => conflicting_documents_report
+                .iter()
+                .chain(generated_documents_ids_report)
+                .chain(invalid_documents_report)
+                .copied()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 /// Serialize a `time::Duration` as a best effort ISO 8601 while waiting for
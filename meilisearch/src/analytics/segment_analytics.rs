@@ -26,7 +26,7 @@ use uuid::Uuid;
 use super::{config_user_id_path, DocumentDeletionKind, MEILISEARCH_CONFIG_PATH};
 use crate::analytics::Analytics;
 use crate::option::{default_http_addr, IndexerOpts, MaxMemory, MaxThreads, ScheduleSnapshot};
-use crate::routes::indexes::documents::UpdateDocumentsQuery;
+use crate::routes::indexes::documents::{MergeStrategy, UpdateDocumentsQuery};
 use crate::routes::tasks::TasksFilterQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
@@ -479,6 +479,9 @@ pub struct SearchAggregator {
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
 
+    // locales
+    locales: HashSet<String>,
+
     // pagination
     max_limit: usize,
     max_offset: usize,
@@ -553,6 +556,10 @@ impl SearchAggregator {
 
         ret.matching_strategy.insert(format!("{:?}", query.matching_strategy), 1);
 
+        if let Some(ref locales) = query.locales {
+            ret.locales = locales.iter().map(|locale| format!("{:?}", locale)).collect();
+        }
+
         ret.highlight_pre_tag = query.highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = query.highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
         ret.crop_marker = query.crop_marker != DEFAULT_CROP_MARKER();
@@ -632,6 +639,9 @@ impl SearchAggregator {
             let matching_strategy = self.matching_strategy.entry(key).or_insert(0);
             *matching_strategy = matching_strategy.saturating_add(value);
         }
+
+        // locales
+        self.locales.extend(other.locales);
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -685,7 +695,8 @@ impl SearchAggregator {
                 },
                 "matching_strategy": {
                     "most_used_strategy": self.matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
-                }
+                },
+                "locales": self.locales,
             });
 
             Some(Track {
@@ -712,6 +723,10 @@ pub struct DocumentsAggregator {
     content_types: HashSet<String>,
     primary_keys: HashSet<String>,
     index_creation: bool,
+    deep_merge: bool,
+    autogenerate_ids: bool,
+    skip_invalid_documents: bool,
+    custom_csv_dialect: bool,
 }
 
 impl DocumentsAggregator {
@@ -725,8 +740,8 @@ impl DocumentsAggregator {
 
         ret.updated = true;
         ret.user_agents = extract_user_agents(request).into_iter().collect();
-        if let Some(primary_key) = documents_query.primary_key.clone() {
-            ret.primary_keys.insert(primary_key);
+        if let Some(primary_key) = &documents_query.primary_key {
+            ret.primary_keys.insert(primary_key.to_string());
         }
         let content_type = request
             .headers()
@@ -736,6 +751,10 @@ impl DocumentsAggregator {
             .to_string();
         ret.content_types.insert(content_type);
         ret.index_creation = index_creation;
+        ret.deep_merge = documents_query.merge_strategy == MergeStrategy::Deep;
+        ret.autogenerate_ids = documents_query.autogenerate_ids;
+        ret.skip_invalid_documents = documents_query.skip_invalid_documents;
+        ret.custom_csv_dialect = documents_query.csv_dialect().is_some();
 
         ret
     }
@@ -758,6 +777,10 @@ impl DocumentsAggregator {
             self.content_types.insert(content_type);
         }
         self.index_creation |= other.index_creation;
+        self.deep_merge |= other.deep_merge;
+        self.autogenerate_ids |= other.autogenerate_ids;
+        self.skip_invalid_documents |= other.skip_invalid_documents;
+        self.custom_csv_dialect |= other.custom_csv_dialect;
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -769,6 +792,10 @@ impl DocumentsAggregator {
                 "payload_type": self.content_types,
                 "primary_key": self.primary_keys,
                 "index_creation": self.index_creation,
+                "deep_merge": self.deep_merge,
+                "autogenerate_ids": self.autogenerate_ids,
+                "skip_invalid_documents": self.skip_invalid_documents,
+                "custom_csv_dialect": self.custom_csv_dialect,
             });
 
             Some(Track {
@@ -795,6 +822,7 @@ pub struct DocumentsDeletionAggregator {
     per_document_id: bool,
     clear_all: bool,
     per_batch: bool,
+    per_filter: bool,
 }
 
 impl DocumentsDeletionAggregator {
@@ -808,6 +836,7 @@ impl DocumentsDeletionAggregator {
             DocumentDeletionKind::PerDocumentId => ret.per_document_id = true,
             DocumentDeletionKind::ClearAll => ret.clear_all = true,
             DocumentDeletionKind::PerBatch => ret.per_batch = true,
+            DocumentDeletionKind::PerFilter => ret.per_filter = true,
         }
 
         ret
@@ -827,6 +856,7 @@ impl DocumentsDeletionAggregator {
         self.per_document_id |= other.per_document_id;
         self.clear_all |= other.clear_all;
         self.per_batch |= other.per_batch;
+        self.per_filter |= other.per_filter;
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -3,7 +3,6 @@ use aweb::error::{JsonPayloadError, QueryPayloadError};
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::index_uid::IndexUidFormatError;
-use serde_json::Value;
 use tokio::task::JoinError;
 
 #[derive(Debug, thiserror::Error)]
@@ -16,14 +15,18 @@ pub enum MeilisearchHttpError {
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
     )]
     InvalidContentType(String, Vec<String>),
+    #[error("The `csvDelimiter`, `csvQuote`, `csvQuoting`, `csvEscape` and `csvArraySeparator` parameters can only be used with a `text/csv` payload, not a {0} payload.")]
+    CsvDialectWithoutCsvPayload(PayloadType),
+    #[error("The `arrayMergeStrategy` parameter can only be used with the `deep` merge strategy.")]
+    ArrayMergeStrategyWithoutDeepMerge,
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
-    #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
-    InvalidExpression(&'static [&'static str], Value),
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("The provided payload reached the size limit.")]
     PayloadTooLarge,
+    #[error("The provided payload reached the size limit of {0} bytes once decompressed.")]
+    DecompressedPayloadTooLarge(usize),
     #[error("Two indexes must be given for each swap. The list `{:?}` contains {} indexes.",
         .0, .0.len()
     )]
@@ -54,9 +57,15 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingContentType(_) => Code::MissingContentType,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
+            MeilisearchHttpError::CsvDialectWithoutCsvPayload(_) => Code::InvalidDocumentCsvDialect,
+            MeilisearchHttpError::ArrayMergeStrategyWithoutDeepMerge => {
+                Code::InvalidDocumentArrayMergeStrategy
+            }
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
-            MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
             MeilisearchHttpError::PayloadTooLarge => Code::PayloadTooLarge,
+            MeilisearchHttpError::DecompressedPayloadTooLarge(_) => {
+                Code::DecompressedPayloadTooLarge
+            }
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
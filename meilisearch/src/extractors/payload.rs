@@ -2,15 +2,22 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use actix_http::encoding::Decoder as Decompress;
+use actix_web::http::header::{ContentEncoding, CONTENT_ENCODING};
 use actix_web::{dev, web, FromRequest, HttpRequest};
 use futures::future::{ready, Ready};
 use futures::Stream;
 
 use crate::error::MeilisearchHttpError;
 
+/// The body of a request, decompressed as a stream according to its `Content-Encoding`,
+/// the size limit applying to the decompressed bytes.
 pub struct Payload {
     payload: Decompress<dev::Payload>,
     limit: usize,
+    /// The remaining number of bytes that can be read before reaching the limit.
+    remaining: usize,
+    /// Whether the payload is compressed, to tell the user which size reached the limit.
+    compressed: bool,
 }
 
 pub struct PayloadConfig {
@@ -40,9 +47,17 @@ impl FromRequest for Payload {
             .app_data::<PayloadConfig>()
             .map(|c| c.limit)
             .unwrap_or(PayloadConfig::default().limit);
+        let compressed = req
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|encoding| encoding.to_str().ok())
+            .and_then(|encoding| encoding.parse::<ContentEncoding>().ok())
+            .map_or(false, |encoding| encoding != ContentEncoding::Identity);
         ready(Ok(Payload {
             payload: Decompress::from_headers(payload.take(), req.headers()),
             limit,
+            remaining: limit,
+            compressed,
         }))
     }
 }
@@ -54,11 +69,14 @@ impl Stream for Payload {
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         match Pin::new(&mut self.payload).poll_next(cx) {
             Poll::Ready(Some(result)) => match result {
-                Ok(bytes) => match self.limit.checked_sub(bytes.len()) {
-                    Some(new_limit) => {
-                        self.limit = new_limit;
+                Ok(bytes) => match self.remaining.checked_sub(bytes.len()) {
+                    Some(remaining) => {
+                        self.remaining = remaining;
                         Poll::Ready(Some(Ok(bytes)))
                     }
+                    None if self.compressed => Poll::Ready(Some(Err(
+                        MeilisearchHttpError::DecompressedPayloadTooLarge(self.limit),
+                    ))),
                     None => Poll::Ready(Some(Err(MeilisearchHttpError::PayloadTooLarge))),
                 },
                 x => Poll::Ready(Some(x.map_err(MeilisearchHttpError::from))),
@@ -37,7 +37,7 @@ use meilisearch_auth::AuthController;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
 use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
 use meilisearch_types::settings::apply_settings_to_builder;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{Details, KindWithContent};
 use meilisearch_types::versioning::{check_version_file, create_version_file};
 use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
 pub use option::Opt;
@@ -45,6 +45,9 @@ use option::ScheduleSnapshot;
 
 use crate::error::MeilisearchHttpError;
 
+/// The interval at which the indexes are checked for expired documents.
+const DOCUMENTS_EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Check if a db is empty. It does not provide any information on the
 /// validity of the data in it.
 /// We consider a database as non empty when it's a non empty directory.
@@ -184,6 +187,23 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Auth
             .unwrap();
     }
 
+    // We create a loop in a thread that registers documentExpiration tasks,
+    // it stops as soon as the index scheduler is dropped.
+    let weak_index_scheduler = Arc::downgrade(&index_scheduler);
+    thread::Builder::new()
+        .name(String::from("register-expiration-tasks"))
+        .spawn(move || loop {
+            thread::sleep(DOCUMENTS_EXPIRATION_CHECK_INTERVAL);
+            let index_scheduler = match weak_index_scheduler.upgrade() {
+                Some(index_scheduler) => index_scheduler,
+                None => break,
+            };
+            if let Err(e) = index_scheduler.register_documents_expirations() {
+                error!("Error while registering documents expirations: {}", e);
+            }
+        })
+        .unwrap();
+
     Ok((index_scheduler, auth_controller))
 }
 
@@ -296,7 +316,7 @@ fn import_dump(
         let mut builder = milli::update::Settings::new(&mut wtxn, &index, indexer_config);
         // 3.1 Import the primary key if there is one.
         if let Some(ref primary_key) = metadata.primary_key {
-            builder.set_primary_key(primary_key.to_string());
+            builder.set_primary_key(primary_key.clone());
         }
 
         // 3.2 Import the settings.
@@ -336,15 +356,30 @@ fn import_dump(
         let (builder, user_result) = builder.add_documents(reader)?;
         log::info!("{} documents found.", user_result?);
         builder.execute()?;
+
+        // 3.4 Import the versions of the documents, they replace the ones of the addition.
+        for version in index_reader.documents_versions()? {
+            let dump::DocumentVersion { id, version } = version?;
+            index.put_document_version(&mut wtxn, &id, version)?;
+        }
         wtxn.commit()?;
         log::info!("All documents successfully imported.");
     }
 
     // 4. Import the tasks.
+    let mut reports = Vec::new();
     for ret in dump_reader.tasks()? {
         let (task, file) = ret?;
+        reports.extend(task.details.iter().flat_map(Details::report_uuids));
         index_scheduler.register_dumped_task(task, file)?;
     }
+
+    // 5. Import the reports of the tasks.
+    for uuid in reports {
+        if let Some(report) = dump_reader.report(uuid)? {
+            index_scheduler.register_dumped_report(uuid, report)?;
+        }
+    }
     Ok(())
 }
 
@@ -1,38 +1,48 @@
-use std::io::ErrorKind;
+use std::io::{self, ErrorKind, Write};
+use std::mem;
 use std::num::ParseIntError;
+use std::str::ParseBoolError;
 
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use bstr::ByteSlice;
+use bytes::Bytes;
 use deserr::DeserializeFromValue;
 use futures::StreamExt;
 use index_scheduler::IndexScheduler;
 use log::debug;
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_ndjson, CsvDialect, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{DeserrError, ResponseError, TakeErrorMessage};
+use meilisearch_types::error::{Code, DeserrError, ResponseError, TakeErrorMessage};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{ArrayMergeStrategy, IndexDocumentsMethod};
+use meilisearch_types::milli::PrimaryKey;
 use meilisearch_types::star_or::StarOr;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 use serde_cs::vec::CS;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tempfile::tempfile;
 use tokio::fs::File;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 
-use super::search::parse_usize_take_error_message;
+use super::search::{parse_bool_take_error_message, parse_usize_take_error_message};
 use crate::analytics::{Analytics, DocumentDeletionKind};
 use crate::error::MeilisearchHttpError;
 use crate::error::PayloadError::ReceivePayload;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::extractors::json::ValidatedJson;
 use crate::extractors::payload::Payload;
 use crate::extractors::query_parameters::QueryParameter;
 use crate::extractors::sequential_extractor::SeqHandler;
@@ -74,6 +84,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     // this route needs to be before the /documents/{document_id} to match properly
     .service(web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents))))
+    .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_function))))
+    // A document whose id is `export` can't be retrieved from `GET /documents/export`
+    // because of this route, it is still returned by `POST /documents/fetch` with its id.
+    .service(web::resource("/export").route(web::get().to(SeqHandler(export_documents))))
+    .service(web::resource("/fetch").route(web::post().to(SeqHandler(fetch_documents))))
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
@@ -86,6 +102,9 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 pub struct GetDocument {
     #[deserr(error = DeserrError<InvalidDocumentFields>)]
     fields: Option<CS<StarOr<String>>>,
+    /// Adds the current `_version` of the document to the returned document.
+    #[deserr(error = DeserrError<InvalidDocumentRetrieveVersion>, default, from(&String) = parse_bool_take_error_message -> TakeErrorMessage<ParseBoolError>)]
+    retrieve_version: bool,
 }
 
 pub async fn get_document(
@@ -93,11 +112,12 @@ pub async fn get_document(
     path: web::Path<DocumentParam>,
     params: QueryParameter<GetDocument, DeserrError>,
 ) -> Result<HttpResponse, ResponseError> {
-    let GetDocument { fields } = params.into_inner();
+    let GetDocument { fields, retrieve_version } = params.into_inner();
     let attributes_to_retrieve = fields.and_then(fold_star_or);
 
     let index = index_scheduler.index(&path.index_uid)?;
-    let document = retrieve_document(&index, &path.document_id, attributes_to_retrieve)?;
+    let document =
+        retrieve_document(&index, &path.document_id, attributes_to_retrieve, retrieve_version)?;
     debug!("returns: {:?}", document);
     Ok(HttpResponse::Ok().json(document))
 }
@@ -127,6 +147,9 @@ pub struct BrowseQuery {
     limit: usize,
     #[deserr(error = DeserrError<InvalidDocumentLimit>)]
     fields: Option<CS<StarOr<String>>>,
+    /// Adds the current `_version` of the documents to the returned documents.
+    #[deserr(error = DeserrError<InvalidDocumentRetrieveVersion>, default, from(&String) = parse_bool_take_error_message -> TakeErrorMessage<ParseBoolError>)]
+    retrieve_version: bool,
 }
 
 pub async fn get_all_documents(
@@ -135,11 +158,12 @@ pub async fn get_all_documents(
     params: QueryParameter<BrowseQuery, DeserrError>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!("called with params: {:?}", params);
-    let BrowseQuery { limit, offset, fields } = params.into_inner();
+    let BrowseQuery { limit, offset, fields, retrieve_version } = params.into_inner();
     let attributes_to_retrieve = fields.and_then(fold_star_or);
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, attributes_to_retrieve)?;
+    let (total, documents) =
+        retrieve_documents(&index, offset, limit, attributes_to_retrieve, retrieve_version)?;
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
@@ -147,11 +171,221 @@ pub async fn get_all_documents(
     Ok(HttpResponse::Ok().json(ret))
 }
 
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FetchDocumentsQuery {
+    #[deserr(error = DeserrError<InvalidDocumentOffset>, default)]
+    offset: usize,
+    #[deserr(error = DeserrError<InvalidDocumentLimit>, default = crate::routes::PAGINATION_DEFAULT_LIMIT())]
+    limit: usize,
+    #[deserr(error = DeserrError<InvalidDocumentFields>)]
+    fields: Option<Vec<StarOr<String>>>,
+    #[deserr(error = DeserrError<InvalidDocumentFilter>)]
+    filter: Option<Value>,
+    /// The external ids of the documents, only the existing ones are returned in the requested order.
+    #[deserr(error = DeserrError<InvalidDocumentIds>)]
+    ids: Option<Vec<Value>>,
+}
+
+pub async fn fetch_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: ValidatedJson<FetchDocumentsQuery, DeserrError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let FetchDocumentsQuery { offset, limit, fields, filter, ids } = body.into_inner();
+    let attributes_to_retrieve = fields.and_then(fold_star_or);
+
+    analytics.publish(
+        "Documents Fetched".to_string(),
+        json!({ "filtered": filter.is_some(), "per_document_ids": ids.is_some() }),
+        Some(&req),
+    );
+
+    let ids = ids
+        .map(|ids| ids.into_iter().map(external_document_id).collect::<Result<Vec<_>, _>>())
+        .transpose()?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let (total, documents) =
+        retrieve_filtered_documents(&index, offset, limit, filter, ids, attributes_to_retrieve)?;
+
+    let ret = PaginationView::new(offset, limit, total as usize, documents);
+
+    debug!("returns: {:?}", ret);
+    Ok(HttpResponse::Ok().json(ret))
+}
+
+/// Accepts the document ids given as strings or as integers.
+fn external_document_id(id: Value) -> Result<String, ResponseError> {
+    match id {
+        Value::String(id) => Ok(id),
+        Value::Number(id) if id.is_u64() || id.is_i64() => Ok(id.to_string()),
+        id => Err(ResponseError::from_msg(
+            format!("`{id}` is not a valid document id. It must be a string or an integer."),
+            Code::InvalidDocumentIds,
+        )),
+    }
+}
+
 #[derive(Deserialize, Debug, DeserializeFromValue)]
 #[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields)]
 pub struct UpdateDocumentsQuery {
-    #[deserr(error = DeserrError<InvalidIndexPrimaryKey>)]
-    pub primary_key: Option<String>,
+    /// A single field, or the fields of a composite primary key as a JSON array.
+    #[deserr(error = DeserrError<InvalidIndexPrimaryKey>, default = None, from(Option<String>) = parse_primary_key -> TakeErrorMessage<InvalidPrimaryKeyError>)]
+    pub primary_key: Option<PrimaryKey>,
+    /// How the new documents are merged with the existing ones, only used by the updates.
+    #[serde(default)]
+    #[deserr(error = DeserrError<InvalidDocumentMergeStrategy>, default)]
+    pub merge_strategy: MergeStrategy,
+    /// How the arrays are merged when the documents are deep merged, only used by the deep merge.
+    #[serde(default)]
+    #[deserr(error = DeserrError<InvalidDocumentArrayMergeStrategy>, default)]
+    pub array_merge_strategy: Option<ArrayMergeStrategy>,
+    /// Generate a UUIDv4 for the documents missing the primary key.
+    #[serde(default)]
+    #[deserr(error = DeserrError<InvalidDocumentAutogenerateIds>, default, from(&String) = parse_bool_take_error_message -> TakeErrorMessage<ParseBoolError>)]
+    pub autogenerate_ids: bool,
+    /// Leave the invalid documents out and report them in the task details instead of failing.
+    #[serde(default)]
+    #[deserr(error = DeserrError<InvalidDocumentSkipInvalidDocuments>, default, from(&String) = parse_bool_take_error_message -> TakeErrorMessage<ParseBoolError>)]
+    pub skip_invalid_documents: bool,
+    /// The character separating the fields of a CSV payload.
+    #[deserr(error = DeserrError<InvalidDocumentCsvDelimiter>, default = None, from(Option<String>) = parse_csv_character -> TakeErrorMessage<InvalidCsvDialectError>)]
+    pub csv_delimiter: Option<u8>,
+    /// The character quoting the values of a CSV payload.
+    #[deserr(error = DeserrError<InvalidDocumentCsvQuote>, default = None, from(Option<String>) = parse_csv_character -> TakeErrorMessage<InvalidCsvDialectError>)]
+    pub csv_quote: Option<u8>,
+    /// Whether the quotes of a CSV payload are interpreted, `true` by default.
+    #[deserr(error = DeserrError<InvalidDocumentCsvQuoting>, default = None, from(Option<String>) = parse_csv_quoting -> TakeErrorMessage<ParseBoolError>)]
+    pub csv_quoting: Option<bool>,
+    /// The character escaping the quotes inside of the quoted values of a CSV payload.
+    #[deserr(error = DeserrError<InvalidDocumentCsvEscape>, default = None, from(Option<String>) = parse_csv_character -> TakeErrorMessage<InvalidCsvDialectError>)]
+    pub csv_escape: Option<u8>,
+    /// The separator of the items of the `:array` columns of a CSV payload.
+    #[deserr(error = DeserrError<InvalidDocumentCsvArraySeparator>, default = None, from(Option<String>) = parse_csv_array_separator -> TakeErrorMessage<InvalidCsvDialectError>)]
+    pub csv_array_separator: Option<String>,
+}
+
+impl UpdateDocumentsQuery {
+    /// The dialect of the CSV payload, if any of its parameters is specified.
+    pub fn csv_dialect(&self) -> Option<CsvDialect> {
+        if self.csv_delimiter.is_none()
+            && self.csv_quote.is_none()
+            && self.csv_quoting.is_none()
+            && self.csv_escape.is_none()
+            && self.csv_array_separator.is_none()
+        {
+            return None;
+        }
+
+        let default = CsvDialect::default();
+        Some(CsvDialect {
+            delimiter: self.csv_delimiter.unwrap_or(default.delimiter),
+            quote: self.csv_quote.unwrap_or(default.quote),
+            quoting: self.csv_quoting.unwrap_or(default.quoting),
+            escape: self.csv_escape.or(default.escape),
+            array_separator: self.csv_array_separator.clone().unwrap_or(default.array_separator),
+        })
+    }
+}
+
+fn parse_primary_key(
+    value: Option<String>,
+) -> std::result::Result<Option<PrimaryKey>, TakeErrorMessage<InvalidPrimaryKeyError>> {
+    match value {
+        Some(value) if value.starts_with('[') => {
+            match serde_json::from_str(&value).ok().and_then(PrimaryKey::composite) {
+                Some(primary_key) => Ok(Some(primary_key)),
+                None => Err(TakeErrorMessage(InvalidPrimaryKeyError(value))),
+            }
+        }
+        value => Ok(value.map(PrimaryKey::Field)),
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidPrimaryKeyError(String);
+
+impl std::fmt::Display for InvalidPrimaryKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid composite primary key. It must be a JSON array of at least two distinct and non-empty fields.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidPrimaryKeyError {}
+
+fn parse_csv_character(
+    value: Option<String>,
+) -> std::result::Result<Option<u8>, TakeErrorMessage<InvalidCsvDialectError>> {
+    match value {
+        Some(value) => match value.as_bytes() {
+            [character] if character.is_ascii() => Ok(Some(*character)),
+            _ => Err(TakeErrorMessage(InvalidCsvDialectError::Character(value))),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse_csv_quoting(
+    value: Option<String>,
+) -> std::result::Result<Option<bool>, TakeErrorMessage<ParseBoolError>> {
+    value.map(|value| parse_bool_take_error_message(&value)).transpose()
+}
+
+fn parse_csv_array_separator(
+    value: Option<String>,
+) -> std::result::Result<Option<String>, TakeErrorMessage<InvalidCsvDialectError>> {
+    match value {
+        Some(value) if value.is_empty() => {
+            Err(TakeErrorMessage(InvalidCsvDialectError::EmptyArraySeparator))
+        }
+        value => Ok(value),
+    }
+}
+
+#[derive(Debug)]
+pub enum InvalidCsvDialectError {
+    Character(String),
+    EmptyArraySeparator,
+}
+
+impl std::fmt::Display for InvalidCsvDialectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Character(value) => {
+                write!(
+                    f,
+                    "`{value}` is not a valid CSV character. It must be a single ASCII character."
+                )
+            }
+            Self::EmptyArraySeparator => f.write_str("The CSV array separator must not be empty."),
+        }
+    }
+}
+
+impl std::error::Error for InvalidCsvDialectError {}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, DeserializeFromValue)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    /// Only the top-level attributes of the documents are merged.
+    Shallow,
+    /// The nested objects of the documents are recursively merged.
+    Deep,
+}
+
+impl Default for MergeStrategy {
+    fn default() -> Self {
+        Self::Shallow
+    }
 }
 
 pub async fn add_documents(
@@ -167,6 +401,7 @@ pub async fn add_documents(
 
     analytics.add_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
 
+    let csv_dialect = params.csv_dialect();
     let allow_index_creation = index_scheduler.filters().allow_index_creation;
     let task = document_addition(
         extract_mime_type(&req)?,
@@ -176,6 +411,9 @@ pub async fn add_documents(
         body,
         IndexDocumentsMethod::ReplaceDocuments,
         allow_index_creation,
+        params.autogenerate_ids,
+        params.skip_invalid_documents,
+        csv_dialect,
     )
     .await?;
 
@@ -192,32 +430,51 @@ pub async fn update_documents(
 ) -> Result<HttpResponse, ResponseError> {
     debug!("called with params: {:?}", params);
     let index_uid = path.into_inner();
+    let params = params.into_inner();
 
     analytics.update_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
 
+    let method = match (params.merge_strategy, params.array_merge_strategy) {
+        (MergeStrategy::Shallow, None) => IndexDocumentsMethod::UpdateDocuments,
+        (MergeStrategy::Shallow, Some(_)) => {
+            return Err(MeilisearchHttpError::ArrayMergeStrategyWithoutDeepMerge.into())
+        }
+        (MergeStrategy::Deep, array_merge_strategy) => {
+            IndexDocumentsMethod::DeepMergeDocuments(array_merge_strategy.unwrap_or_default())
+        }
+    };
+
+    let csv_dialect = params.csv_dialect();
     let allow_index_creation = index_scheduler.filters().allow_index_creation;
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
-        params.into_inner().primary_key,
+        params.primary_key,
         body,
-        IndexDocumentsMethod::UpdateDocuments,
+        method,
         allow_index_creation,
+        params.autogenerate_ids,
+        params.skip_invalid_documents,
+        csv_dialect,
     )
     .await?;
 
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn document_addition(
     mime_type: Option<Mime>,
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
     index_uid: String,
-    primary_key: Option<String>,
+    primary_key: Option<PrimaryKey>,
     mut body: Payload,
     method: IndexDocumentsMethod,
     allow_index_creation: bool,
+    autogenerate_ids: bool,
+    skip_invalid_documents: bool,
+    csv_dialect: Option<CsvDialect>,
 ) -> Result<SummarizedTaskView, MeilisearchHttpError> {
     let format = match mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())) {
         Some(("application", "json")) => PayloadType::Json,
@@ -234,6 +491,11 @@ async fn document_addition(
         }
     };
 
+    if csv_dialect.is_some() && !matches!(format, PayloadType::Csv) {
+        return Err(MeilisearchHttpError::CsvDialectWithoutCsvPayload(format));
+    }
+    let csv_dialect = csv_dialect.unwrap_or_default();
+
     // is your indexUid valid?
     let index_uid = IndexUid::try_from(index_uid)?.into_inner();
 
@@ -277,7 +539,7 @@ async fn document_addition(
     let documents_count = tokio::task::spawn_blocking(move || {
         let documents_count = match format {
             PayloadType::Json => read_json(&read_file, update_file.as_file_mut())?,
-            PayloadType::Csv => read_csv(&read_file, update_file.as_file_mut())?,
+            PayloadType::Csv => read_csv(&read_file, update_file.as_file_mut(), &csv_dialect)?,
             PayloadType::Ndjson => read_ndjson(&read_file, update_file.as_file_mut())?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
@@ -312,6 +574,8 @@ async fn document_addition(
         documents_count,
         primary_key,
         allow_index_creation,
+        autogenerate_ids,
+        skip_invalid_documents,
         index_uid,
     };
 
@@ -353,6 +617,99 @@ pub async fn delete_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentDeletionByFilter {
+    #[deserr(error = DeserrError<InvalidDocumentDeleteFilter>, missing_field_error = DeserrError::missing_document_filter)]
+    filter: Value,
+}
+
+pub async fn delete_documents_by_filter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
+    path: web::Path<String>,
+    body: ValidatedJson<DocumentDeletionByFilter, DeserrError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let DocumentDeletionByFilter { filter } = body.into_inner();
+
+    analytics.delete_documents(DocumentDeletionKind::PerFilter, &req);
+
+    // we ensure the filter is well formed before enqueuing it, the filterable
+    // attributes are only checked when the task is processed.
+    match milli::Filter::from_json(&filter) {
+        Ok(Some(_)) => (),
+        Ok(None) => {
+            return Err(ResponseError::from_msg(
+                "The filter cannot be empty.".to_string(),
+                Code::InvalidDocumentDeleteFilter,
+            ))
+        }
+        Err(e) => {
+            return Err(ResponseError::from_msg(e.to_string(), Code::InvalidDocumentDeleteFilter))
+        }
+    }
+
+    let task = KindWithContent::DocumentDeletionByFilter {
+        index_uid: path.into_inner(),
+        filter_expr: filter,
+    };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentEditionByFunction {
+    #[deserr(error = DeserrError<InvalidDocumentEditFilter>)]
+    filter: Option<Value>,
+    #[deserr(error = DeserrError<InvalidDocumentEditFunction>, missing_field_error = DeserrError::missing_document_edit_function)]
+    function: String,
+}
+
+pub async fn edit_documents_by_function(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<String>,
+    body: ValidatedJson<DocumentEditionByFunction, DeserrError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let DocumentEditionByFunction { filter, function } = body.into_inner();
+
+    analytics.publish(
+        "Documents Edited".to_string(),
+        json!({ "filtered": filter.is_some() }),
+        Some(&req),
+    );
+
+    // we ensure the filter is well formed and the function compiles before enqueuing
+    // them, the filterable attributes are only checked when the task is processed.
+    if let Some(filter) = &filter {
+        if let Err(e) = milli::Filter::from_json(filter) {
+            return Err(ResponseError::from_msg(e.to_string(), Code::InvalidDocumentEditFilter));
+        }
+    }
+    if let Err(e) = milli::update::validate_document_edition_function(&function) {
+        return Err(ResponseError::from_msg(e.to_string(), Code::InvalidDocumentEditFunction));
+    }
+
+    let task = KindWithContent::DocumentEdition {
+        index_uid: path.into_inner(),
+        filter_expr: filter,
+        function,
+    };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     path: web::Path<String>,
@@ -369,6 +726,309 @@ pub async fn clear_all_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+/// The number of bytes of the exported documents that are buffered before being sent.
+const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ExportDocumentsQuery {
+    #[deserr(error = DeserrError<InvalidDocumentFilter>)]
+    filter: Option<String>,
+    #[deserr(error = DeserrError<InvalidDocumentExportFormat>, default)]
+    format: ExportFormat,
+    #[deserr(error = DeserrError<InvalidDocumentFields>)]
+    fields: Option<CS<StarOr<String>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, DeserializeFromValue)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// One JSON document per line.
+    Ndjson,
+    /// One document per record, the type of the columns being written in the header.
+    Csv,
+}
+
+impl Default for ExportFormat {
+    fn default() -> Self {
+        Self::Ndjson
+    }
+}
+
+impl ExportFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Csv => "text/csv",
+        }
+    }
+}
+
+pub async fn export_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: QueryParameter<ExportDocumentsQuery, DeserrError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", params);
+    let ExportDocumentsQuery { filter, format, fields } = params.into_inner();
+    let attributes_to_retrieve = fields.and_then(fold_star_or);
+
+    analytics.publish(
+        "Documents Exported".to_string(),
+        json!({ "format": format, "filtered": filter.is_some() }),
+        Some(&req),
+    );
+
+    let index = index_scheduler.index(&index_uid)?;
+    let (sender, mut receiver) = mpsc::channel(8);
+    tokio::task::spawn_blocking(move || {
+        let result = export_documents_into(&index, filter, attributes_to_retrieve, format, &sender);
+        if let Err(error) = result {
+            // The client may have gone away, in which case there is no one to report to.
+            let _ = sender.blocking_send(Err(error));
+        }
+    });
+
+    // The errors happening before any document is exported, e.g. an invalid filter,
+    // are returned as a regular error response.
+    let first_chunk = match receiver.recv().await {
+        Some(Err(error)) => return Err(error),
+        first_chunk => first_chunk,
+    };
+    let chunks = futures::stream::iter(first_chunk).chain(ReceiverStream::new(receiver));
+
+    Ok(HttpResponse::Ok().content_type(format.content_type()).streaming(chunks))
+}
+
+/// Writes the documents matching the filter in the given format and sends them as chunks,
+/// all the documents being read from a single transaction to export a consistent snapshot.
+fn export_documents_into(
+    index: &Index,
+    filter: Option<String>,
+    attributes_to_retrieve: Option<Vec<String>>,
+    format: ExportFormat,
+    sender: &mpsc::Sender<Result<Bytes, ResponseError>>,
+) -> Result<(), ResponseError> {
+    let rtxn = index.read_txn()?;
+
+    let filter = match &filter {
+        Some(filter) => milli::Filter::from_str(filter).map_err(document_filter_error)?,
+        None => None,
+    };
+    let candidates = filtered_documents(index, &rtxn, filter)?;
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let mut writer = ChunkWriter { buffer: Vec::new(), sender };
+
+    match format {
+        ExportFormat::Ndjson => {
+            for_each_document(index, &rtxn, &candidates, &fields_ids_map, |document| {
+                let document = match &attributes_to_retrieve {
+                    Some(attributes) => permissive_json_pointer::select_values(
+                        &document,
+                        attributes.iter().map(|s| s.as_ref()),
+                    ),
+                    None => document,
+                };
+                serde_json::to_writer(&mut writer, &document).map_err(io::Error::from)?;
+                writer.write_all(b"\n")
+            })?;
+            writer.flush()?;
+        }
+        ExportFormat::Csv => {
+            let columns = match attributes_to_retrieve {
+                Some(attributes) => attributes,
+                None => {
+                    let names: Vec<_> = fields_ids_map.iter().map(|(_, name)| name).collect();
+                    // The nested fields are already exported as part of their parent field.
+                    names
+                        .iter()
+                        .filter(|name| {
+                            !names.iter().any(|parent| {
+                                name.strip_prefix(*parent)
+                                    .map_or(false, |rest| rest.starts_with('.'))
+                            })
+                        })
+                        .map(|name| name.to_string())
+                        .collect()
+                }
+            };
+
+            // The columns are typed in the header so that the export can be imported back,
+            // it requires a first pass over the documents to find the type of each column.
+            let mut types = vec![None; columns.len()];
+            for_each_document(index, &rtxn, &candidates, &fields_ids_map, |document| {
+                for (column, column_type) in columns.iter().zip(&mut types) {
+                    if let Some(value) = field_value(&document, column) {
+                        *column_type = CsvColumnType::merge(*column_type, CsvColumnType::of(value));
+                    }
+                }
+                Ok(())
+            })?;
+            let types: Vec<_> =
+                types.into_iter().map(|column_type| column_type.unwrap_or_default()).collect();
+
+            let mut csv = csv::Writer::from_writer(writer);
+            let header = columns.iter().zip(&types).map(|(column, column_type)| match column_type
+                .annotation(column)
+            {
+                Some(annotation) => format!("{column}:{annotation}"),
+                None => column.clone(),
+            });
+            csv.write_record(header).map_err(io::Error::from)?;
+            for_each_document(index, &rtxn, &candidates, &fields_ids_map, |document| {
+                let record = columns.iter().zip(&types).map(|(column, column_type)| {
+                    match (field_value(&document, column), column_type) {
+                        (None | Some(Value::Null), _) => String::new(),
+                        (Some(Value::String(string)), CsvColumnType::String) => string.clone(),
+                        (Some(value), _) => value.to_string(),
+                    }
+                });
+                csv.write_record(record).map_err(io::Error::from)
+            })?;
+            csv.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The type of the values of a column of an exported CSV, the columns mixing several types
+/// or containing objects or arrays are exported as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CsvColumnType {
+    String,
+    Number,
+    Boolean,
+    Json,
+}
+
+impl Default for CsvColumnType {
+    fn default() -> Self {
+        Self::String
+    }
+}
+
+impl CsvColumnType {
+    /// The type of a value, the null values don't have any.
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::String(_) => Some(Self::String),
+            Value::Number(_) => Some(Self::Number),
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Array(_) | Value::Object(_) => Some(Self::Json),
+        }
+    }
+
+    fn merge(left: Option<Self>, right: Option<Self>) -> Option<Self> {
+        match (left, right) {
+            (Some(left), Some(right)) if left != right => Some(Self::Json),
+            (left, right) => left.or(right),
+        }
+    }
+
+    /// The annotation of the column in the header, the strings are the default type
+    /// and only need one when the name of the column contains a `:`.
+    fn annotation(&self, column: &str) -> Option<&'static str> {
+        match self {
+            Self::String if column.contains(':') => Some("string"),
+            Self::String => None,
+            Self::Number => Some("number"),
+            Self::Boolean => Some("boolean"),
+            Self::Json => Some("json"),
+        }
+    }
+}
+
+/// Returns the documents matching the filter, or all the documents when there is none.
+fn filtered_documents(
+    index: &Index,
+    rtxn: &RoTxn,
+    filter: Option<milli::Filter>,
+) -> Result<RoaringBitmap, ResponseError> {
+    match filter {
+        Some(filter) => filter.evaluate(rtxn, index).map_err(document_filter_error),
+        None => Ok(index.documents_ids(rtxn)? - index.soft_deleted_documents_ids(rtxn)?),
+    }
+}
+
+/// Calls the closure on each of the candidates documents, fetching them by batches.
+fn for_each_document(
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &RoaringBitmap,
+    fields_ids_map: &milli::FieldsIdsMap,
+    mut f: impl FnMut(Document) -> io::Result<()>,
+) -> Result<(), ResponseError> {
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+    let mut candidates = candidates.iter();
+    let mut ids = Vec::with_capacity(1000);
+    loop {
+        ids.clear();
+        ids.extend(candidates.by_ref().take(1000));
+        if ids.is_empty() {
+            return Ok(());
+        }
+        for (_id, document) in index.documents(rtxn, ids.iter().copied())? {
+            let document = milli::obkv_to_json(&all_fields, fields_ids_map, document)?;
+            f(document)?;
+        }
+    }
+}
+
+/// Retrieves the value of a field of a document, going through the nested objects.
+fn field_value<'a>(document: &'a Document, name: &str) -> Option<&'a Value> {
+    if let Some(value) = document.get(name) {
+        return Some(value);
+    }
+
+    name.match_indices('.').find_map(|(index, _)| match document.get(&name[..index]) {
+        Some(Value::Object(object)) => field_value(object, &name[index + 1..]),
+        _ => None,
+    })
+}
+
+/// Reports the invalid filters with the code of the documents routes.
+fn document_filter_error(error: milli::Error) -> ResponseError {
+    match error {
+        milli::Error::UserError(
+            error @ (milli::UserError::InvalidFilter(_)
+            | milli::UserError::InvalidFilterExpression(..)),
+        ) => ResponseError::from_msg(error.to_string(), Code::InvalidDocumentFilter),
+        error => error.into(),
+    }
+}
+
+/// Buffers the exported documents and sends them as chunks of the response.
+struct ChunkWriter<'a> {
+    buffer: Vec<u8>,
+    sender: &'a mpsc::Sender<Result<Bytes, ResponseError>>,
+}
+
+impl Write for ChunkWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= EXPORT_CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            let chunk = Bytes::from(mem::take(&mut self.buffer));
+            self.sender
+                .blocking_send(Ok(chunk))
+                .map_err(|_| io::Error::new(ErrorKind::BrokenPipe, "the export was interrupted"))?;
+        }
+        Ok(())
+    }
+}
+
 fn all_documents<'a>(
     index: &Index,
     rtxn: &'a RoTxn,
@@ -388,18 +1048,38 @@ fn retrieve_documents<S: AsRef<str>>(
     offset: usize,
     limit: usize,
     attributes_to_retrieve: Option<Vec<S>>,
+    retrieve_version: bool,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
+    let primary_key = index.primary_key(&rtxn)?;
 
     let mut documents = Vec::new();
     for document in all_documents(index, &rtxn)?.skip(offset).take(limit) {
-        let document = match &attributes_to_retrieve {
+        let document = document?;
+        // The version must be retrieved before the primary key is removed from the document.
+        let version = match &primary_key {
+            Some(primary_key) if retrieve_version => {
+                let external_id =
+                    milli::update::extract_external_document_id(primary_key, |name| {
+                        Ok(document.get(name).cloned())
+                    })?;
+                match external_id {
+                    Some(external_id) => Some(index.document_version(&rtxn, &external_id)?),
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        let mut document = match &attributes_to_retrieve {
             Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
-                &document?,
+                &document,
                 attributes_to_retrieve.iter().map(|s| s.as_ref()),
             ),
-            None => document?,
+            None => document,
         };
+        if let Some(version) = version {
+            document.insert("_version".to_string(), version.into());
+        }
         documents.push(document);
     }
 
@@ -407,10 +1087,61 @@ fn retrieve_documents<S: AsRef<str>>(
     Ok((number_of_documents, documents))
 }
 
+fn retrieve_filtered_documents<S: AsRef<str>>(
+    index: &Index,
+    offset: usize,
+    limit: usize,
+    filter: Option<Value>,
+    ids: Option<Vec<String>>,
+    attributes_to_retrieve: Option<Vec<S>>,
+) -> Result<(u64, Vec<Document>), ResponseError> {
+    let rtxn = index.read_txn()?;
+
+    let filter = match &filter {
+        Some(filter) => milli::Filter::from_json(filter).map_err(document_filter_error)?,
+        None => None,
+    };
+    let candidates = filtered_documents(index, &rtxn, filter)?;
+    let (total, documents_ids): (u64, Vec<_>) = match ids {
+        // The documents are returned in the order of the requested ids, each of them once.
+        Some(ids) => {
+            let external_documents_ids = index.external_documents_ids(&rtxn)?;
+            let mut seen = RoaringBitmap::new();
+            let documents_ids: Vec<_> = ids
+                .iter()
+                .filter_map(|id| external_documents_ids.get(id.as_bytes()))
+                .filter(|docid| candidates.contains(*docid) && seen.insert(*docid))
+                .collect();
+            let total = documents_ids.len() as u64;
+            (total, documents_ids.into_iter().skip(offset).take(limit).collect())
+        }
+        None => (candidates.len(), candidates.iter().skip(offset).take(limit).collect()),
+    };
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+
+    let mut documents = Vec::new();
+    for (_id, document) in index.documents(&rtxn, documents_ids)? {
+        let document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+        let document = match &attributes_to_retrieve {
+            Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
+                &document,
+                attributes_to_retrieve.iter().map(|s| s.as_ref()),
+            ),
+            None => document,
+        };
+        documents.push(document);
+    }
+
+    Ok((total, documents))
+}
+
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
+    retrieve_version: bool,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -430,13 +1161,17 @@ fn retrieve_document<S: AsRef<str>>(
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
     let document = meilisearch_types::milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
-    let document = match &attributes_to_retrieve {
+    let mut document = match &attributes_to_retrieve {
         Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
             &document,
             attributes_to_retrieve.iter().map(|s| s.as_ref()),
         ),
         None => document,
     };
+    if retrieve_version {
+        let version = index.document_version(&txn, doc_id)?;
+        document.insert("_version".to_string(), version.into());
+    }
 
     Ok(document)
 }
@@ -8,7 +8,7 @@ use log::debug;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{unwrap_any, Code, DeserrError, ResponseError, TakeErrorMessage};
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::{self, FieldDistribution, Index};
+use meilisearch_types::milli::{self, FieldDistribution, Index, PrimaryKey};
 use meilisearch_types::tasks::KindWithContent;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -42,6 +42,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::resource("/compact").route(web::post().to(SeqHandler(compact_index))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/settings").configure(settings::configure)),
@@ -56,7 +57,7 @@ pub struct IndexView {
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
-    pub primary_key: Option<String>,
+    pub primary_key: Option<PrimaryKey>,
 }
 
 impl IndexView {
@@ -66,7 +67,7 @@ impl IndexView {
             uid,
             created_at: index.created_at(&rtxn)?,
             updated_at: index.updated_at(&rtxn)?,
-            primary_key: index.primary_key(&rtxn)?.map(String::from),
+            primary_key: index.primary_key(&rtxn)?,
         })
     }
 }
@@ -112,7 +113,7 @@ pub struct IndexCreateRequest {
     #[deserr(error = DeserrError<InvalidIndexUid>, missing_field_error = DeserrError::missing_index_uid)]
     uid: String,
     #[deserr(error = DeserrError<InvalidIndexPrimaryKey>)]
-    primary_key: Option<String>,
+    primary_key: Option<PrimaryKey>,
 }
 
 pub async fn create_index(
@@ -165,7 +166,7 @@ fn deny_immutable_fields_index(
 #[deserr(error = DeserrError, rename_all = camelCase, deny_unknown_fields = deny_immutable_fields_index)]
 pub struct UpdateIndexRequest {
     #[deserr(error = DeserrError<InvalidIndexPrimaryKey>)]
-    primary_key: Option<String>,
+    primary_key: Option<PrimaryKey>,
 }
 
 pub async fn get_index(
@@ -218,6 +219,22 @@ pub async fn delete_index(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+pub async fn compact_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    analytics.publish("Index Compacted".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexCompaction { index_uid: index_uid.into_inner() };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn get_index_stats(
     index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
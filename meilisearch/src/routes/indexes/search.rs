@@ -7,6 +7,7 @@ use log::debug;
 use meilisearch_auth::IndexSearchRules;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{DeserrError, ResponseError, TakeErrorMessage};
+use meilisearch_types::milli::{Locale, UnsupportedLocale};
 use serde_cs::vec::CS;
 use serde_json::Value;
 
@@ -80,6 +81,16 @@ pub struct SearchQueryGet {
     crop_marker: String,
     #[deserr(error = DeserrError<InvalidSearchMatchingStrategy>, default)]
     matching_strategy: MatchingStrategy,
+    #[deserr(error = DeserrError<InvalidSearchLocales>, default = None, from(Option<CS<String>>) = parse_option_cs_locales -> TakeErrorMessage<UnsupportedLocale>)]
+    locales: Option<Vec<Locale>>,
+}
+
+fn parse_option_cs_locales(
+    s: Option<CS<String>>,
+) -> Result<Option<Vec<Locale>>, TakeErrorMessage<UnsupportedLocale>> {
+    s.map(|locales| locales.into_iter().map(|locale| locale.parse()).collect())
+        .transpose()
+        .map_err(TakeErrorMessage)
 }
 
 impl From<SearchQueryGet> for SearchQuery {
@@ -110,6 +121,7 @@ impl From<SearchQueryGet> for SearchQuery {
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            locales: other.locales,
         }
     }
 }
@@ -178,6 +178,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/date-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDateAttributes,
+    >,
+    date_attributes,
+    "dateAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DateAttributes Updated".to_string(),
+            json!({
+                "date_attributes": {
+                    "total": setting.as_ref().map(|date| date.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/displayed-attributes",
     put,
@@ -242,6 +267,13 @@ make_setting_route!(
                             .set()
                             .map(|s| s.two_typos.set()))
                         .flatten(),
+                    "min_word_size_for_typos_per_attribute": setting
+                        .as_ref()
+                        .and_then(|s| s.min_word_size_for_typos_per_attribute.as_ref().set().map(|m| m.len())),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set())
+                        .copied(),
                 },
             }),
             Some(req),
@@ -300,6 +332,81 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSeparatorTokens,
+    >,
+    separator_tokens,
+    "separatorTokens",
+    analytics,
+    |separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SeparatorTokens Updated".to_string(),
+            json!({
+                "separator_tokens": {
+                    "total": separator_tokens.as_ref().map(|separator_tokens| separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/non-separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNonSeparatorTokens,
+    >,
+    non_separator_tokens,
+    "nonSeparatorTokens",
+    analytics,
+    |non_separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "NonSeparatorTokens Updated".to_string(),
+            json!({
+                "non_separator_tokens": {
+                    "total": non_separator_tokens.as_ref().map(|non_separator_tokens| non_separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/dictionary",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDictionary,
+    >,
+    dictionary,
+    "dictionary",
+    analytics,
+    |dictionary: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Dictionary Updated".to_string(),
+            json!({
+                "dictionary": {
+                    "total": dictionary.as_ref().map(|dictionary| dictionary.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/synonyms",
     put,
@@ -430,6 +537,107 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/rules",
+    put,
+    Vec<meilisearch_types::milli::CurationRule>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsRules,
+    >,
+    rules,
+    "rules",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::milli::CurationRule>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Rules Updated".to_string(),
+            json!({
+                "rules": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::milli::LocalizedAttributesRule>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::milli::LocalizedAttributesRule>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming-languages",
+    put,
+    Vec<meilisearch_types::milli::StemmingLanguage>,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemmingLanguages,
+    >,
+    stemming_languages,
+    "stemmingLanguages",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::milli::StemmingLanguage>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "StemmingLanguages Updated".to_string(),
+            json!({
+                "stemming_languages": {
+                    "total": setting.as_ref().map(|languages| languages.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/schema",
+    put,
+    meilisearch_types::milli::Schema,
+    meilisearch_types::error::DeserrError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSchema,
+    >,
+    schema,
+    "schema",
+    analytics,
+    |setting: &Option<meilisearch_types::milli::Schema>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Schema Updated".to_string(),
+            json!({
+                "schema": {
+                    "total": setting.as_ref().map(|schema| schema.len()),
+                    "required": setting.as_ref().map(|schema| schema.values().filter(|field| field.required).count()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -447,15 +655,23 @@ macro_rules! generate_configure {
 generate_configure!(
     filterable_attributes,
     sortable_attributes,
+    date_attributes,
     displayed_attributes,
     searchable_attributes,
     distinct_attribute,
     stop_words,
+    separator_tokens,
+    non_separator_tokens,
+    dictionary,
     synonyms,
     ranking_rules,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    rules,
+    localized_attributes,
+    stemming_languages,
+    schema
 );
 
 pub async fn update_all(
@@ -495,6 +711,9 @@ pub async fn update_all(
                 "total": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.len()),
                 "has_geo": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.iter().any(|s| s == "_geo")),
             },
+            "date_attributes": {
+                "total": new_settings.date_attributes.as_ref().set().map(|date| date.len()),
+            },
             "distinct_attribute": {
                 "set": new_settings.distinct_attribute.as_ref().set().is_some()
             },
@@ -528,6 +747,15 @@ pub async fn update_all(
                         .set()
                         .map(|s| s.two_typos.set()))
                     .flatten(),
+                "min_word_size_for_typos_per_attribute": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.min_word_size_for_typos_per_attribute.as_ref().set().map(|m| m.len())),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set())
+                    .copied(),
             },
             "faceting": {
                 "max_values_per_facet": new_settings.faceting
@@ -544,6 +772,27 @@ pub async fn update_all(
             "stop_words": {
                 "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
             },
+            "separator_tokens": {
+                "total": new_settings.separator_tokens.as_ref().set().map(|separator_tokens| separator_tokens.len()),
+            },
+            "non_separator_tokens": {
+                "total": new_settings.non_separator_tokens.as_ref().set().map(|non_separator_tokens| non_separator_tokens.len()),
+            },
+            "dictionary": {
+                "total": new_settings.dictionary.as_ref().set().map(|dictionary| dictionary.len()),
+            },
+            "rules": {
+                "total": new_settings.rules.as_ref().set().map(|rules| rules.len()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
+            "stemming_languages": {
+                "total": new_settings.stemming_languages.as_ref().set().map(|languages| languages.len()),
+            },
+            "schema": {
+                "total": new_settings.schema.as_ref().set().map(|schema| schema.len()),
+            },
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
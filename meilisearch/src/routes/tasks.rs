@@ -1,17 +1,19 @@
+use std::io::Read;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::DeserializeFromValue;
-use index_scheduler::{IndexScheduler, Query, TaskId};
+use index_scheduler::{IndexScheduler, Query, TaskId, TaskProgress};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{DeserrError, ResponseError, TakeErrorMessage};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::PrimaryKey;
 use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::star_or::StarOr;
 use meilisearch_types::tasks::{
-    serialize_duration, Details, IndexSwap, Kind, KindWithContent, Status, Task,
+    serialize_duration, Details, IndexSwap, InvalidDocument, Kind, KindWithContent, Status, Task,
 };
 use serde::{Deserialize, Serialize};
 use serde_cs::vec::CS;
@@ -37,7 +39,19 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
-    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
+    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))))
+    .service(
+        web::resource("/{task_id}/invalid-documents")
+            .route(web::get().to(SeqHandler(get_task_invalid_documents))),
+    )
+    .service(
+        web::resource("/{task_id}/generated-documents-ids")
+            .route(web::get().to(SeqHandler(get_task_generated_documents_ids))),
+    )
+    .service(
+        web::resource("/{task_id}/conflicting-documents")
+            .route(web::get().to(SeqHandler(get_task_conflicting_documents))),
+    );
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -61,6 +75,8 @@ pub struct TaskView {
     pub started_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option", default)]
     pub finished_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgressView>,
 }
 
 impl TaskView {
@@ -77,6 +93,50 @@ impl TaskView {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            progress: None,
+        }
+    }
+
+    /// Attaches the progress of the processing tasks to this task if it is processing.
+    fn with_progress(self, progress: &Option<TaskProgress>) -> TaskView {
+        match self.status {
+            Status::Processing => {
+                TaskView { progress: progress.clone().map(TaskProgressView::from), ..self }
+            }
+            _ => self,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgressView {
+    pub current_step: &'static str,
+    pub finished_steps: usize,
+    pub total_steps: usize,
+    pub processed_documents: Option<usize>,
+    pub total_documents: Option<usize>,
+    #[serde(serialize_with = "serialize_duration")]
+    pub estimated_remaining_time: Option<Duration>,
+}
+
+impl From<TaskProgress> for TaskProgressView {
+    fn from(progress: TaskProgress) -> Self {
+        let TaskProgress {
+            current_step,
+            finished_steps,
+            total_steps,
+            processed_documents,
+            total_documents,
+            estimated_remaining_time,
+        } = progress;
+        TaskProgressView {
+            current_step,
+            finished_steps,
+            total_steps,
+            processed_documents,
+            total_documents,
+            estimated_remaining_time,
         }
     }
 }
@@ -89,12 +149,22 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub primary_key: Option<Option<String>>,
+    pub conflicting_documents: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_documents_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_documents: Option<Vec<InvalidDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<Option<PrimaryKey>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_tasks: Option<Option<u64>>,
@@ -109,18 +179,34 @@ pub struct DetailsView {
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_compaction_size: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_compaction_size: Option<Option<u64>>,
 }
 
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                conflicting_documents,
+                conflicting_documents_report: _,
+                generated_documents_ids,
+                generated_documents_ids_report: _,
+                invalid_documents,
+                invalid_documents_report: _,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                conflicting_documents: (!conflicting_documents.is_empty())
+                    .then_some(conflicting_documents),
+                generated_documents_ids: (!generated_documents_ids.is_empty())
+                    .then_some(generated_documents_ids),
+                invalid_documents: (!invalid_documents.is_empty()).then_some(invalid_documents),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { settings } => {
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
             }
@@ -135,7 +221,23 @@ impl From<Details> for DetailsView {
                 deleted_documents: Some(deleted_documents),
                 ..DetailsView::default()
             },
-            Details::ClearAll { deleted_documents } => {
+            Details::DocumentDeletionByFilter { original_filter, deleted_documents } => {
+                DetailsView {
+                    original_filter: Some(original_filter),
+                    deleted_documents: Some(deleted_documents),
+                    ..DetailsView::default()
+                }
+            }
+            Details::DocumentEdition { original_filter, function, edited_documents } => {
+                DetailsView {
+                    original_filter,
+                    function: Some(function),
+                    edited_documents: Some(edited_documents),
+                    ..DetailsView::default()
+                }
+            }
+            Details::DocumentExpiration { deleted_documents }
+            | Details::ClearAll { deleted_documents } => {
                 DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
             }
             Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
@@ -160,6 +262,11 @@ impl From<Details> for DetailsView {
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::IndexCompaction { pre_compaction_size, post_compaction_size } => DetailsView {
+                pre_compaction_size: Some(pre_compaction_size),
+                post_compaction_size: Some(post_compaction_size),
+                ..DetailsView::default()
+            },
         }
     }
 }
@@ -460,13 +567,14 @@ async fn get_tasks(
         after_finished_at,
     };
 
+    let progress = index_scheduler.processing_tasks_progress()?;
     let mut tasks_results: Vec<TaskView> = index_scheduler
         .get_tasks_from_authorized_indexes(
             query,
             index_scheduler.filters().search_rules.authorized_indexes(),
         )?
         .into_iter()
-        .map(|t| TaskView::from_task(&t))
+        .map(|t| TaskView::from_task(&t).with_progress(&progress))
         .collect();
 
     // If we were able to fetch the number +1 tasks we asked
@@ -509,13 +617,122 @@ async fn get_task(
         )?
         .first()
     {
-        let task_view = TaskView::from_task(task);
+        let progress = index_scheduler.processing_tasks_progress()?;
+        let task_view = TaskView::from_task(task).with_progress(&progress);
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
     }
 }
 
+/// Returns the full report of the documents skipped by a document addition, one per line.
+async fn get_task_invalid_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let task = get_authorized_task(&index_scheduler, task_uid.into_inner())?;
+
+    let mut report = Vec::new();
+    match task.details {
+        Some(Details::DocumentAdditionOrUpdate {
+            invalid_documents_report: Some(uuid), ..
+        }) => {
+            let mut file = index_scheduler.get_update_file(uuid)?;
+            file.read_to_end(&mut report).map_err(index_scheduler::Error::from)?;
+        }
+        // The report couldn't be written, the invalid documents were all kept in the details.
+        Some(Details::DocumentAdditionOrUpdate { invalid_documents, .. }) => {
+            for document in invalid_documents {
+                serde_json::to_writer(&mut report, &document).unwrap();
+                report.push(b'\n');
+            }
+        }
+        _ => return Err(index_scheduler::Error::TaskReportNotFound(task.uid).into()),
+    }
+
+    Ok(HttpResponse::Ok().content_type("application/x-ndjson").body(report))
+}
+
+/// Returns all the ids generated by a document addition, one per line.
+async fn get_task_generated_documents_ids(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let task = get_authorized_task(&index_scheduler, task_uid.into_inner())?;
+
+    let mut report = Vec::new();
+    match task.details {
+        // Too many ids were generated to be kept in the details, they are all in the report.
+        Some(Details::DocumentAdditionOrUpdate {
+            generated_documents_ids_report: Some(uuid),
+            ..
+        }) => {
+            let mut file = index_scheduler.get_update_file(uuid)?;
+            file.read_to_end(&mut report).map_err(index_scheduler::Error::from)?;
+        }
+        Some(Details::DocumentAdditionOrUpdate { generated_documents_ids, .. }) => {
+            for id in generated_documents_ids {
+                serde_json::to_writer(&mut report, &id).unwrap();
+                report.push(b'\n');
+            }
+        }
+        _ => return Err(index_scheduler::Error::TaskReportNotFound(task.uid).into()),
+    }
+
+    Ok(HttpResponse::Ok().content_type("application/x-ndjson").body(report))
+}
+
+/// Returns the ids of all the documents left out by a document addition because of a
+/// `_version` conflict, one per line.
+async fn get_task_conflicting_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let task = get_authorized_task(&index_scheduler, task_uid.into_inner())?;
+
+    let mut report = Vec::new();
+    match task.details {
+        // Too many documents were conflicting to be kept in the details, they are all in the report.
+        Some(Details::DocumentAdditionOrUpdate {
+            conflicting_documents_report: Some(uuid),
+            ..
+        }) => {
+            let mut file = index_scheduler.get_update_file(uuid)?;
+            file.read_to_end(&mut report).map_err(index_scheduler::Error::from)?;
+        }
+        Some(Details::DocumentAdditionOrUpdate { conflicting_documents, .. }) => {
+            for id in conflicting_documents {
+                serde_json::to_writer(&mut report, &id).unwrap();
+                report.push(b'\n');
+            }
+        }
+        _ => return Err(index_scheduler::Error::TaskReportNotFound(task.uid).into()),
+    }
+
+    Ok(HttpResponse::Ok().content_type("application/x-ndjson").body(report))
+}
+
+/// Returns the task with the given uid if it belongs to an index the requester can access.
+fn get_authorized_task(
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    task_uid: String,
+) -> Result<Task, ResponseError> {
+    let task_uid: TaskId = match task_uid.parse() {
+        Ok(id) => id,
+        Err(_e) => return Err(index_scheduler::Error::InvalidTaskUids { task_uid }.into()),
+    };
+
+    let query = index_scheduler::Query { uids: Some(vec![task_uid]), ..Query::default() };
+    let mut tasks = index_scheduler.get_tasks_from_authorized_indexes(
+        query,
+        index_scheduler.filters().search_rules.authorized_indexes(),
+    )?;
+    match tasks.pop() {
+        Some(task) => Ok(task),
+        None => Err(index_scheduler::Error::TaskNotFound(task_uid).into()),
+    }
+}
+
 pub enum DeserializeDateOption {
     Before,
     After,
@@ -709,9 +926,9 @@ mod tests {
     #[test]
     fn deserialize_task_filter_types() {
         {
-            let params = "types=documentAdditionOrUpdate,documentDeletion,settingsUpdate,indexCreation,indexDeletion,indexUpdate,indexSwap,taskCancelation,taskDeletion,dumpCreation,snapshotCreation";
+            let params = "types=documentAdditionOrUpdate,documentDeletion,documentEdition,documentExpiration,settingsUpdate,indexCreation,indexDeletion,indexUpdate,indexSwap,indexCompaction,taskCancelation,taskDeletion,dumpCreation,snapshotCreation";
             let query = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap();
-            snapshot!(format!("{:?}", query.types.unwrap()), @"[DocumentAdditionOrUpdate, DocumentDeletion, SettingsUpdate, IndexCreation, IndexDeletion, IndexUpdate, IndexSwap, TaskCancelation, TaskDeletion, DumpCreation, SnapshotCreation]");
+            snapshot!(format!("{:?}", query.types.unwrap()), @"[DocumentAdditionOrUpdate, DocumentDeletion, DocumentEdition, DocumentExpiration, SettingsUpdate, IndexCreation, IndexDeletion, IndexUpdate, IndexSwap, IndexCompaction, TaskCancelation, TaskDeletion, DumpCreation, SnapshotCreation]");
         }
         {
             let params = "types=settingsUpdate";
@@ -721,7 +938,7 @@ mod tests {
         {
             let params = "types=createIndex";
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
-            snapshot!(format!("{err}"), @"`createIndex` is not a type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`. at `.types`.");
+            snapshot!(format!("{err}"), @"`createIndex` is not a type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `documentEdition`, `documentExpiration`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `indexCompaction`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`. at `.types`.");
         }
     }
     #[test]
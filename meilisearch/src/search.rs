@@ -4,17 +4,17 @@ use std::str::FromStr;
 use std::time::Instant;
 
 use deserr::DeserializeFromValue;
-use either::Either;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::DeserrError;
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    locales_allow_list, AscDesc, CurationRule, DocumentId, FieldId, FieldsIdsMap, Filter,
+    FilterCondition, FormatOptions, Index, Locale, MatchBounds, MatcherBuilder, PinnedDocument,
+    Search, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -66,6 +66,8 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(error = DeserrError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(error = DeserrError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
 }
 
 impl SearchQuery {
@@ -146,6 +148,10 @@ pub fn perform_search(
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
 
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.clone());
+    }
+
     let max_total_hits = index
         .pagination_max_total_hits(&rtxn)
         .map_err(milli::Error::from)?
@@ -169,13 +175,13 @@ pub fn perform_search(
     let offset = min(offset, max_total_hits);
     let limit = min(limit, max_total_hits.saturating_sub(offset));
 
-    search.offset(offset);
-    search.limit(limit);
+    let filter = match query.filter {
+        Some(ref filter) => Filter::from_json(filter)?,
+        None => None,
+    };
 
-    if let Some(ref filter) = query.filter {
-        if let Some(facets) = parse_filter(filter)? {
-            search.filter(facets);
-        }
+    if let Some(ref facets) = filter {
+        search.filter(facets.clone());
     }
 
     if let Some(ref sort) = query.sort {
@@ -189,7 +195,13 @@ pub fn perform_search(
         search.sort_criteria(sort);
     }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, .. } = search.execute()?;
+    let curation =
+        Curation::new(index, &rtxn, query.q.as_deref().unwrap_or_default(), filter.as_ref())?;
+
+    let milli::SearchResult { documents_ids, matching_words, candidates, .. } = match curation {
+        Some(ref curation) => curation.execute(&mut search, filter.as_ref(), offset, limit)?,
+        None => search.offset(offset).limit(limit).execute()?,
+    };
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
@@ -244,7 +256,14 @@ pub fn perform_search(
         &displayed_ids,
     );
 
-    let tokenizer = TokenizerBuilder::default().build();
+    // the highlighting must tokenize the documents the same way the query was tokenized
+    let allow_list = query.locales.as_deref().map(locales_allow_list);
+    let tokenizer_words = index.tokenizer_words(&rtxn)?;
+    let mut tokenizer_builder = tokenizer_words.tokenizer_builder::<Vec<u8>>();
+    if let Some(ref allow_list) = allow_list {
+        tokenizer_builder.allow_list(allow_list);
+    }
+    let tokenizer = tokenizer_builder.build();
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer);
     formatter_builder.crop_marker(query.crop_marker);
@@ -331,6 +350,143 @@ pub fn perform_search(
     Ok(result)
 }
 
+/// The documents pinned, hidden and boosted by the curation rules matching a search query.
+struct Curation {
+    /// The pinned documents along with their position, sorted by position.
+    pinned: Vec<(usize, DocumentId)>,
+    pinned_ids: RoaringBitmap,
+    hidden: RoaringBitmap,
+    /// The filters of the rules boosting documents.
+    boosts: Vec<String>,
+}
+
+impl Curation {
+    /// Gathers the curation rules of the index matching the given query,
+    /// returns `None` if there is none.
+    ///
+    /// A pinned document is ignored when it doesn't exist, is hidden, or doesn't match
+    /// the filter of the search. When a document is pinned by several rules the first one wins.
+    fn new(
+        index: &Index,
+        rtxn: &milli::heed::RoTxn,
+        query: &str,
+        filter: Option<&Filter>,
+    ) -> Result<Option<Self>, MeilisearchHttpError> {
+        let rules: Vec<CurationRule> = index
+            .curation_rules(rtxn)
+            .map_err(milli::Error::from)?
+            .into_iter()
+            .filter(|rule| rule.matches_query(query))
+            .collect();
+
+        if rules.is_empty() {
+            return Ok(None);
+        }
+
+        let external_documents_ids = index.external_documents_ids(rtxn)?;
+
+        let mut hidden = RoaringBitmap::new();
+        for id in rules.iter().flat_map(|rule| &rule.hide) {
+            if let Some(docid) = external_documents_ids.get(id) {
+                hidden.insert(docid);
+            }
+        }
+
+        let filtered = match filter {
+            Some(filter) => Some(filter.evaluate(rtxn, index)?),
+            None => None,
+        };
+
+        let mut pinned = Vec::new();
+        let mut pinned_ids = RoaringBitmap::new();
+        for PinnedDocument { id, position } in rules.iter().flat_map(|rule| &rule.pin) {
+            if let Some(docid) = external_documents_ids.get(id) {
+                let matches_filter = filtered.as_ref().map_or(true, |f| f.contains(docid));
+                if matches_filter && !hidden.contains(docid) && pinned_ids.insert(docid) {
+                    pinned.push((*position, docid));
+                }
+            }
+        }
+        // the sort is stable, the rules order is kept for the documents pinned at the same position.
+        pinned.sort_by_key(|(position, _)| *position);
+
+        let boosts = rules.into_iter().filter_map(|rule| rule.boost).collect();
+
+        Ok(Some(Curation { pinned, pinned_ids, hidden, boosts }))
+    }
+
+    /// The filter matching the documents boosted by any of the rules,
+    /// returns `None` if no rule boosts documents.
+    fn boost_filter(&self) -> milli::Result<Option<FilterCondition>> {
+        let mut boosts = Vec::new();
+        for boost in &self.boosts {
+            if let Some(filter) = Filter::from_str(boost)? {
+                boosts.push(filter.into());
+            }
+        }
+
+        Ok(match boosts.len() {
+            0 => None,
+            1 => boosts.pop(),
+            _ => Some(FilterCondition::Or(boosts)),
+        })
+    }
+
+    /// Executes the search with the rules applied and returns the requested page.
+    ///
+    /// The boosted documents are ranked among all the candidates, before the other ones, and the
+    /// documents are ranked from the start to be able to move the pinned and hidden ones around.
+    /// The requested page is only extracted once the rules have been applied.
+    fn execute<'a>(
+        &'a self,
+        search: &mut Search<'a>,
+        filter: Option<&Filter<'a>>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<milli::SearchResult, MeilisearchHttpError> {
+        let window = offset + limit + self.hidden.len() as usize + self.pinned.len();
+        search.offset(0).limit(window);
+
+        let mut result = match self.boost_filter()? {
+            Some(boost) => {
+                let filter = filter.cloned().map(FilterCondition::from);
+                let restrict = |condition: FilterCondition<'a>| match &filter {
+                    Some(filter) => {
+                        Filter::from(FilterCondition::And(vec![filter.clone(), condition]))
+                    }
+                    None => Filter::from(condition),
+                };
+
+                search.filter(restrict(boost.clone()));
+                let mut boosted = search.execute()?;
+
+                search.filter(restrict(FilterCondition::Not(Box::new(boost))));
+                let others = search.execute()?;
+
+                boosted.documents_ids.extend(others.documents_ids);
+                boosted.candidates |= others.candidates;
+                boosted
+            }
+            None => search.execute()?,
+        };
+
+        let mut documents: Vec<_> = result
+            .documents_ids
+            .into_iter()
+            .filter(|docid| !self.hidden.contains(*docid) && !self.pinned_ids.contains(*docid))
+            .collect();
+
+        for &(position, docid) in &self.pinned {
+            documents.insert(min(position, documents.len()), docid);
+        }
+
+        result.documents_ids = documents.into_iter().skip(offset).take(limit).collect();
+        result.candidates = (result.candidates - &self.hidden) | &self.pinned_ids;
+
+        Ok(result)
+    }
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -474,10 +630,10 @@ fn make_document(
     Ok(document)
 }
 
-fn format_fields<A: AsRef<[u8]>>(
+fn format_fields(
     document: &Document,
     field_ids_map: &FieldsIdsMap,
-    builder: &MatcherBuilder<'_, A>,
+    builder: &MatcherBuilder<'_>,
     formatted_options: &BTreeMap<FieldId, FormatOptions>,
     compute_matches: bool,
     displayable_ids: &BTreeSet<FieldId>,
@@ -522,9 +678,9 @@ fn format_fields<A: AsRef<[u8]>>(
     Ok((matches_position, document))
 }
 
-fn format_value<A: AsRef<[u8]>>(
+fn format_value(
     value: Value,
-    builder: &MatcherBuilder<'_, A>,
+    builder: &MatcherBuilder<'_>,
     format_options: Option<FormatOptions>,
     infos: &mut Vec<MatchBounds>,
     compute_matches: bool,
@@ -603,49 +759,6 @@ fn format_value<A: AsRef<[u8]>>(
     }
 }
 
-fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
-    match facets {
-        Value::String(expr) => {
-            let condition = Filter::from_str(expr)?;
-            Ok(condition)
-        }
-        Value::Array(arr) => parse_filter_array(arr),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone())),
-    }
-}
-
-fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
-    let mut ands = Vec::new();
-    for value in arr {
-        match value {
-            Value::String(s) => ands.push(Either::Right(s.as_str())),
-            Value::Array(arr) => {
-                let mut ors = Vec::new();
-                for value in arr {
-                    match value {
-                        Value::String(s) => ors.push(s.as_str()),
-                        v => {
-                            return Err(MeilisearchHttpError::InvalidExpression(
-                                &["String"],
-                                v.clone(),
-                            ))
-                        }
-                    }
-                }
-                ands.push(Either::Left(ors));
-            }
-            v => {
-                return Err(MeilisearchHttpError::InvalidExpression(
-                    &["String", "[String]"],
-                    v.clone(),
-                ))
-            }
-        }
-    }
-
-    Ok(Filter::from_array(ands)?)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
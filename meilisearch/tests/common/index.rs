@@ -77,6 +77,11 @@ impl Index<'_> {
         self.service.delete(url).await
     }
 
+    pub async fn compact(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/compact", urlencode(self.uid.as_ref()));
+        self.service.post(url, Value::Null).await
+    }
+
     pub async fn add_documents(
         &self,
         documents: Value,
@@ -187,6 +192,16 @@ impl Index<'_> {
         self.service.post_encoded(url, serde_json::to_value(&ids).unwrap(), self.encoder).await
     }
 
+    pub async fn delete_document_by_filter(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/delete", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
+    pub async fn edit_documents(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn settings(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.get(url).await
@@ -321,6 +321,127 @@ async fn error_add_malformed_csv_documents() {
     assert_eq!(response["link"], json!("https://docs.meilisearch.com/errors#malformed-payload"));
 }
 
+#[actix_rt::test]
+async fn add_csv_documents_with_custom_dialect() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = "id:number\ttitle\tavailable:boolean\ttags:array\tdimensions:json\n\
+                     1\t'hammer\t42'\ttrue\ttools|garden\t{\"width\": 10}\n";
+    let (response, code) = index
+        .service
+        .post_str(
+            "/indexes/test/documents?csvDelimiter=%09&csvQuote='&csvArraySeparator=%7C",
+            documents,
+            ("content-type", "text/csv"),
+        )
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({
+            "id": 1,
+            "title": "hammer\t42",
+            "available": true,
+            "tags": ["tools", "garden"],
+            "dimensions": { "width": 10 },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn add_csv_documents_with_escape_or_without_quoting() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .post_str(
+            "/indexes/test/documents?csvEscape=%5C",
+            "id:number,title\n1,\"a \\\"big\\\" hammer\"\n",
+            ("content-type", "text/csv"),
+        )
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .service
+        .post_str(
+            "/indexes/test/documents?csvQuoting=false",
+            "id:number,title\n2,\"nails\"\n",
+            ("content-type", "text/csv"),
+        )
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "title": "a \"big\" hammer" }));
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "title": "\"nails\"" }));
+}
+
+#[actix_rt::test]
+async fn error_add_csv_documents_bad_dialect() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .post_str("/indexes/test/documents?csvDelimiter=ab", "id\n1", ("content-type", "text/csv"))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_csv_delimiter");
+
+    let (response, code) = index
+        .service
+        .post_str(
+            "/indexes/test/documents?csvArraySeparator=",
+            "id\n1",
+            ("content-type", "text/csv"),
+        )
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_csv_array_separator");
+
+    let (response, code) =
+        index.service.post("/indexes/test/documents?csvQuote='", json!([{ "id": 1 }])).await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_csv_dialect");
+
+    let (response, code) = index
+        .service
+        .post_str("/indexes/test/documents?csvQuoting=maybe", "id\n1", ("content-type", "text/csv"))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_csv_quoting");
+
+    let (response, code) = index
+        .service
+        .post_str("/indexes/test/documents?csvEscape=%5C%5C", "id\n1", ("content-type", "text/csv"))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_csv_escape");
+
+    let (response, code) = index
+        .service
+        .post_str(
+            "/indexes/test/documents",
+            "id:number,available:boolean\n1,maybe",
+            ("content-type", "text/csv"),
+        )
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "malformed_payload");
+}
+
 #[actix_rt::test]
 async fn error_add_malformed_json_documents() {
     let document = r#"[{"id": 1}, {id: 2}]"#;
@@ -896,6 +1017,33 @@ async fn error_add_documents_payload_size() {
     assert_eq!(code, 413);
 }
 
+#[actix_rt::test]
+async fn error_add_documents_decompressed_payload_size() {
+    let server = Server::new().await;
+    let index = server.index_with_encoder("test", Encoder::Gzip);
+    index.create(Some("id")).await;
+    let document = json!(
+        {
+            "id": "11",
+            "content": "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec metus erat, consequat in blandit venenatis, ultricies eu ipsum. Etiam luctus elit et mollis ultrices. Nam turpis risus, dictum non eros in, eleifend feugiat elit. Morbi non dolor pulvinar, sagittis mi sed, ultricies lorem. Nulla ultricies sem metus. Donec at suscipit quam, sed elementum mi. Suspendisse potenti. Fusce pharetra turpis tortor, sed eleifend odio dapibus ut. Nulla facilisi. Suspendisse elementum, dui eget aliquet dignissim, ex tellus aliquam nisl, at eleifend nisl metus tempus diam. Mauris fermentum sollicitudin efficitur. Donec dignissim est vitae elit finibus faucibus"
+        }
+    );
+    // the compressed payload is far below the size limit, but not once decompressed.
+    let documents: Vec<_> = (0..16000).into_iter().map(|_| document.clone()).collect();
+    let documents = json!(documents);
+    let (response, code) = index.add_documents(documents, None).await;
+
+    let expected_response = json!({
+        "message": "The provided payload reached the size limit of 10485760 bytes once decompressed.",
+        "code": "decompressed_payload_too_large",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#decompressed-payload-too-large"
+    });
+
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 413);
+}
+
 #[actix_rt::test]
 async fn error_primary_key_inference() {
     let server = Server::new().await;
@@ -1090,3 +1238,425 @@ async fn batch_several_documents_addition() {
     assert_eq!(code, 200, "failed with `{}`", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 120);
 }
+
+#[actix_rt::test]
+async fn add_documents_with_version_precondition() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([{ "id": 1, "title": "foo" }, { "id": 2, "title": "bar" }]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(0).await;
+
+    // The document 1 is at version 1, the document 2 is pushed with a stale version.
+    let documents = json!([
+        { "id": 1, "title": "foo2", "_version": 1 },
+        { "id": 2, "title": "bar2", "_version": 0 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_task(1).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["receivedDocuments"], 2);
+    assert_eq!(response["details"]["indexedDocuments"], 1);
+    assert_eq!(response["details"]["conflictingDocuments"], json!(["2"]));
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "title": "foo2" }));
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "title": "bar" }));
+
+    let (response, code) =
+        index.service.get("/indexes/test/documents/1?fields=title&retrieveVersion=true").await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "title": "foo2", "_version": 2 }));
+
+    let (response, code) =
+        index.service.get("/indexes/test/documents?fields=title&retrieveVersion=true").await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["results"],
+        json!([{ "title": "foo2", "_version": 2 }, { "title": "bar", "_version": 1 }])
+    );
+
+    // A deleted document keeps its version, adding it back continues from it.
+    index.delete_document(1).await;
+    index.wait_task(2).await;
+    let documents = json!([{ "id": 1, "title": "foo3", "_version": 0 }]);
+    index.add_documents(documents, None).await;
+    index.wait_task(3).await;
+    let (response, _code) = index.get_task(3).await;
+    assert_eq!(response["details"]["conflictingDocuments"], json!(["1"]));
+}
+
+#[actix_rt::test]
+async fn add_documents_with_many_conflicting_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..150).map(|i| json!({ "id": i })).collect();
+    index.add_documents(json!(documents), Some("id")).await;
+    index.wait_task(0).await;
+
+    let documents: Vec<_> = (0..150).map(|i| json!({ "id": i, "_version": 0 })).collect();
+    index.add_documents(json!(documents), None).await;
+    index.wait_task(1).await;
+
+    // only the first ids are kept in the details of the task.
+    let (response, code) = index.get_task(1).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    let conflicting = response["details"]["conflictingDocuments"].as_array().unwrap();
+    assert_eq!(conflicting.len(), 100);
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get().uri("/tasks/1/conflicting-documents").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let body = test::read_body(res).await;
+    let ids: Vec<Value> =
+        serde_json::Deserializer::from_slice(&body).into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(ids.len(), 150);
+    assert_eq!(&ids[..100], conflicting.as_slice());
+}
+
+#[actix_rt::test]
+async fn error_add_documents_invalid_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([{ "id": 1, "title": "foo", "_version": "one" }]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "invalid_document_version");
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("is invalid. Was expecting a positive integer but instead got `\"one\"`."));
+}
+
+#[actix_rt::test]
+async fn error_add_documents_invalid_expires_at() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([{ "id": 1, "title": "foo", "_expiresAt": "tomorrow" }]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "invalid_document_expires_at");
+    assert!(response["error"]["message"].as_str().unwrap().ends_with(
+        "is invalid. Was expecting an RFC 3339 date or a Unix timestamp in seconds but instead got `\"tomorrow\"`."
+    ));
+}
+
+#[actix_rt::test]
+async fn add_documents_with_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(json!({
+            "schema": {
+                "title": { "type": "string", "required": true },
+                "price": { "type": "number" },
+            }
+        }))
+        .await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    // the values are coerced into the type of their field.
+    let documents = json!([{ "id": 1, "title": 42, "price": "12.5" }]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(1).await;
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "id": 1, "title": "42", "price": 12.5 }));
+
+    let documents = json!([{ "id": 2, "title": "foo", "price": "cheap" }]);
+    index.add_documents(documents, None).await;
+    index.wait_task(2).await;
+    let (response, code) = index.get_task(2).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "invalid_document_schema");
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("the field `/price` must be of type number but instead got `\"cheap\"`."));
+
+    let documents = json!([{ "id": 3, "price": 10 }]);
+    index.add_documents(documents, None).await;
+    index.wait_task(3).await;
+    let (response, code) = index.get_task(3).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "invalid_document_schema");
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("the required field `/title` is missing."));
+
+    // a partial update doesn't have to repeat the required fields.
+    index.update_documents(json!([{ "id": 1, "price": "13" }]), None).await;
+    index.wait_task(4).await;
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "id": 1, "title": "42", "price": 13 }));
+}
+
+#[actix_rt::test]
+async fn add_documents_with_autogenerated_ids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([{ "id": "kevin", "name": "kevin" }, { "name": "kevina" }]);
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?primaryKey=id&autogenerateIds=true", documents)
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["indexedDocuments"], 2);
+    let generated_ids = response["details"]["generatedDocumentsIds"].as_array().unwrap();
+    assert_eq!(generated_ids.len(), 1);
+    let generated_id = generated_ids[0].as_str().unwrap();
+    assert_eq!(generated_id.len(), 36);
+
+    let (response, code) =
+        index.service.get(format!("/indexes/test/documents/{generated_id}")).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": generated_id, "name": "kevina" }));
+
+    // without the flag the documents missing the primary key are rejected.
+    index.add_documents(json!([{ "name": "benoit" }]), None).await;
+    index.wait_task(1).await;
+    let (response, code) = index.get_task(1).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "missing_document_id");
+}
+
+#[actix_rt::test]
+async fn add_documents_with_many_autogenerated_ids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..150).map(|i| json!({ "name": format!("kevin{i}") })).collect();
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?primaryKey=id&autogenerateIds=true", json!(documents))
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    // only the first ids are kept in the details of the task.
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    let generated_ids = response["details"]["generatedDocumentsIds"].as_array().unwrap();
+    assert_eq!(generated_ids.len(), 100);
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get().uri("/tasks/0/generated-documents-ids").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let body = test::read_body(res).await;
+    let ids: Vec<Value> =
+        serde_json::Deserializer::from_slice(&body).into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(ids.len(), 150);
+    assert_eq!(&ids[..100], generated_ids.as_slice());
+}
+
+#[actix_rt::test]
+async fn error_add_documents_bad_autogenerate_ids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?autogenerateIds=doggo", json!([{ "name": "kevin" }]))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_autogenerate_ids");
+}
+
+#[actix_rt::test]
+async fn add_documents_skipping_invalid_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "hammer" },
+        { "id": "invalid id", "title": "screwdriver" },
+        { "id": 3, "title": "nails" },
+        { "id": "another invalid id", "title": "pliers" },
+    ]);
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?primaryKey=id&skipInvalidDocuments=true", documents)
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["receivedDocuments"], 4);
+    assert_eq!(response["details"]["indexedDocuments"], 2);
+    let invalid_documents = response["details"]["invalidDocuments"].as_array().unwrap();
+    assert_eq!(invalid_documents.len(), 2);
+    assert_eq!(invalid_documents[0]["offset"], 1);
+    assert_eq!(invalid_documents[0]["documentId"], json!(null));
+    assert_eq!(invalid_documents[0]["error"]["code"], "invalid_document_id");
+
+    // the report contains one invalid document per line.
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get().uri("/tasks/0/invalid-documents").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let body = test::read_body(res).await;
+    let report: Vec<Value> = serde_json::Deserializer::from_slice(&body)
+        .into_iter::<Value>()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0]["offset"], 1);
+    assert_eq!(report[0]["error"]["code"], "invalid_document_id");
+    assert_eq!(report[1]["offset"], 3);
+    assert_eq!(report[1]["error"]["code"], "invalid_document_id");
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"].as_array().unwrap().len(), 2);
+
+    // only the document additions have a report.
+    let (response, code) =
+        index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(1).await;
+    let (response, code) = index.service.get("/tasks/1/invalid-documents").await;
+    assert_eq!(code, 404, "response: {}", response);
+    assert_eq!(response["code"], "task_not_found");
+    let (response, code) = index.service.get("/tasks/42/invalid-documents").await;
+    assert_eq!(code, 404, "response: {}", response);
+    assert_eq!(response["code"], "task_not_found");
+}
+
+#[actix_rt::test]
+async fn error_add_documents_bad_skip_invalid_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?skipInvalidDocuments=doggo", json!([{ "id": 1 }]))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_skip_invalid_documents");
+}
+
+#[actix_rt::test]
+async fn add_documents_with_composite_primary_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .post("/indexes", json!({ "uid": "test", "primaryKey": ["tenant_id", "sku"] }))
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let documents = json!([
+        { "tenant_id": "acme", "sku": 1, "title": "hammer" },
+        { "tenant_id": "globex", "sku": 1, "title": "screwdriver" },
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["primaryKey"], json!(["tenant_id", "sku"]));
+
+    let (response, code) = index.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["fieldDistribution"], json!({ "tenant_id": 2, "sku": 2, "title": 2 }));
+
+    let (response, code) = index.service.get("/indexes/test/documents/acme:1").await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "tenant_id": "acme", "sku": 1, "title": "hammer" }));
+
+    let (response, code) = index.service.get("/indexes/test/documents/globex:1").await;
+    assert_eq!(code, 200);
+    assert_eq!(response["title"], "screwdriver");
+
+    let documents = json!([{ "tenant_id": "acme", "title": "nails" }]);
+    index.add_documents(documents, None).await;
+    index.wait_task(2).await;
+    let (response, code) = index.get_task(2).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "missing_document_id");
+
+    // a composite primary key must be made of at least two distinct fields.
+    let (response, code) =
+        index.service.post("/indexes", json!({ "uid": "other", "primaryKey": ["id"] })).await;
+    assert_eq!(code, 400, "response: {}", response);
+    assert_eq!(response["code"], "invalid_index_primary_key");
+}
+
+#[actix_rt::test]
+async fn add_documents_with_composite_primary_key_in_query() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "tenant_id": "acme", "sku": 1, "title": "hammer" },
+        { "tenant_id": "globex", "sku": 1, "title": "screwdriver" },
+    ]);
+    // the fields of a composite primary key are sent as a JSON array.
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?primaryKey=%5B%22tenant_id%22%2C%22sku%22%5D", documents)
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(0).await;
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["primaryKey"], json!(["tenant_id", "sku"]));
+
+    let (response, code) = index.service.get("/indexes/test/documents/globex:1").await;
+    assert_eq!(code, 200);
+    assert_eq!(response["title"], "screwdriver");
+
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents?primaryKey=%5B%22tenant_id%22%5D", json!([{ "id": 1 }]))
+        .await;
+    assert_eq!(code, 400, "response: {}", response);
+    assert_eq!(response["code"], "invalid_index_primary_key");
+    assert_eq!(
+        response["message"],
+        "`[\"tenant_id\"]` is not a valid composite primary key. It must be a JSON array of at least two distinct and non-empty fields. at `.primaryKey`."
+    );
+}
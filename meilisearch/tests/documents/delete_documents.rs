@@ -135,3 +135,94 @@ async fn delete_no_document_batch() {
     assert_eq!(code, 200);
     assert_eq!(response["results"].as_array().unwrap().len(), 3);
 }
+
+#[actix_rt::test]
+async fn delete_document_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings(json!({ "filterableAttributes": ["color"] })).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "color": "red" },
+                { "id": 1, "color": "blue" },
+                { "id": 2, "color": "blue" },
+                { "id": 3 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.delete_document_by_filter(json!({ "filter": "color = blue" })).await;
+    assert_eq!(code, 202, "{}", response);
+    assert_eq!(response["type"], "documentDeletion");
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["originalFilter"], "\"color = blue\"");
+    assert_eq!(response["details"]["deletedDocuments"], 2);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"], json!([{ "id": 0, "color": "red" }, { "id": 3 }]));
+
+    let (_response, code) =
+        index.delete_document_by_filter(json!({ "filter": ["color NOT EXISTS"] })).await;
+    assert_eq!(code, 202);
+
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["deletedDocuments"], 1);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"], json!([{ "id": 0, "color": "red" }]));
+}
+
+#[actix_rt::test]
+async fn delete_document_by_filter_on_non_filterable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.add_documents(json!([{ "id": 0, "color": "red" }]), Some("id")).await;
+    index.wait_task(0).await;
+
+    let (_response, code) =
+        index.delete_document_by_filter(json!({ "filter": "color = red" })).await;
+    assert_eq!(code, 202);
+
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["details"]["deletedDocuments"], 0);
+    assert_eq!(response["error"]["code"], "invalid_search_filter");
+}
+
+#[actix_rt::test]
+async fn error_delete_document_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.delete_document_by_filter(json!({})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "missing_document_filter");
+
+    let (response, code) = index.delete_document_by_filter(json!({ "filter": 1 })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_delete_filter");
+
+    let (response, code) = index.delete_document_by_filter(json!({ "filter": "color = " })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_delete_filter");
+
+    let (response, code) = index.delete_document_by_filter(json!({ "filter": [] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_delete_filter");
+
+    let (response, code) =
+        index.delete_document_by_filter(json!({ "filter": "color = red" })).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "index_not_found");
+}
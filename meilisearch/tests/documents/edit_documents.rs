@@ -0,0 +1,117 @@
+use serde_json::json;
+
+use crate::common::{GetAllDocumentsOptions, Server};
+
+#[actix_rt::test]
+async fn edit_documents_by_function() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings(json!({ "filterableAttributes": ["age"] })).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "KEFIR", "age": 2 },
+                { "id": 1, "name": "Intel", "age": 8 },
+                { "id": 2, "name": "Vénus", "age": 4 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents(json!({
+            "filter": "age < 5",
+            "function": "doc.name = doc.name.to_lower(); doc.age += 1;",
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    assert_eq!(response["type"], "documentEdition");
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["originalFilter"], "\"age < 5\"");
+    assert_eq!(response["details"]["editedDocuments"], 2);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["results"],
+        json!([
+            { "id": 0, "name": "kefir", "age": 3 },
+            { "id": 1, "name": "Intel", "age": 8 },
+            { "id": 2, "name": "vénus", "age": 5 },
+        ])
+    );
+
+    // without filter the function runs on all the documents.
+    let (_response, code) =
+        index.edit_documents(json!({ "function": "if doc.age > 4 { doc.senior = true }" })).await;
+    assert_eq!(code, 202);
+
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["editedDocuments"], 2);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["results"],
+        json!([
+            { "id": 0, "name": "kefir", "age": 3 },
+            { "id": 1, "name": "Intel", "age": 8, "senior": true },
+            { "id": 2, "name": "vénus", "age": 5, "senior": true },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_function_failures() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.add_documents(json!([{ "id": 0, "name": "kefir" }]), Some("id")).await;
+    index.wait_task(0).await;
+
+    // the function can't change the primary key.
+    let (_response, code) = index.edit_documents(json!({ "function": "doc.id = 12" })).await;
+    assert_eq!(code, 202);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["details"]["editedDocuments"], 0);
+    assert_eq!(response["error"]["code"], "document_edition_failed");
+
+    // the function can't run forever.
+    let (_response, code) = index.edit_documents(json!({ "function": "loop {}" })).await;
+    assert_eq!(code, 202);
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "document_edition_failed");
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"], json!([{ "id": 0, "name": "kefir" }]));
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_by_function() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.edit_documents(json!({})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "missing_document_edit_function");
+
+    let (response, code) = index.edit_documents(json!({ "function": 12 })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_edit_function");
+
+    // the function doesn't compile.
+    let (response, code) = index.edit_documents(json!({ "function": "doc.name = " })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_edit_function");
+
+    let (response, code) =
+        index.edit_documents(json!({ "function": "doc.a = 1", "filter": "age = " })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_edit_filter");
+}
@@ -378,3 +378,154 @@ async fn get_documents_displayed_attributes_is_ignored() {
     assert_eq!(response.as_object().unwrap().keys().count(), 16);
     assert!(response.as_object().unwrap().get("gender").is_some());
 }
+
+#[actix_rt::test]
+async fn export_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["color"] })).await;
+    let documents = json!([
+        { "id": 1, "title": "hammer", "color": "red", "size": { "width": 10 }, "available": true, "tags": ["tool"] },
+        { "id": 2, "title": "screwdriver", "color": "blue", "available": false, "tags": "tool" },
+        { "id": 3, "title": "nails, \"small\"", "color": "red" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents/export?filter=color%20%3D%20red&fields=id,title")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("content-type").unwrap(), "application/x-ndjson");
+    let body = test::read_body(res).await;
+    let documents: Vec<Value> =
+        serde_json::Deserializer::from_slice(&body).into_iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        documents,
+        vec![
+            json!({ "id": 1, "title": "hammer" }),
+            json!({ "id": 3, "title": "nails, \"small\"" }),
+        ]
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents/export?format=csv&fields=id,title,size.width")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("content-type").unwrap(), "text/csv");
+    let body = test::read_body(res).await;
+    assert_eq!(
+        std::str::from_utf8(&body).unwrap(),
+        "id:number,title,size.width:number\n1,hammer,10\n2,screwdriver,\n3,\"nails, \"\"small\"\"\",\n"
+    );
+
+    // the columns mixing several types are exported as JSON.
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents/export?format=csv&fields=id,available,tags")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let body = test::read_body(res).await;
+    assert_eq!(
+        std::str::from_utf8(&body).unwrap(),
+        "id:number,available:boolean,tags:json\n1,true,\"[\"\"tool\"\"]\"\n2,false,\"\"\"tool\"\"\"\n3,,\n"
+    );
+}
+
+#[actix_rt::test]
+async fn error_export_documents_bad_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "color": "red" }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.service.get("/indexes/test/documents/export?filter=color%20%3D%20red").await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_filter");
+
+    let (response, code) = index.service.get("/indexes/test/documents/export?format=xml").await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_export_format");
+}
+
+#[actix_rt::test]
+async fn fetch_documents_with_filter_and_ids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["color"] })).await;
+    let documents = json!([
+        { "id": 1, "title": "hammer", "color": "red" },
+        { "id": 2, "title": "screwdriver", "color": "blue" },
+        { "id": 3, "title": "nails", "color": "red" },
+        { "id": 4, "title": "saw", "color": "red" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .service
+        .post(
+            "/indexes/test/documents/fetch",
+            json!({ "filter": "color = red", "fields": ["id", "title"], "offset": 1, "limit": 1 }),
+        )
+        .await;
+    assert_eq!(code, 200, "response: {}", response);
+    assert_eq!(
+        response,
+        json!({ "results": [{ "id": 3, "title": "nails" }], "offset": 1, "limit": 1, "total": 3 })
+    );
+
+    let (response, code) = index
+        .service
+        .post(
+            "/indexes/test/documents/fetch",
+            json!({ "filter": "color = red", "ids": [2, "4", "42"], "fields": ["id"] }),
+        )
+        .await;
+    assert_eq!(code, 200, "response: {}", response);
+    assert_eq!(response["results"], json!([{ "id": 4 }]));
+    assert_eq!(response["total"], 1);
+
+    // the documents are returned in the order of the requested ids.
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents/fetch", json!({ "ids": [4, 1, "3", 1], "fields": ["id"] }))
+        .await;
+    assert_eq!(code, 200, "response: {}", response);
+    assert_eq!(response["results"], json!([{ "id": 4 }, { "id": 1 }, { "id": 3 }]));
+    assert_eq!(response["total"], 3);
+
+    let (response, code) = index.service.post("/indexes/test/documents/fetch", json!({})).await;
+    assert_eq!(code, 200, "response: {}", response);
+    assert_eq!(response["results"].as_array().unwrap().len(), 4);
+    assert_eq!(response["total"], 4);
+}
+
+#[actix_rt::test]
+async fn error_fetch_documents_bad_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "color": "red" }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .service
+        .post("/indexes/test/documents/fetch", json!({ "filter": "color = red" }))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_filter");
+
+    let (response, code) =
+        index.service.post("/indexes/test/documents/fetch", json!({ "ids": [[1]] })).await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_ids");
+
+    let (response, code) =
+        index.service.post("/indexes/test/documents/fetch", json!({ "limit": "ten" })).await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_limit");
+}
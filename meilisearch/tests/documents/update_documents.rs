@@ -125,6 +125,83 @@ async fn update_document_gzip_encoded() {
     assert_eq!(response.to_string(), r##"{"doc_id":1,"content":"foo","other":"bar"}"##);
 }
 
+#[actix_rt::test]
+async fn deep_merge_document() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "price": { "eur": 10, "usd": 12 },
+            "tags": ["red", "blue"],
+        }
+    ]);
+    let (_response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    let documents = json!([
+        {
+            "id": 1,
+            "price": { "eur": 11 },
+            "tags": ["blue", "green"],
+        }
+    ]);
+    let (response, code) = index
+        .service
+        .put("/indexes/test/documents?mergeStrategy=deep&arrayMergeStrategy=union", documents)
+        .await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_task(1).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({ "id": 1, "price": { "eur": 11, "usd": 12 }, "tags": ["red", "blue", "green"] })
+    );
+}
+
+#[actix_rt::test]
+async fn error_update_documents_bad_merge_strategy() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .service
+        .put("/indexes/test/documents?mergeStrategy=doggo", json!([{ "id": 1 }]))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_merge_strategy");
+
+    let (response, code) = index
+        .service
+        .put(
+            "/indexes/test/documents?mergeStrategy=deep&arrayMergeStrategy=doggo",
+            json!([{ "id": 1 }]),
+        )
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_array_merge_strategy");
+
+    // the array merge strategy is only used by the deep merge.
+    let (response, code) = index
+        .service
+        .put("/indexes/test/documents?arrayMergeStrategy=union", json!([{ "id": 1 }]))
+        .await;
+    assert_eq!(code, 400);
+    assert_eq!(response["code"], "invalid_document_array_merge_strategy");
+    assert_eq!(
+        response["message"],
+        "The `arrayMergeStrategy` parameter can only be used with the `deep` merge strategy."
+    );
+}
+
 #[actix_rt::test]
 async fn update_larger_dataset() {
     let server = Server::new().await;
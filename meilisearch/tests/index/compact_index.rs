@@ -0,0 +1,58 @@
+use serde_json::json;
+
+use crate::common::{GetAllDocumentsOptions, Server};
+
+#[actix_rt::test]
+async fn compact_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let documents: Vec<_> = (0..1000).map(|id| json!({ "id": id, "text": "hello" })).collect();
+    index.add_documents(json!(documents), Some("id")).await;
+    index.wait_task(0).await;
+    let (_response, code) = index.delete_batch((0..990).collect()).await;
+    assert_eq!(code, 202);
+    index.wait_task(1).await;
+
+    let (response, code) = index.compact().await;
+    assert_eq!(code, 202, "{}", response);
+    assert_eq!(response["type"], "indexCompaction");
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    let pre_compaction_size = response["details"]["preCompactionSize"].as_u64().unwrap();
+    let post_compaction_size = response["details"]["postCompactionSize"].as_u64().unwrap();
+    assert!(post_compaction_size <= pre_compaction_size);
+
+    // the compacted index is still usable.
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["total"], 10);
+
+    index.add_documents(json!([{ "id": 2000, "text": "world" }]), None).await;
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "world" })).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["hits"], json!([{ "id": 2000, "text": "world" }]));
+}
+
+#[actix_rt::test]
+async fn error_compact_unexisting_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (_, code) = index.compact().await;
+
+    assert_eq!(code, 202);
+
+    let expected_response = json!({
+        "message": "Index `test` not found.",
+        "code": "index_not_found",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#index-not-found"
+    });
+
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"], expected_response);
+}
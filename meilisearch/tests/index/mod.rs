@@ -1,3 +1,4 @@
+mod compact_index;
 mod create_index;
 mod delete_index;
 mod get_index;
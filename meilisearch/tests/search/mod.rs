@@ -681,3 +681,101 @@ async fn faceting_max_values_per_facet() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_with_curation_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["title"],
+            "rules": [{
+                "query": "",
+                "pin": [{ "id": "450465", "position": 0 }],
+                "hide": ["299537"],
+                "boost": "title = \"Escape Room\"",
+            }],
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    let res = index.wait_task(1).await;
+    meili_snap::snapshot!(res["status"], @r###""succeeded""###);
+
+    index
+        .search(json!({}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| &h["id"]).collect();
+            assert_eq!(
+                ids,
+                [&json!("450465"), &json!("522681"), &json!("287947"), &json!("166428")]
+            );
+            assert_eq!(response["estimatedTotalHits"], json!(4));
+        })
+        .await;
+
+    index
+        .search(json!({ "offset": 1, "limit": 2 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let ids: Vec<_> =
+                response["hits"].as_array().unwrap().iter().map(|h| &h["id"]).collect();
+            assert_eq!(ids, [&json!("522681"), &json!("287947")]);
+        })
+        .await;
+
+    // the hidden documents are not counted in the facet distribution
+    index
+        .search(json!({ "facets": ["title"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let distribution = response["facetDistribution"]["title"].as_object().unwrap();
+            assert_eq!(distribution.len(), 4, "{}", response);
+            assert!(!distribution.contains_key("Captain Marvel"), "{}", response);
+        })
+        .await;
+
+    // the rule doesn't match a non-empty query
+    index
+        .search(json!({ "q": "captain" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"][0]["id"], json!("299537"));
+        })
+        .await;
+
+    // a boosted document is moved to the first page even when it is ranked after it
+    index
+        .update_settings(json!({
+            "rules": [{ "query": "", "boost": "title = \"How to Train Your Dragon: The Hidden World\"" }],
+        }))
+        .await;
+    let res = index.wait_task(2).await;
+    meili_snap::snapshot!(res["status"], @r###""succeeded""###);
+
+    index
+        .search(json!({ "limit": 1 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"][0]["id"], json!("166428"));
+            assert_eq!(response["estimatedTotalHits"], json!(5));
+        })
+        .await;
+
+    index
+        .search(json!({ "offset": 1, "limit": 1 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"][0]["id"], json!("287947"));
+        })
+        .await;
+
+    // the boost filters can only use filterable attributes
+    index.update_settings(json!({ "rules": [{ "query": "", "boost": "id = 287947" }] })).await;
+    let res = index.wait_task(3).await;
+    meili_snap::snapshot!(res["status"], @r###""failed""###);
+
+    // an attribute used by a boost filter can't stop being filterable
+    index.update_settings(json!({ "filterableAttributes": [] })).await;
+    let res = index.wait_task(4).await;
+    meili_snap::snapshot!(res["status"], @r###""failed""###);
+}
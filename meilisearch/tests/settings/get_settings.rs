@@ -10,12 +10,16 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("displayed_attributes", json!(["*"]));
     map.insert("searchable_attributes", json!(["*"]));
     map.insert("filterable_attributes", json!([]));
+    map.insert("date_attributes", json!([]));
     map.insert("distinct_attribute", json!(Value::Null));
     map.insert(
         "ranking_rules",
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
     );
     map.insert("stop_words", json!([]));
+    map.insert("separator_tokens", json!([]));
+    map.insert("non_separator_tokens", json!([]));
+    map.insert("dictionary", json!([]));
     map.insert("synonyms", json!({}));
     map.insert(
         "faceting",
@@ -29,6 +33,10 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
             "maxTotalHits": json!(1000),
         }),
     );
+    map.insert("rules", json!([]));
+    map.insert("localized_attributes", json!([]));
+    map.insert("stemming_languages", json!([]));
+    map.insert("schema", json!({}));
     map
 });
 
@@ -48,17 +56,21 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 11);
+    assert_eq!(settings.keys().len(), 19);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
     assert_eq!(settings["sortableAttributes"], json!([]));
+    assert_eq!(settings["dateAttributes"], json!([]));
     assert_eq!(settings["distinctAttribute"], json!(null));
     assert_eq!(
         settings["rankingRules"],
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
     assert_eq!(settings["stopWords"], json!([]));
+    assert_eq!(settings["separatorTokens"], json!([]));
+    assert_eq!(settings["nonSeparatorTokens"], json!([]));
+    assert_eq!(settings["dictionary"], json!([]));
     assert_eq!(
         settings["faceting"],
         json!({
@@ -71,6 +83,10 @@ async fn get_settings() {
             "maxTotalHits": 1000,
         })
     );
+    assert_eq!(settings["rules"], json!([]));
+    assert_eq!(settings["localizedAttributes"], json!([]));
+    assert_eq!(settings["stemmingLanguages"], json!([]));
+    assert_eq!(settings["schema"], json!({}));
 }
 
 #[actix_rt::test]
@@ -262,14 +278,22 @@ macro_rules! test_setting_routes {
 
 test_setting_routes!(
     filterable_attributes put,
+    date_attributes put,
     displayed_attributes put,
     searchable_attributes put,
     distinct_attribute put,
     stop_words put,
+    separator_tokens put,
+    non_separator_tokens put,
+    dictionary put,
     ranking_rules put,
     synonyms put,
     pagination patch,
-    faceting patch
+    faceting patch,
+    rules put,
+    localized_attributes put,
+    stemming_languages put,
+    schema put
 );
 
 #[actix_rt::test]
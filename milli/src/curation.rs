@@ -0,0 +1,112 @@
+use deserr::DeserializeFromValue;
+use serde::{Deserialize, Serialize};
+
+/// A curation rule, it pins, hides or boosts documents when the search query matches its pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct CurationRule {
+    /// The pattern the search query is compared to, case insensitively.
+    pub query: String,
+    /// How the search query must be compared to the pattern.
+    #[serde(default)]
+    #[deserr(default)]
+    pub match_type: QueryMatchType,
+    /// The documents that must be displayed at a fixed position in the results.
+    #[serde(default)]
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocument>,
+    /// The external ids of the documents that must never be returned.
+    #[serde(default)]
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    /// A filter, the documents matching it are moved in front of the other ranked documents.
+    #[serde(default)]
+    #[deserr(default)]
+    pub boost: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocument {
+    /// The external id of the pinned document.
+    pub id: String,
+    /// The zero-based position of the document in the results.
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum QueryMatchType {
+    /// The query must be equal to the pattern.
+    Exact,
+    /// The query must contain the words of the pattern, in the same order.
+    Contains,
+}
+
+impl Default for QueryMatchType {
+    fn default() -> Self {
+        Self::Exact
+    }
+}
+
+impl CurationRule {
+    /// Returns `true` if the rule must be applied to the given search query.
+    ///
+    /// The query and the pattern are compared word by word, ignoring the case
+    /// and the number of whitespaces between the words.
+    pub fn matches_query(&self, query: &str) -> bool {
+        let pattern = normalize_words(&self.query);
+        let query = normalize_words(query);
+        match self.match_type {
+            QueryMatchType::Exact => query == pattern,
+            QueryMatchType::Contains => {
+                pattern.is_empty() || format!(" {query} ").contains(&format!(" {pattern} "))
+            }
+        }
+    }
+}
+
+fn normalize_words(s: &str) -> String {
+    s.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(query: &str, match_type: QueryMatchType) -> CurationRule {
+        CurationRule {
+            query: query.to_string(),
+            match_type,
+            pin: vec![],
+            hide: vec![],
+            boost: None,
+        }
+    }
+
+    #[test]
+    fn matches_query() {
+        let exact = rule("Red  Shoes", QueryMatchType::Exact);
+        assert!(exact.matches_query("red shoes"));
+        assert!(exact.matches_query("  RED shoes "));
+        assert!(!exact.matches_query("red shoes for kids"));
+        assert!(!exact.matches_query(""));
+
+        let contains = rule("red shoes", QueryMatchType::Contains);
+        assert!(contains.matches_query("red shoes"));
+        assert!(contains.matches_query("cheap Red Shoes for kids"));
+        assert!(!contains.matches_query("shoes red"));
+        assert!(!contains.matches_query("bored shoes"));
+
+        let placeholder = rule("", QueryMatchType::Exact);
+        assert!(placeholder.matches_query(""));
+        assert!(!placeholder.matches_query("shoes"));
+
+        let everything = rule("", QueryMatchType::Contains);
+        assert!(everything.matches_query(""));
+        assert!(everything.matches_query("shoes"));
+    }
+}
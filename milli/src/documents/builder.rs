@@ -88,7 +88,17 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 
     /// Appends a new CSV file into the batch and updates the `DocumentsBatchIndex` accordingly.
-    pub fn append_csv<R: io::Read>(&mut self, mut reader: csv::Reader<R>) -> Result<(), Error> {
+    pub fn append_csv<R: io::Read>(&mut self, reader: csv::Reader<R>) -> Result<(), Error> {
+        self.append_csv_with_array_separator(reader, DEFAULT_CSV_ARRAY_SEPARATOR)
+    }
+
+    /// Appends a new CSV file into the batch, the values of the `:array` columns
+    /// being split on the given separator.
+    pub fn append_csv_with_array_separator<R: io::Read>(
+        &mut self,
+        mut reader: csv::Reader<R>,
+        array_separator: &str,
+    ) -> Result<(), Error> {
         // Make sure that we insert the fields ids in order as the obkv writer has this requirement.
         let mut typed_fields_ids: Vec<_> = reader
             .headers()?
@@ -142,6 +152,46 @@ impl<W: Write> DocumentsBatchBuilder<W> {
                             to_writer(&mut self.value_buffer, value)?;
                         }
                     }
+                    AllowedType::Boolean => {
+                        if value.trim().is_empty() {
+                            to_writer(&mut self.value_buffer, &Value::Null)?;
+                        } else {
+                            match value.trim().parse::<bool>() {
+                                Ok(boolean) => to_writer(&mut self.value_buffer, &boolean)?,
+                                Err(error) => {
+                                    return Err(Error::ParseBool {
+                                        error,
+                                        line,
+                                        value: value.to_string(),
+                                    });
+                                }
+                            }
+                        }
+                    }
+                    AllowedType::Array => {
+                        if value.is_empty() {
+                            to_writer(&mut self.value_buffer, &Value::Null)?;
+                        } else {
+                            let values: Vec<_> = value.split(array_separator).collect();
+                            to_writer(&mut self.value_buffer, &values)?;
+                        }
+                    }
+                    AllowedType::Json => {
+                        if value.trim().is_empty() {
+                            to_writer(&mut self.value_buffer, &Value::Null)?;
+                        } else {
+                            match serde_json::from_str::<Value>(value) {
+                                Ok(json) => to_writer(&mut self.value_buffer, &json)?,
+                                Err(error) => {
+                                    return Err(Error::ParseJson {
+                                        error,
+                                        line,
+                                        value: value.to_string(),
+                                    });
+                                }
+                            }
+                        }
+                    }
                 }
 
                 // We insert into the obkv writer the value buffer that has been filled just above.
@@ -170,10 +220,16 @@ impl<W: Write> DocumentsBatchBuilder<W> {
     }
 }
 
+/// The separator used to split the values of the `:array` columns by default.
+pub const DEFAULT_CSV_ARRAY_SEPARATOR: &str = ",";
+
 #[derive(Debug)]
 enum AllowedType {
     String,
     Number,
+    Boolean,
+    Array,
+    Json,
 }
 
 fn parse_csv_header(header: &str) -> (&str, AllowedType) {
@@ -182,6 +238,9 @@ fn parse_csv_header(header: &str) -> (&str, AllowedType) {
         Some((field_name, field_type)) => match field_type {
             "string" => (field_name, AllowedType::String),
             "number" => (field_name, AllowedType::Number),
+            "boolean" => (field_name, AllowedType::Boolean),
+            "array" => (field_name, AllowedType::Array),
+            "json" => (field_name, AllowedType::Json),
             // if the pattern isn't reconized, we keep the whole field.
             _otherwise => (header, AllowedType::String),
         },
@@ -556,6 +615,51 @@ mod test {
         assert!(builder.append_csv(csv).is_err());
     }
 
+    #[test]
+    fn typed_columns_in_header() {
+        let csv_content = "id:number\tavailable:boolean\ttags:array\tdimensions:json\n\
+                           1\ttrue\tred|blue\t{\"width\": 10}\n\
+                           2\t\t\t";
+        let csv = csv::ReaderBuilder::new().delimiter(b'\t').from_reader(Cursor::new(csv_content));
+
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        builder.append_csv_with_array_separator(csv, "|").unwrap();
+        let vector = builder.into_inner().unwrap();
+
+        let (mut cursor, index) = DocumentsBatchReader::from_reader(Cursor::new(vector))
+            .unwrap()
+            .into_cursor_and_fields_index();
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(
+            val,
+            json!({
+                "id": 1,
+                "available": true,
+                "tags": ["red", "blue"],
+                "dimensions": { "width": 10 },
+            })
+        );
+
+        let doc = cursor.next_document().unwrap().unwrap();
+        let val = obkv_to_object(&doc, &index).map(Value::from).unwrap();
+        assert_eq!(val, json!({ "id": 2, "available": null, "tags": null, "dimensions": null }));
+    }
+
+    #[test]
+    fn bad_typed_values() {
+        let csv_content = "id:number,available:boolean\n1,maybe";
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        assert!(matches!(builder.append_csv(csv), Err(Error::ParseBool { line: 1, .. })));
+
+        let csv_content = "id:number,dimensions:json\n1,{\"width\": }";
+        let csv = csv::Reader::from_reader(Cursor::new(csv_content));
+        let mut builder = DocumentsBatchBuilder::new(Vec::new());
+        assert!(matches!(builder.append_csv(csv), Err(Error::ParseJson { line: 1, .. })));
+    }
+
     #[test]
     fn bad_column_count1() {
         let csv_content = r#"city,country,pop
@@ -2,13 +2,14 @@ use std::fs::File;
 use std::{io, str};
 
 use obkv::KvReader;
+use roaring::RoaringBitmap;
 
 use super::{
     DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchIndex, DocumentsBatchReader,
     Error,
 };
 use crate::update::DocumentId;
-use crate::FieldId;
+use crate::{FieldId, PrimaryKey};
 
 /// The `EnrichedDocumentsBatchReader` provides a way to iterate over documents that have
 /// been created with a `DocumentsBatchWriter` and, for the enriched data,
@@ -16,23 +17,37 @@ use crate::FieldId;
 ///
 /// The documents are returned in the form of `obkv::Reader` where each field is identified with a
 /// `FieldId`. The mapping between the field ids and the field names is done thanks to the index.
+///
+/// The documents at the `skipped_offsets` positions were found invalid and are never returned.
+/// When the index has a schema, the `coerced_values` contain, for each returned document, the values
+/// of its declared fields once coerced to their type.
 pub struct EnrichedDocumentsBatchReader<R> {
     documents: DocumentsBatchReader<R>,
-    primary_key: String,
+    primary_key: PrimaryKey,
     external_ids: grenad::ReaderCursor<File>,
+    coerced_values: Option<grenad::ReaderCursor<File>>,
+    skipped_offsets: RoaringBitmap,
 }
 
 impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
     pub fn new(
         documents: DocumentsBatchReader<R>,
-        primary_key: String,
+        primary_key: PrimaryKey,
         external_ids: grenad::Reader<File>,
+        coerced_values: Option<grenad::Reader<File>>,
+        skipped_offsets: RoaringBitmap,
     ) -> Result<Self, Error> {
-        if documents.documents_count() as u64 == external_ids.len() {
+        let valid_count =
+            documents.documents_count() as u64 == external_ids.len() + skipped_offsets.len();
+        let coerced_count =
+            coerced_values.as_ref().map_or(true, |values| values.len() == external_ids.len());
+        if valid_count && coerced_count {
             Ok(EnrichedDocumentsBatchReader {
                 documents,
                 primary_key,
                 external_ids: external_ids.into_cursor()?,
+                coerced_values: coerced_values.map(|values| values.into_cursor()).transpose()?,
+                skipped_offsets,
             })
         } else {
             Err(Error::InvalidEnrichedData)
@@ -43,7 +58,7 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
         self.documents.documents_count()
     }
 
-    pub fn primary_key(&self) -> &str {
+    pub fn primary_key(&self) -> &PrimaryKey {
         &self.primary_key
     }
 
@@ -59,10 +74,25 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
     pub fn into_cursor_and_fields_index(
         self,
     ) -> (EnrichedDocumentsBatchCursor<R>, DocumentsBatchIndex) {
-        let EnrichedDocumentsBatchReader { documents, primary_key, mut external_ids } = self;
+        let EnrichedDocumentsBatchReader {
+            documents,
+            primary_key,
+            mut external_ids,
+            mut coerced_values,
+            skipped_offsets,
+        } = self;
         let (documents, fields_index) = documents.into_cursor_and_fields_index();
         external_ids.reset();
-        (EnrichedDocumentsBatchCursor { documents, primary_key, external_ids }, fields_index)
+        coerced_values.iter_mut().for_each(|values| values.reset());
+        let cursor = EnrichedDocumentsBatchCursor {
+            documents,
+            primary_key,
+            external_ids,
+            coerced_values,
+            skipped_offsets,
+            offset: 0,
+        };
+        (cursor, fields_index)
     }
 }
 
@@ -70,22 +100,31 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
 pub struct EnrichedDocument<'a> {
     pub document: KvReader<'a, FieldId>,
     pub document_id: DocumentId,
+    /// The position of the document in its batch, the skipped documents included.
+    pub offset: u32,
+    /// The coerced values of the fields declared in the schema, to use instead of the ones of the document.
+    pub coerced_values: Option<KvReader<'a, FieldId>>,
 }
 
 pub struct EnrichedDocumentsBatchCursor<R> {
     documents: DocumentsBatchCursor<R>,
-    primary_key: String,
+    primary_key: PrimaryKey,
     external_ids: grenad::ReaderCursor<File>,
+    coerced_values: Option<grenad::ReaderCursor<File>>,
+    skipped_offsets: RoaringBitmap,
+    offset: u32,
 }
 
 impl<R> EnrichedDocumentsBatchCursor<R> {
-    pub fn primary_key(&self) -> &str {
+    pub fn primary_key(&self) -> &PrimaryKey {
         &self.primary_key
     }
     /// Resets the cursor to be able to read from the start again.
     pub fn reset(&mut self) {
         self.documents.reset();
         self.external_ids.reset();
+        self.coerced_values.iter_mut().for_each(|values| values.reset());
+        self.offset = 0;
     }
 }
 
@@ -95,14 +134,29 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchCursor<R> {
     pub fn next_enriched_document(
         &mut self,
     ) -> Result<Option<EnrichedDocument>, DocumentsBatchCursorError> {
+        while self.skipped_offsets.contains(self.offset) {
+            if self.documents.next_document()?.is_none() {
+                return Ok(None);
+            }
+            self.offset += 1;
+        }
+
+        let offset = self.offset;
         let document = self.documents.next_document()?;
         let document_id = match self.external_ids.move_on_next()? {
             Some((_, bytes)) => serde_json::from_slice(bytes).map(Some)?,
             None => None,
         };
+        let coerced_values = match self.coerced_values.as_mut() {
+            Some(values) => values.move_on_next()?.map(|(_, bytes)| KvReader::new(bytes)),
+            None => None,
+        };
+        self.offset += 1;
 
         match document.zip(document_id) {
-            Some((document, document_id)) => Ok(Some(EnrichedDocument { document, document_id })),
+            Some((document, document_id)) => {
+                Ok(Some(EnrichedDocument { document, document_id, offset, coerced_values }))
+            }
             None => Ok(None),
         }
     }
@@ -8,7 +8,7 @@ use std::io;
 use std::str::Utf8Error;
 
 use bimap::BiHashMap;
-pub use builder::DocumentsBatchBuilder;
+pub use builder::{DocumentsBatchBuilder, DEFAULT_CSV_ARRAY_SEPARATOR};
 pub use enriched::{EnrichedDocument, EnrichedDocumentsBatchCursor, EnrichedDocumentsBatchReader};
 use obkv::KvReader;
 pub use reader::{DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchReader};
@@ -90,6 +90,8 @@ impl DocumentsBatchIndex {
 #[derive(Debug)]
 pub enum Error {
     ParseFloat { error: std::num::ParseFloatError, line: usize, value: String },
+    ParseBool { error: std::str::ParseBoolError, line: usize, value: String },
+    ParseJson { error: serde_json::Error, line: usize, value: String },
     InvalidDocumentFormat,
     InvalidEnrichedData,
     InvalidUtf8(Utf8Error),
@@ -136,6 +138,12 @@ impl fmt::Display for Error {
             Error::ParseFloat { error, line, value } => {
                 write!(f, "Error parsing number {:?} at line {}: {}", value, line, error)
             }
+            Error::ParseBool { error, line, value } => {
+                write!(f, "Error parsing boolean {:?} at line {}: {}", value, line, error)
+            }
+            Error::ParseJson { error, line, value } => {
+                write!(f, "Error parsing JSON {:?} at line {}: {}", value, line, error)
+            }
             Error::InvalidDocumentFormat => {
                 f.write_str("Invalid document addition format, missing the documents batch index.")
             }
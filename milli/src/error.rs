@@ -8,7 +8,7 @@ use serde_json::Value;
 use thiserror::Error;
 
 use crate::documents::{self, DocumentsBatchCursorError};
-use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
+use crate::{CriterionError, DocumentId, FieldId, Object, SchemaMismatch, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
     ["_geo", "_geoDistance", "_geoPoint", "_geoRadius"].contains(&keyword)
@@ -94,12 +94,28 @@ pub enum UserError {
     CriterionError(#[from] CriterionError),
     #[error("Maximum number of documents reached.")]
     DocumentLimitReached,
+    #[error("{0}")]
+    DocumentEditionCompilationError(rhai::ParseError),
+    #[error("{0}")]
+    DocumentEditionRuntimeError(Box<rhai::EvalAltResult>),
+    #[error("The edition function cannot modify the primary key of a document.")]
+    DocumentEditionCannotModifyPrimaryKey,
+    #[error("The edition function must keep the `doc` variable an object.")]
+    DocumentEditionDocumentMustBeObject,
     #[error(
         "Document identifier `{}` is invalid. \
 A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("The `_version` field in the document with the id: `{document_id}` is invalid. Was expecting a positive integer but instead got `{version}`.")]
+    InvalidDocumentVersion { document_id: Value, version: Value },
+    #[error("The `_expiresAt` field in the document with the id: `{document_id}` is invalid. Was expecting an RFC 3339 date or a Unix timestamp in seconds but instead got `{expires_at}`.")]
+    InvalidDocumentExpiresAt { document_id: Value, expires_at: Value },
+    #[error("The document with the id: `{document_id}` doesn't match the schema of the index, {mismatch}.")]
+    InvalidDocumentSchema { document_id: Value, mismatch: SchemaMismatch },
+    #[error("The field `{field}` can't be declared in the schema, {reason}.")]
+    InvalidSchemaField { field: String, reason: &'static str },
     #[error("Invalid facet distribution, the fields `{}` are not set as filterable.",
         .invalid_facets_name.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
      )]
@@ -108,6 +124,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidGeoField(#[from] GeoError),
     #[error("{0}")]
     InvalidFilter(String),
+    #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
+    InvalidFilterExpression(&'static [&'static str], Value),
     #[error("Attribute `{}` is not sortable. {}",
         .field,
         match .valid_fields.is_empty() {
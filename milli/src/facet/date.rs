@@ -0,0 +1,42 @@
+use time::format_description::well_known::{Iso8601, Rfc3339};
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+/// Parses an RFC 3339 or ISO 8601 date into a Unix timestamp expressed in seconds.
+///
+/// Dates and date-times without an offset are considered to be in UTC. The sub-second
+/// part of the date is kept as the fractional part of the returned number, this way
+/// the timestamps can be stored and ordered in the number facet databases.
+pub fn date_to_timestamp(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let datetime = OffsetDateTime::parse(s, &Rfc3339)
+        .or_else(|_| OffsetDateTime::parse(s, &Iso8601::DEFAULT))
+        .or_else(|_| PrimitiveDateTime::parse(s, &Iso8601::DEFAULT).map(|dt| dt.assume_utc()))
+        .or_else(|_| {
+            Date::parse(s, format_description!("[year]-[month]-[day]"))
+                .map(|date| date.with_time(Time::MIDNIGHT).assume_utc())
+        })
+        .ok()?;
+
+    Some(datetime.unix_timestamp_nanos() as f64 / 1_000_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(date_to_timestamp("1970-01-01"), Some(0.0));
+        assert_eq!(date_to_timestamp("2023-01-01"), Some(1672531200.0));
+        assert_eq!(date_to_timestamp("2023-01-01T00:00:00Z"), Some(1672531200.0));
+        assert_eq!(date_to_timestamp("2023-01-01T01:00:00+01:00"), Some(1672531200.0));
+        assert_eq!(date_to_timestamp("2023-01-01T00:00:00.5Z"), Some(1672531200.5));
+        assert_eq!(date_to_timestamp(" 2023-01-01T00:00:00 "), Some(1672531200.0));
+        assert_eq!(date_to_timestamp("1969-12-31T23:59:59Z"), Some(-1.0));
+
+        assert_eq!(date_to_timestamp("2023-13-01"), None);
+        assert_eq!(date_to_timestamp("yesterday"), None);
+        assert_eq!(date_to_timestamp("1672531200"), None);
+    }
+}
@@ -1,14 +1,16 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::mem::size_of;
 use std::path::Path;
 
+use charabia::TokenizerBuilder;
 use heed::flags::Flags;
 use heed::types::*;
 use heed::{CompactionOption, Database, PolyDatabase, RoTxn, RwTxn};
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::error::{InternalError, UserError};
@@ -21,33 +23,73 @@ use crate::heed_codec::facet::{
 use crate::heed_codec::StrRefCodec;
 use crate::{
     default_criteria, BEU32StrCodec, BoRoaringBitmapCodec, CboRoaringBitmapCodec, Criterion,
-    DocumentId, ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId,
-    FieldIdWordCountCodec, GeoPoint, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec,
-    Search, StrBEU32Codec, U8StrStrCodec, BEU16, BEU32,
+    CurationRule, DocumentId, ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId,
+    FieldIdWordCountCodec, GeoPoint, Locale, LocalizedAttributesRule, ObkvCodec, PrimaryKey,
+    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Schema, Search, StemmingLanguage,
+    StrBEU32Codec, U8StrStrCodec, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// The minimum word lengths for typos of an attribute, the index-wide
+/// lengths are used for the ones that are not defined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinWordLenForTypo {
+    pub one_typo: Option<u8>,
+    pub two_typos: Option<u8>,
+}
+
+/// The separators and the dictionary of an index, see [`Index::tokenizer_words`].
+pub struct TokenizerWords<'t> {
+    separators: Option<Vec<&'t str>>,
+    dictionary: Option<Vec<&'t str>>,
+}
+
+impl<'t> TokenizerWords<'t> {
+    /// Returns a tokenizer builder configured with the separators and the dictionary,
+    /// it borrows them.
+    pub fn tokenizer_builder<A: AsRef<[u8]>>(&self) -> TokenizerBuilder<'_, A> {
+        let mut builder = TokenizerBuilder::new();
+        if let Some(ref separators) = self.separators {
+            builder.separators(separators);
+        }
+        if let Some(ref dictionary) = self.dictionary {
+            builder.words_dict(dictionary);
+        }
+        builder
+    }
+}
+
 pub mod main_key {
+    pub const COMPOSITE_PRIMARY_KEY_KEY: &str = "composite-primary-key";
     pub const CRITERIA_KEY: &str = "criteria";
+    pub const CURATION_RULES_KEY: &str = "curation-rules";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
+    pub const DICTIONARY_KEY: &str = "dictionary";
     pub const DISTINCT_FIELD_KEY: &str = "distinct-field-key";
     pub const DOCUMENTS_IDS_KEY: &str = "documents-ids";
     pub const SOFT_DELETED_DOCUMENTS_IDS_KEY: &str = "soft-deleted-documents-ids";
     pub const HIDDEN_FACETED_FIELDS_KEY: &str = "hidden-faceted-fields";
     pub const FILTERABLE_FIELDS_KEY: &str = "filterable-fields";
     pub const SORTABLE_FIELDS_KEY: &str = "sortable-fields";
+    pub const DATE_FIELDS_KEY: &str = "date-fields";
     pub const FIELD_DISTRIBUTION_KEY: &str = "fields-distribution";
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
     pub const HARD_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "hard-external-documents-ids";
+    pub const LOCALIZED_ATTRIBUTES_RULES_KEY: &str = "localized-attributes-rules";
+    pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const NUMBER_FACETED_DOCUMENTS_IDS_PREFIX: &str = "number-faceted-documents-ids";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
+    pub const SCHEMA_KEY: &str = "schema";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
+    pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const SOFT_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "soft-external-documents-ids";
+    pub const STEMMING_LANGUAGES_KEY: &str = "stemming-languages";
     pub const STOP_WORDS_KEY: &str = "stop-words";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
     pub const SYNONYMS_KEY: &str = "synonyms";
@@ -56,6 +98,8 @@ pub mod main_key {
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
+    pub const ATTRIBUTES_MIN_WORD_LEN_FOR_TYPO: &str = "attributes-min-word-len-for-typo";
     pub const ONE_TYPO_WORD_LEN: &str = "one-typo-word-len";
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
@@ -79,10 +123,14 @@ pub mod db_name {
     pub const FIELD_ID_WORD_COUNT_DOCIDS: &str = "field-id-word-count-docids";
     pub const FACET_ID_F64_DOCIDS: &str = "facet-id-f64-docids";
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
+    pub const FACET_ID_IS_NULL_DOCIDS: &str = "facet-id-is-null-docids";
+    pub const FACET_ID_IS_EMPTY_DOCIDS: &str = "facet-id-is-empty-docids";
     pub const FACET_ID_STRING_DOCIDS: &str = "facet-id-string-docids";
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const DOCUMENTS: &str = "documents";
+    pub const DOCUMENTS_VERSIONS: &str = "documents-versions";
+    pub const DOCUMENTS_EXPIRATION: &str = "documents-expiration";
 }
 
 #[derive(Clone)]
@@ -124,6 +172,10 @@ pub struct Index {
 
     /// Maps the facet field id and the docids for which this field exists
     pub facet_id_exists_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and the docids for which this field is set as null
+    pub facet_id_is_null_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and the docids for which this field is considered empty
+    pub facet_id_is_empty_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
 
     /// Maps the facet field id and ranges of numbers with the docids that corresponds to them.
     pub facet_id_f64_docids: Database<FacetGroupKeyCodec<OrderedF64Codec>, FacetGroupValueCodec>,
@@ -137,6 +189,10 @@ pub struct Index {
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<OwnedType<BEU32>, ObkvCodec>,
+    /// Maps the external document id to the current version of the document.
+    pub(crate) documents_versions: Database<Str, OwnedType<BEU64>>,
+    /// Maps the expiration date, in seconds since the Unix epoch, to the docids expiring at that date.
+    pub(crate) documents_expiration: Database<OwnedType<BEU64>, RoaringBitmapCodec>,
 }
 
 impl Index {
@@ -148,7 +204,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(18);
+        options.max_dbs(22);
         unsafe { options.flag(Flags::MdbAlwaysFreePages) };
 
         let env = options.open(path)?;
@@ -169,11 +225,15 @@ impl Index {
         let facet_id_f64_docids = env.create_database(Some(FACET_ID_F64_DOCIDS))?;
         let facet_id_string_docids = env.create_database(Some(FACET_ID_STRING_DOCIDS))?;
         let facet_id_exists_docids = env.create_database(Some(FACET_ID_EXISTS_DOCIDS))?;
+        let facet_id_is_null_docids = env.create_database(Some(FACET_ID_IS_NULL_DOCIDS))?;
+        let facet_id_is_empty_docids = env.create_database(Some(FACET_ID_IS_EMPTY_DOCIDS))?;
 
         let field_id_docid_facet_f64s = env.create_database(Some(FIELD_ID_DOCID_FACET_F64S))?;
         let field_id_docid_facet_strings =
             env.create_database(Some(FIELD_ID_DOCID_FACET_STRINGS))?;
         let documents = env.create_database(Some(DOCUMENTS))?;
+        let documents_versions = env.create_database(Some(DOCUMENTS_VERSIONS))?;
+        let documents_expiration = env.create_database(Some(DOCUMENTS_EXPIRATION))?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
 
@@ -194,9 +254,13 @@ impl Index {
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_exists_docids,
+            facet_id_is_null_docids,
+            facet_id_is_empty_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             documents,
+            documents_versions,
+            documents_expiration,
         })
     }
 
@@ -329,20 +393,49 @@ impl Index {
 
     /* primary key */
 
-    /// Writes the documents primary key, this is the field name that is used to store the id.
-    pub(crate) fn put_primary_key(&self, wtxn: &mut RwTxn, primary_key: &str) -> heed::Result<()> {
+    /// Writes the documents primary key, this is the field name that is used to store the id
+    /// or the list of the fields whose values make up the id.
+    pub(crate) fn put_primary_key(
+        &self,
+        wtxn: &mut RwTxn,
+        primary_key: &PrimaryKey,
+    ) -> heed::Result<()> {
         self.set_updated_at(wtxn, &OffsetDateTime::now_utc())?;
-        self.main.put::<_, Str, Str>(wtxn, main_key::PRIMARY_KEY_KEY, primary_key)
+        // A composite primary key is stored under its own key so that the existing single
+        // field primary keys keep their meaning whatever their name.
+        match primary_key {
+            PrimaryKey::Field(name) => {
+                self.main.delete::<_, Str>(wtxn, main_key::COMPOSITE_PRIMARY_KEY_KEY)?;
+                self.main.put::<_, Str, Str>(wtxn, main_key::PRIMARY_KEY_KEY, name)
+            }
+            PrimaryKey::Composite(names) => {
+                self.main.delete::<_, Str>(wtxn, main_key::PRIMARY_KEY_KEY)?;
+                self.main.put::<_, Str, SerdeJson<Vec<String>>>(
+                    wtxn,
+                    main_key::COMPOSITE_PRIMARY_KEY_KEY,
+                    names,
+                )
+            }
+        }
     }
 
     /// Deletes the primary key of the documents, this can be done to reset indexes settings.
     pub(crate) fn delete_primary_key(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
-        self.main.delete::<_, Str>(wtxn, main_key::PRIMARY_KEY_KEY)
+        let deleted = self.main.delete::<_, Str>(wtxn, main_key::PRIMARY_KEY_KEY)?;
+        let composite_deleted =
+            self.main.delete::<_, Str>(wtxn, main_key::COMPOSITE_PRIMARY_KEY_KEY)?;
+        Ok(deleted || composite_deleted)
     }
 
     /// Returns the documents primary key, `None` if it hasn't been defined.
-    pub fn primary_key<'t>(&self, rtxn: &'t RoTxn) -> heed::Result<Option<&'t str>> {
-        self.main.get::<_, Str, Str>(rtxn, main_key::PRIMARY_KEY_KEY)
+    pub fn primary_key(&self, rtxn: &RoTxn) -> heed::Result<Option<PrimaryKey>> {
+        match self.main.get::<_, Str, Str>(rtxn, main_key::PRIMARY_KEY_KEY)? {
+            Some(name) => Ok(Some(PrimaryKey::Field(name.to_string()))),
+            None => Ok(self
+                .main
+                .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::COMPOSITE_PRIMARY_KEY_KEY)?
+                .map(PrimaryKey::Composite)),
+        }
     }
 
     /* external documents ids */
@@ -712,6 +805,37 @@ impl Index {
         Ok(fields.into_iter().filter_map(|name| fields_ids_map.id(&name)).collect())
     }
 
+    /* date fields */
+
+    /// Writes the date fields names in the database.
+    pub(crate) fn put_date_fields(
+        &self,
+        wtxn: &mut RwTxn,
+        fields: &HashSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::DATE_FIELDS_KEY, fields)
+    }
+
+    /// Deletes the date fields names in the database.
+    pub(crate) fn delete_date_fields(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::DATE_FIELDS_KEY)
+    }
+
+    /// Returns the date fields names, the fields whose string values are parsed as dates.
+    pub fn date_fields(&self, rtxn: &RoTxn) -> heed::Result<HashSet<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(rtxn, main_key::DATE_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Identical to `date_fields`, but returns ids instead.
+    pub fn date_fields_ids(&self, rtxn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let fields = self.date_fields(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(fields.into_iter().filter_map(|name| fields_ids_map.id(&name)).collect())
+    }
+
     /* faceted fields */
 
     /// Writes the faceted fields in the database.
@@ -838,6 +962,30 @@ impl Index {
         }
     }
 
+    /// Retrieve all the documents for which this field id is set as null
+    pub fn null_faceted_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        field_id: FieldId,
+    ) -> heed::Result<RoaringBitmap> {
+        match self.facet_id_is_null_docids.get(rtxn, &BEU16::new(field_id))? {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
+    /// Retrieve all the documents for which this field id is considered empty
+    pub fn empty_faceted_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        field_id: FieldId,
+    ) -> heed::Result<RoaringBitmap> {
+        match self.facet_id_is_empty_docids.get(rtxn, &BEU16::new(field_id))? {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
     /* distinct field */
 
     pub(crate) fn put_distinct_field(
@@ -917,6 +1065,135 @@ impl Index {
         }
     }
 
+    /* separator tokens */
+
+    pub(crate) fn put_separator_tokens(
+        &self,
+        wtxn: &mut RwTxn,
+        tokens: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SEPARATOR_TOKENS_KEY, tokens)
+    }
+
+    pub(crate) fn delete_separator_tokens(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SEPARATOR_TOKENS_KEY)
+    }
+
+    /// Returns the tokens that must be considered as separators in addition to the default ones.
+    pub fn separator_tokens<'t>(&self, rtxn: &'t RoTxn) -> heed::Result<Option<BTreeSet<&'t str>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(rtxn, main_key::SEPARATOR_TOKENS_KEY)
+    }
+
+    /* non separator tokens */
+
+    pub(crate) fn put_non_separator_tokens(
+        &self,
+        wtxn: &mut RwTxn,
+        tokens: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::NON_SEPARATOR_TOKENS_KEY, tokens)
+    }
+
+    pub(crate) fn delete_non_separator_tokens(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::NON_SEPARATOR_TOKENS_KEY)
+    }
+
+    /// Returns the default separators that must not be considered as separators anymore.
+    pub fn non_separator_tokens<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+    ) -> heed::Result<Option<BTreeSet<&'t str>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(rtxn, main_key::NON_SEPARATOR_TOKENS_KEY)
+    }
+
+    /// Returns the separators the tokenizer must use, `None` if the default ones must be used.
+    ///
+    /// They are the default separators, plus the separator tokens, minus the non separator tokens.
+    pub fn allowed_separators<'t>(&self, rtxn: &'t RoTxn) -> heed::Result<Option<Vec<&'t str>>> {
+        let separator_tokens = self.separator_tokens(rtxn)?;
+        let non_separator_tokens = self.non_separator_tokens(rtxn)?;
+        if separator_tokens.is_none() && non_separator_tokens.is_none() {
+            return Ok(None);
+        }
+
+        let mut separators: BTreeSet<_> =
+            charabia::separators::DEFAULT_SEPARATORS.iter().copied().collect();
+        separators.extend(separator_tokens.unwrap_or_default());
+        for token in non_separator_tokens.unwrap_or_default() {
+            separators.remove(token);
+        }
+
+        Ok(Some(separators.into_iter().collect()))
+    }
+
+    /* dictionary */
+
+    pub(crate) fn put_dictionary(
+        &self,
+        wtxn: &mut RwTxn,
+        words: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::DICTIONARY_KEY, words)
+    }
+
+    pub(crate) fn delete_dictionary(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::DICTIONARY_KEY)
+    }
+
+    /// Returns the words that the tokenizer must never split.
+    pub fn dictionary<'t>(&self, rtxn: &'t RoTxn) -> heed::Result<Option<BTreeSet<&'t str>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(rtxn, main_key::DICTIONARY_KEY)
+    }
+
+    /// Returns the separators and the dictionary the tokenizers of the index must use,
+    /// the documents and the queries are tokenized the same way with them.
+    pub fn tokenizer_words<'t>(&self, rtxn: &'t RoTxn) -> heed::Result<TokenizerWords<'t>> {
+        let separators = self.allowed_separators(rtxn)?;
+        let dictionary = self.dictionary(rtxn)?.map(|words| words.into_iter().collect());
+        Ok(TokenizerWords { separators, dictionary })
+    }
+
+    /* stemming languages */
+
+    pub(crate) fn put_stemming_languages(
+        &self,
+        wtxn: &mut RwTxn,
+        languages: &[StemmingLanguage],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[StemmingLanguage]>>(
+            wtxn,
+            main_key::STEMMING_LANGUAGES_KEY,
+            &languages,
+        )
+    }
+
+    pub(crate) fn delete_stemming_languages(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::STEMMING_LANGUAGES_KEY)
+    }
+
+    /// Returns the languages in which the words are stemmed, no stemming is done when empty.
+    pub fn stemming_languages(&self, rtxn: &RoTxn) -> heed::Result<Vec<StemmingLanguage>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(rtxn, main_key::STEMMING_LANGUAGES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /* schema */
+
+    pub(crate) fn put_schema(&self, wtxn: &mut RwTxn, schema: &Schema) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&Schema>>(wtxn, main_key::SCHEMA_KEY, &schema)
+    }
+
+    pub(crate) fn delete_schema(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SCHEMA_KEY)
+    }
+
+    /// Returns the declared fields the documents are validated against, none when empty.
+    pub fn schema(&self, rtxn: &RoTxn) -> heed::Result<Schema> {
+        Ok(self.main.get::<_, Str, SerdeJson<_>>(rtxn, main_key::SCHEMA_KEY)?.unwrap_or_default())
+    }
+
     /* synonyms */
 
     pub(crate) fn put_synonyms(
@@ -1020,6 +1297,55 @@ impl Index {
             }))
     }
 
+    /// Returns the current version of the document with the given external id.
+    ///
+    /// Every addition, update or edition of a document increments its version, a document
+    /// that never existed or that was never modified since versions exist has the version `0`.
+    /// The version of a deleted document is kept, a document added back with the same id
+    /// continues from it.
+    pub fn document_version(&self, rtxn: &RoTxn, external_id: &str) -> Result<u64> {
+        Ok(self.documents_versions.get(rtxn, external_id)?.map_or(0, |version| version.get()))
+    }
+
+    /// Returns the external ids and the versions of all the documents, including the
+    /// versions kept for the deleted documents.
+    pub fn documents_versions<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+    ) -> Result<impl Iterator<Item = heed::Result<(&'t str, u64)>>> {
+        Ok(self
+            .documents_versions
+            .iter(rtxn)?
+            .map(|entry| entry.map(|(external_id, version)| (external_id, version.get()))))
+    }
+
+    /// Sets the version of the document with the given external id, it is used to restore
+    /// the versions of the documents of a dump.
+    pub fn put_document_version(
+        &self,
+        wtxn: &mut RwTxn,
+        external_id: &str,
+        version: u64,
+    ) -> heed::Result<()> {
+        self.documents_versions.put(wtxn, external_id, &BEU64::new(version))
+    }
+
+    /// Returns the ids of the documents whose `_expiresAt` date is before or equal to `now`.
+    pub fn expired_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<RoaringBitmap> {
+        let now = BEU64::new(now.unix_timestamp().max(0) as u64);
+        let mut expired = RoaringBitmap::new();
+        for result in self.documents_expiration.range(rtxn, &(..=now))? {
+            let (_date, docids) = result?;
+            expired |= docids;
+        }
+        expired -= self.soft_deleted_documents_ids(rtxn)?;
+        Ok(expired)
+    }
+
     pub fn facets_distribution<'a>(&'a self, rtxn: &'a RoTxn) -> FacetDistribution<'a> {
         FacetDistribution::new(rtxn, self)
     }
@@ -1113,6 +1439,84 @@ impl Index {
         Ok(())
     }
 
+    /// Returns the minimum word lengths for typos defined on specific attributes.
+    pub fn attributes_min_word_len_for_typo(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<BTreeMap<String, MinWordLenForTypo>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(txn, main_key::ATTRIBUTES_MIN_WORD_LEN_FOR_TYPO)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the minimum word lengths for one and two typos of the fields
+    /// having their own, completed with the index-wide ones.
+    pub fn fields_ids_min_word_len_for_typo(
+        &self,
+        txn: &RoTxn,
+    ) -> Result<HashMap<FieldId, (u8, u8)>> {
+        let attributes = self.attributes_min_word_len_for_typo(txn)?;
+        if attributes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let one = self.min_word_len_one_typo(txn)?;
+        let two = self.min_word_len_two_typos(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attributes
+            .iter()
+            .filter_map(|(attr, len)| {
+                let field_id = fid_map.id(attr)?;
+                Some((field_id, (len.one_typo.unwrap_or(one), len.two_typos.unwrap_or(two))))
+            })
+            .collect())
+    }
+
+    pub(crate) fn put_attributes_min_word_len_for_typo(
+        &self,
+        txn: &mut RwTxn,
+        attributes: &BTreeMap<String, MinWordLenForTypo>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(
+            txn,
+            main_key::ATTRIBUTES_MIN_WORD_LEN_FOR_TYPO,
+            attributes,
+        )
+    }
+
+    pub(crate) fn delete_attributes_min_word_len_for_typo(
+        &self,
+        txn: &mut RwTxn,
+    ) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::ATTRIBUTES_MIN_WORD_LEN_FOR_TYPO)
+    }
+
+    /// Returns `true` if the words made of digits only must never be matched with typos.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // The absence of a value is false, because by default, typos are allowed on numbers.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_disable_typos_on_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)
+    }
+
     /// List the words on which typo are not allowed
     pub fn exact_words<'t>(&self, txn: &'t RoTxn) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
         match self.main.get::<_, Str, ByteSlice>(txn, main_key::EXACT_WORDS)? {
@@ -1187,6 +1591,69 @@ impl Index {
     pub(crate) fn delete_pagination_max_total_hits(&self, txn: &mut RwTxn) -> heed::Result<bool> {
         self.main.delete::<_, Str>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
+
+    /* curation rules */
+
+    /// Returns the curation rules, the rules pinning, hiding or boosting documents
+    /// depending on the search query.
+    pub fn curation_rules(&self, txn: &RoTxn) -> heed::Result<Vec<CurationRule>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(txn, main_key::CURATION_RULES_KEY)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_curation_rules(
+        &self,
+        txn: &mut RwTxn,
+        rules: &[CurationRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[CurationRule]>>(
+            txn,
+            main_key::CURATION_RULES_KEY,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_curation_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::CURATION_RULES_KEY)
+    }
+
+    /* localized attributes rules */
+
+    /// Returns the rules forcing the languages used to tokenize the attributes.
+    pub fn localized_attributes_rules(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<Vec<LocalizedAttributesRule>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<_>>(txn, main_key::LOCALIZED_ATTRIBUTES_RULES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the locales of the fields matched by the localized attributes rules.
+    pub fn localized_fields_ids(&self, txn: &RoTxn) -> Result<HashMap<FieldId, Vec<Locale>>> {
+        let rules = self.localized_attributes_rules(txn)?;
+        let fields_ids_map = self.fields_ids_map(txn)?;
+        Ok(crate::localized_attributes_rules::localized_fields_ids(&rules, &fields_ids_map))
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn,
+        rules: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<&[LocalizedAttributesRule]>>(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES_KEY,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::LOCALIZED_ATTRIBUTES_RULES_KEY)
+    }
 }
 
 #[cfg(test)]
@@ -4,14 +4,19 @@ pub mod documents;
 
 mod asc_desc;
 mod criterion;
+mod curation;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
 pub mod index;
+mod localized_attributes_rules;
+mod primary_key;
 pub mod proximity;
+mod schema;
 mod search;
+mod stemming;
 pub mod update;
 
 #[cfg(test)]
@@ -30,6 +35,7 @@ pub use {charabia as tokenizer, heed};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::curation::{CurationRule, PinnedDocument, QueryMatchType};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -40,12 +46,18 @@ pub use self::heed_codec::{
     CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec, RoaringBitmapCodec,
     RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec, UncheckedU8StrStrCodec,
 };
-pub use self::index::Index;
+pub use self::index::{Index, TokenizerWords};
+pub use self::localized_attributes_rules::{
+    locales_allow_list, Locale, LocalizedAttributesRule, UnsupportedLocale,
+};
+pub use self::primary_key::{composite_document_id, PrimaryKey};
+pub use self::schema::{FieldSchema, FieldType, Schema, SchemaMismatch};
 pub use self::search::{
     CriterionImplementationStrategy, FacetDistribution, Filter, FormatOptions, MatchBounds,
     MatcherBuilder, MatchingWord, MatchingWords, Search, SearchResult, TermsMatchingStrategy,
     DEFAULT_VALUES_PER_FACET,
 };
+pub use self::stemming::StemmingLanguage;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
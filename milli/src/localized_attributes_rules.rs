@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use charabia::{Language, Script};
+use deserr::DeserializeFromValue;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{FieldId, FieldsIdsMap};
+
+/// Forces the language of the attributes matching one of its patterns.
+///
+/// The tokenizer will only consider the given locales when segmenting and normalizing the
+/// values of these attributes, instead of relying on the language detection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRule {
+    /// The attributes the rule applies to, a `*` can be used at the start
+    /// and/or at the end of a pattern to match any sequence of characters.
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl LocalizedAttributesRule {
+    /// Returns `true` if the given attribute, or one of its parents, matches one of the patterns.
+    pub fn matches_attribute(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| {
+            match_pattern(pattern, attribute)
+                || attribute
+                    .match_indices('.')
+                    .any(|(index, _)| match_pattern(pattern, &attribute[..index]))
+        })
+    }
+}
+
+fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        _ if pattern == "*" => true,
+        (Some(_), Some(_)) => attribute.contains(&pattern[1..pattern.len() - 1]),
+        (Some(suffix), None) => attribute.ends_with(suffix),
+        (None, Some(prefix)) => attribute.starts_with(prefix),
+        (None, None) => attribute == pattern,
+    }
+}
+
+/// Returns the locales of each field of the map, the first rule matching a field wins.
+pub fn localized_fields_ids(
+    rules: &[LocalizedAttributesRule],
+    fields_ids_map: &FieldsIdsMap,
+) -> HashMap<FieldId, Vec<Locale>> {
+    fields_ids_map
+        .iter()
+        .filter_map(|(id, name)| {
+            let rule = rules.iter().find(|rule| rule.matches_attribute(name))?;
+            Some((id, rule.locales.clone()))
+        })
+        .filter(|(_, locales)| !locales.is_empty())
+        .collect()
+}
+
+/// Builds the list of the languages the tokenizer is allowed to detect, grouped by script.
+pub fn locales_allow_list(locales: &[Locale]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for locale in locales {
+        let languages = allow_list.entry(locale.script()).or_default();
+        if !languages.contains(&locale.language()) {
+            languages.push(locale.language());
+        }
+    }
+    allow_list
+}
+
+#[derive(Error, Debug)]
+#[error("Unsupported locale `{0}`, expected an ISO 639-3 code like `eng`, `jpn` or `cmn`")]
+pub struct UnsupportedLocale(pub String);
+
+macro_rules! make_locales {
+    ($($locale:ident => $script:ident),+ $(,)?) => {
+        /// A language identified by its ISO 639-3 code.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, DeserializeFromValue)]
+        #[serde(rename_all = "camelCase")]
+        #[deserr(rename_all = camelCase)]
+        pub enum Locale {
+            $($locale),+
+        }
+
+        impl Locale {
+            /// The language the tokenizer must use for this locale.
+            pub fn language(self) -> Language {
+                match self {
+                    $(Locale::$locale => Language::$locale),+
+                }
+            }
+
+            /// The script this locale is written in.
+            pub fn script(self) -> Script {
+                match self {
+                    $(Locale::$locale => Script::$script),+
+                }
+            }
+        }
+
+        impl FromStr for Locale {
+            type Err = UnsupportedLocale;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case(stringify!($locale)) {
+                        return Ok(Locale::$locale);
+                    }
+                )+
+                Err(UnsupportedLocale(s.to_string()))
+            }
+        }
+    };
+}
+
+make_locales! {
+    Afr => Latin,
+    Aka => Latin,
+    Amh => Ethiopic,
+    Ara => Arabic,
+    Aze => Latin,
+    Bel => Cyrillic,
+    Ben => Bengali,
+    Bul => Cyrillic,
+    Cat => Latin,
+    Ces => Latin,
+    Cmn => Cj,
+    Dan => Latin,
+    Deu => Latin,
+    Ell => Greek,
+    Eng => Latin,
+    Epo => Latin,
+    Est => Latin,
+    Fin => Latin,
+    Fra => Latin,
+    Guj => Gujarati,
+    Heb => Hebrew,
+    Hin => Devanagari,
+    Hrv => Latin,
+    Hun => Latin,
+    Hye => Armenian,
+    Ind => Latin,
+    Ita => Latin,
+    Jav => Latin,
+    Jpn => Cj,
+    Kan => Kannada,
+    Kat => Georgian,
+    Khm => Khmer,
+    Kor => Hangul,
+    Lat => Latin,
+    Lav => Latin,
+    Lit => Latin,
+    Mal => Malayalam,
+    Mar => Devanagari,
+    Mkd => Cyrillic,
+    Mya => Myanmar,
+    Nep => Devanagari,
+    Nld => Latin,
+    Nob => Latin,
+    Ori => Oriya,
+    Pan => Gurmukhi,
+    Pes => Arabic,
+    Pol => Latin,
+    Por => Latin,
+    Ron => Latin,
+    Rus => Cyrillic,
+    Sin => Sinhala,
+    Slk => Latin,
+    Slv => Latin,
+    Sna => Latin,
+    Spa => Latin,
+    Srp => Cyrillic,
+    Swe => Latin,
+    Tam => Tamil,
+    Tel => Telugu,
+    Tgl => Latin,
+    Tha => Thai,
+    Tuk => Latin,
+    Tur => Latin,
+    Ukr => Cyrillic,
+    Urd => Arabic,
+    Uzb => Latin,
+    Vie => Latin,
+    Yid => Hebrew,
+    Zul => Latin,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_attribute() {
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["title".into(), "*_ja".into(), "desc*".into()],
+            locales: vec![Locale::Jpn],
+        };
+        assert!(rule.matches_attribute("title"));
+        assert!(rule.matches_attribute("title.original"));
+        assert!(rule.matches_attribute("name_ja"));
+        assert!(rule.matches_attribute("description"));
+        assert!(!rule.matches_attribute("subtitle"));
+        assert!(!rule.matches_attribute("name_jap"));
+
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["*".into()],
+            locales: vec![Locale::Cmn],
+        };
+        assert!(rule.matches_attribute("anything"));
+    }
+
+    #[test]
+    fn allow_list() {
+        let allow_list = locales_allow_list(&[Locale::Jpn, Locale::Eng, Locale::Jpn, Locale::Fra]);
+        assert_eq!(allow_list[&Script::Cj], vec![Language::Jpn]);
+        assert_eq!(allow_list[&Script::Latin], vec![Language::Eng, Language::Fra]);
+    }
+
+    #[test]
+    fn parse_locale() {
+        assert_eq!("jpn".parse::<Locale>().unwrap(), Locale::Jpn);
+        assert_eq!("CMN".parse::<Locale>().unwrap(), Locale::Cmn);
+        assert!("ja".parse::<Locale>().is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use deserr::{DeserializeError, DeserializeFromValue, ErrorKind, ValueKind};
+use serde::{Deserialize, Serialize};
+
+/// The symbol used to join the values of the fields of a composite primary key
+/// into a single document id, it can't appear in the values themselves.
+pub const COMPOSITE_DOCUMENT_ID_SEPARATOR: char = ':';
+
+/// The primary key of an index, either a single, possibly nested, field or an ordered list
+/// of fields whose values are joined to make the id of the documents.
+///
+/// It is represented by a string for a single field and by an array of strings for
+/// a composite primary key.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrimaryKey {
+    Field(String),
+    Composite(Vec<String>),
+}
+
+impl PrimaryKey {
+    /// Returns a composite primary key made of the given fields, or `None` if they are not
+    /// at least two distinct and non-empty fields.
+    pub fn composite(names: Vec<String>) -> Option<Self> {
+        let distinct: HashSet<_> = names.iter().collect();
+        if names.len() < 2 || distinct.len() != names.len() || names.iter().any(String::is_empty) {
+            None
+        } else {
+            Some(PrimaryKey::Composite(names))
+        }
+    }
+
+    /// Returns the names of the fields that make up the primary key, in order.
+    pub fn fields(&self) -> &[String] {
+        match self {
+            PrimaryKey::Field(name) => std::slice::from_ref(name),
+            PrimaryKey::Composite(names) => names,
+        }
+    }
+
+    pub fn is_composite(&self) -> bool {
+        matches!(self, PrimaryKey::Composite(_))
+    }
+}
+
+/// Joins the values of the fields of a composite primary key into the id of a document.
+pub fn composite_document_id(components: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut document_id = String::new();
+    for component in components {
+        if !document_id.is_empty() {
+            document_id.push(COMPOSITE_DOCUMENT_ID_SEPARATOR);
+        }
+        document_id.push_str(component.as_ref());
+    }
+    document_id
+}
+
+impl From<String> for PrimaryKey {
+    fn from(name: String) -> Self {
+        PrimaryKey::Field(name)
+    }
+}
+
+impl From<&str> for PrimaryKey {
+    fn from(name: &str) -> Self {
+        PrimaryKey::Field(name.to_string())
+    }
+}
+
+// Formats the primary key the same way it is sent and received, i.e. as a string or an array.
+impl fmt::Debug for PrimaryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimaryKey::Field(name) => fmt::Debug::fmt(name, f),
+            PrimaryKey::Composite(names) => fmt::Debug::fmt(names, f),
+        }
+    }
+}
+
+impl fmt::Display for PrimaryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimaryKey::Field(name) => f.write_str(name),
+            PrimaryKey::Composite(names) => write!(f, "{:?}", names),
+        }
+    }
+}
+
+impl<E: DeserializeError> DeserializeFromValue<E> for PrimaryKey {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> std::result::Result<Self, E> {
+        match value {
+            deserr::Value::String(name) => Ok(PrimaryKey::Field(name)),
+            deserr::Value::Sequence(_) => {
+                let names = Vec::<String>::deserialize_from_value(value, location)?;
+                match PrimaryKey::composite(names.clone()) {
+                    Some(primary_key) => Ok(primary_key),
+                    None => {
+                        let msg = format!(
+                            "A composite primary key must be made of at least two distinct and non-empty fields but found `{:?}`.",
+                            names
+                        );
+                        match E::error::<V>(None, ErrorKind::Unexpected { msg }, location) {
+                            Ok(error) | Err(error) => Err(error),
+                        }
+                    }
+                }
+            }
+            _ => {
+                let kind = ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::String, ValueKind::Sequence],
+                };
+                match E::error::<V>(None, kind, location) {
+                    Ok(error) | Err(error) => Err(error),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use deserr::DeserializeFromValue;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use thiserror::Error;
+
+use crate::Object;
+
+/// The declared fields of the documents of an index, indexed by their name.
+///
+/// A nested field is declared with its dotted name, e.g. `product.price`.
+pub type Schema = BTreeMap<String, FieldSchema>;
+
+/// The type of the values a field of the schema must have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FieldType {
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::String => f.write_str("string"),
+            FieldType::Number => f.write_str("number"),
+            FieldType::Boolean => f.write_str("boolean"),
+            FieldType::Array => f.write_str("array"),
+            FieldType::Object => f.write_str("object"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct FieldSchema {
+    #[serde(rename = "type")]
+    #[deserr(rename = "type")]
+    pub field_type: FieldType,
+    /// Whether the documents missing this field, or where it is `null`, must be rejected.
+    #[serde(default)]
+    #[deserr(default)]
+    pub required: bool,
+}
+
+/// The reason why a document doesn't match the schema of its index.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SchemaMismatch {
+    #[error("the required field `{pointer}` is missing")]
+    MissingField { pointer: String },
+    #[error("the field `{pointer}` must be of type {expected} but instead got `{found}`")]
+    InvalidType { pointer: String, expected: FieldType, found: Value },
+}
+
+/// Returns `true` if the given top-level field, or one of its nested fields, is declared in the schema.
+pub fn schema_covers_field(schema: &Schema, field: &str) -> bool {
+    schema.keys().any(|name| {
+        name == field || name.strip_prefix(field).map_or(false, |rest| rest.starts_with('.'))
+    })
+}
+
+/// Checks the type of the declared fields present in the document, coercing the values
+/// that can be converted without loss, e.g. a string into a number when it can be parsed.
+pub fn coerce_document_fields(
+    schema: &Schema,
+    document: &mut Object,
+) -> Result<(), SchemaMismatch> {
+    for (name, field) in schema {
+        let mut pointer = String::new();
+        if let Some(value) = field_mut(document, name, &mut pointer) {
+            if !value.is_null() && !coerce_value(field.field_type, value) {
+                let found = value.clone();
+                return Err(SchemaMismatch::InvalidType {
+                    pointer,
+                    expected: field.field_type,
+                    found,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the document contains all the required fields of the schema.
+pub fn check_required_fields(schema: &Schema, document: &mut Object) -> Result<(), SchemaMismatch> {
+    for (name, _) in schema.iter().filter(|(_, field)| field.required) {
+        let mut pointer = String::new();
+        match field_mut(document, name, &mut pointer) {
+            Some(value) if !value.is_null() => (),
+            _ => return Err(SchemaMismatch::MissingField { pointer: json_pointer(name) }),
+        }
+    }
+
+    Ok(())
+}
+
+/// Retrieves the field with the given dotted name, going through the nested objects,
+/// and writes the JSON pointer to the field in `pointer`.
+fn field_mut<'a>(
+    object: &'a mut Object,
+    name: &str,
+    pointer: &mut String,
+) -> Option<&'a mut Value> {
+    if object.contains_key(name) {
+        push_pointer_token(pointer, name);
+        return object.get_mut(name);
+    }
+
+    let split = name
+        .match_indices('.')
+        .map(|(index, _)| index)
+        .find(|&index| matches!(object.get(&name[..index]), Some(Value::Object(_))))?;
+    let (parent, rest) = (&name[..split], &name[split + 1..]);
+    push_pointer_token(pointer, parent);
+    match object.get_mut(parent) {
+        Some(Value::Object(object)) => field_mut(object, rest, pointer),
+        _ => None,
+    }
+}
+
+/// The JSON pointer of a field of the schema, each level of nesting being a token.
+fn json_pointer(name: &str) -> String {
+    let mut pointer = String::new();
+    name.split('.').for_each(|token| push_pointer_token(&mut pointer, token));
+    pointer
+}
+
+/// Appends an escaped token to a JSON pointer, as defined by RFC 6901.
+fn push_pointer_token(pointer: &mut String, token: &str) {
+    pointer.push('/');
+    pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
+
+/// Returns `false` when the value doesn't have and can't be converted into the given type.
+fn coerce_value(field_type: FieldType, value: &mut Value) -> bool {
+    let coerced = match (field_type, &*value) {
+        (FieldType::String, Value::String(_))
+        | (FieldType::Number, Value::Number(_))
+        | (FieldType::Boolean, Value::Bool(_))
+        | (FieldType::Array, Value::Array(_))
+        | (FieldType::Object, Value::Object(_)) => return true,
+        (FieldType::String, Value::Number(number)) => Value::String(number.to_string()),
+        (FieldType::String, Value::Bool(boolean)) => Value::String(boolean.to_string()),
+        (FieldType::Number, Value::String(string)) => match parse_number(string.trim()) {
+            Some(number) => Value::Number(number),
+            None => return false,
+        },
+        (FieldType::Boolean, Value::String(string)) => match string.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return false,
+        },
+        _ => return false,
+    };
+
+    *value = coerced;
+    true
+}
+
+fn parse_number(string: &str) -> Option<Number> {
+    if let Ok(integer) = string.parse::<i64>() {
+        Some(Number::from(integer))
+    } else {
+        string.parse::<f64>().ok().and_then(Number::from_f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Schema {
+        serde_json::from_value(json!({
+            "title": { "type": "string", "required": true },
+            "price": { "type": "number" },
+            "available": { "type": "boolean" },
+            "product.dimensions": { "type": "object" },
+            "product.tags": { "type": "array" },
+        }))
+        .unwrap()
+    }
+
+    fn object(value: Value) -> Object {
+        match value {
+            Value::Object(object) => object,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn coerce_values() {
+        let mut document = object(json!({
+            "title": 42,
+            "price": " 12.5 ",
+            "available": "true",
+            "product": { "dimensions": {}, "tags": ["a"] },
+        }));
+        coerce_document_fields(&schema(), &mut document).unwrap();
+        assert_eq!(
+            Value::Object(document),
+            json!({
+                "title": "42",
+                "price": 12.5,
+                "available": true,
+                "product": { "dimensions": {}, "tags": ["a"] },
+            })
+        );
+
+        let mut document = object(json!({ "title": "hello", "product.tags": [], "price": null }));
+        coerce_document_fields(&schema(), &mut document).unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_values() {
+        let mut document = object(json!({ "title": "hello", "price": "twelve" }));
+        let error = coerce_document_fields(&schema(), &mut document).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the field `/price` must be of type number but instead got `\"twelve\"`"
+        );
+
+        let mut document = object(json!({ "title": "hello", "product": { "dimensions": [1, 2] } }));
+        let error = coerce_document_fields(&schema(), &mut document).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the field `/product/dimensions` must be of type object but instead got `[1,2]`"
+        );
+    }
+
+    #[test]
+    fn reject_missing_required_fields() {
+        let mut document = object(json!({ "title": "hello" }));
+        check_required_fields(&schema(), &mut document).unwrap();
+
+        let mut document = object(json!({ "title": null, "price": 10 }));
+        let error = check_required_fields(&schema(), &mut document).unwrap_err();
+        assert_eq!(error, SchemaMismatch::MissingField { pointer: S("/title") });
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::mem::take;
 use std::ops::{BitOr, BitOrAssign};
@@ -18,7 +19,10 @@ use super::CriterionImplementationStrategy;
 use crate::search::criteria::geo::Geo;
 use crate::search::{word_derivations, Distinct, WordDerivationsCache};
 use crate::update::{MAX_LENGTH_FOR_PREFIX_PROXIMITY_DB, MAX_PROXIMITY_FOR_PREFIX_PROXIMITY_DB};
-use crate::{AscDesc as AscDescName, DocumentId, FieldId, Index, Member, Result};
+use crate::{
+    absolute_from_relative_position, AscDesc as AscDescName, DocumentId, FieldId, Index, Member,
+    Result,
+};
 
 mod asc_desc;
 mod attribute;
@@ -137,6 +141,16 @@ impl BitOr for InitialCandidates {
 pub trait Context<'c> {
     fn documents_ids(&self) -> heed::Result<RoaringBitmap>;
     fn word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>>;
+    /// Returns the attributes in which a word derived with `typo` typos from a query word
+    /// of `original_word_len` characters can be found, `None` if it can be found in all of them.
+    fn typo_fields_ids(&self, typo: u8, original_word_len: u8) -> Result<Option<Vec<FieldId>>>;
+    /// Returns the documents ids in which the word, or the prefix, appears in one of the attributes.
+    fn word_fields_docids(
+        &self,
+        word: &str,
+        in_prefix_cache: bool,
+        fields_ids: &[FieldId],
+    ) -> heed::Result<RoaringBitmap>;
     fn exact_word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>>;
     fn word_prefix_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>>;
     fn exact_word_prefix_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>>;
@@ -186,6 +200,10 @@ pub struct CriteriaBuilder<'t> {
     index: &'t Index,
     words_fst: fst::Set<Cow<'t, [u8]>>,
     words_prefixes_fst: fst::Set<Cow<'t, [u8]>>,
+    /// The index-wide minimum word lengths for one and two typos.
+    min_word_len_for_typo: (u8, u8),
+    /// The minimum word lengths for typos of the attributes having their own.
+    fields_min_word_len_for_typo: HashMap<FieldId, (u8, u8)>,
 }
 
 /// Return the docids for the following word pairs and proximities using [`Context::word_pair_proximity_docids`].
@@ -259,6 +277,59 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
         self.index.word_docids.get(self.rtxn, word)
     }
 
+    fn typo_fields_ids(&self, typo: u8, original_word_len: u8) -> Result<Option<Vec<FieldId>>> {
+        if typo == 0 || self.fields_min_word_len_for_typo.is_empty() {
+            return Ok(None);
+        }
+
+        let allows_typo = |(one, two): (u8, u8)| {
+            let min_len = if typo == 1 { one } else { two };
+            original_word_len >= min_len
+        };
+        let allowed_by_default = allows_typo(self.min_word_len_for_typo);
+        if allowed_by_default && self.fields_min_word_len_for_typo.values().all(|l| allows_typo(*l))
+        {
+            return Ok(None);
+        }
+
+        let fields_ids = self
+            .searchable_fields_ids()?
+            .into_iter()
+            .filter(|field_id| {
+                self.fields_min_word_len_for_typo
+                    .get(field_id)
+                    .map_or(allowed_by_default, |len| allows_typo(*len))
+            })
+            .collect();
+
+        Ok(Some(fields_ids))
+    }
+
+    fn word_fields_docids(
+        &self,
+        word: &str,
+        in_prefix_cache: bool,
+        fields_ids: &[FieldId],
+    ) -> heed::Result<RoaringBitmap> {
+        let db = match in_prefix_cache {
+            true => self.index.word_prefix_position_docids,
+            false => self.index.word_position_docids,
+        };
+
+        // the positions of an attribute are contiguous, we only read the ones of the given attributes
+        let mut docids = RoaringBitmap::new();
+        for field_id in fields_ids {
+            let left = (word, absolute_from_relative_position(*field_id, u16::MIN));
+            let right = (word, absolute_from_relative_position(*field_id, u16::MAX));
+            for result in db.range(self.rtxn, &(left..=right))? {
+                let (_, position_docids) = result?;
+                docids |= position_docids;
+            }
+        }
+
+        Ok(docids)
+    }
+
     fn exact_word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
         self.index.exact_word_docids.get(self.rtxn, word)
     }
@@ -368,7 +439,17 @@ impl<'t> CriteriaBuilder<'t> {
     pub fn new(rtxn: &'t heed::RoTxn<'t>, index: &'t Index) -> Result<Self> {
         let words_fst = index.words_fst(rtxn)?;
         let words_prefixes_fst = index.words_prefixes_fst(rtxn)?;
-        Ok(Self { rtxn, index, words_fst, words_prefixes_fst })
+        let min_word_len_for_typo =
+            (index.min_word_len_one_typo(rtxn)?, index.min_word_len_two_typos(rtxn)?);
+        let fields_min_word_len_for_typo = index.fields_ids_min_word_len_for_typo(rtxn)?;
+        Ok(Self {
+            rtxn,
+            index,
+            words_fst,
+            words_prefixes_fst,
+            min_word_len_for_typo,
+            fields_min_word_len_for_typo,
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -593,9 +674,14 @@ fn query_docids(
     wdcache: &mut WordDerivationsCache,
 ) -> Result<RoaringBitmap> {
     match &query.kind {
-        QueryKind::Exact { word, original_typo } => {
+        QueryKind::Exact { word, original_typo, original_word_len } => {
+            // the attributes allowing the typos of the word are only computed once for the query
+            let fields_ids = ctx.typo_fields_ids(*original_typo, *original_word_len)?;
             if query.prefix && ctx.in_prefix_cache(word) {
-                let mut docids = ctx.word_prefix_docids(word)?.unwrap_or_default();
+                let mut docids = match &fields_ids {
+                    Some(fields_ids) => ctx.word_fields_docids(word, true, fields_ids)?,
+                    None => ctx.word_prefix_docids(word)?.unwrap_or_default(),
+                };
                 // only add the exact docids if the word hasn't been derived
                 if *original_typo == 0 {
                     docids |= ctx.exact_word_prefix_docids(word)?.unwrap_or_default();
@@ -605,7 +691,10 @@ fn query_docids(
                 let words = word_derivations(word, true, 0, ctx.words_fst(), wdcache)?;
                 let mut docids = RoaringBitmap::new();
                 for (word, _typo) in words {
-                    docids |= ctx.word_docids(word)?.unwrap_or_default();
+                    docids |= match &fields_ids {
+                        Some(fields_ids) => ctx.word_fields_docids(word, false, fields_ids)?,
+                        None => ctx.word_docids(word)?.unwrap_or_default(),
+                    };
                     // only add the exact docids if the word hasn't been derived
                     if *original_typo == 0 {
                         docids |= ctx.exact_word_docids(word)?.unwrap_or_default();
@@ -613,7 +702,10 @@ fn query_docids(
                 }
                 Ok(docids)
             } else {
-                let mut docids = ctx.word_docids(word)?.unwrap_or_default();
+                let mut docids = match &fields_ids {
+                    Some(fields_ids) => ctx.word_fields_docids(word, false, fields_ids)?,
+                    None => ctx.word_docids(word)?.unwrap_or_default(),
+                };
                 // only add the exact docids if the word hasn't been derived
                 if *original_typo == 0 {
                     docids |= ctx.exact_word_docids(word)?.unwrap_or_default();
@@ -621,11 +713,24 @@ fn query_docids(
                 Ok(docids)
             }
         }
-        QueryKind::Tolerant { typo, word } => {
-            let words = word_derivations(word, query.prefix, *typo, ctx.words_fst(), wdcache)?;
+        QueryKind::Tolerant { typo, word: original } => {
+            let original_word_len = original.chars().count().min(u8::MAX as usize) as u8;
+            let words = word_derivations(original, query.prefix, *typo, ctx.words_fst(), wdcache)?;
+            // the attributes allowing the typos only depend on the number of typos,
+            // they are computed once for all the derivations of the query word
+            let mut typos_fields_ids = HashMap::new();
             let mut docids = RoaringBitmap::new();
             for (word, typo) in words {
-                let mut current_docids = ctx.word_docids(word)?.unwrap_or_default();
+                let fields_ids = match typos_fields_ids.entry(*typo) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        entry.insert(ctx.typo_fields_ids(*typo, original_word_len)?)
+                    }
+                };
+                let mut current_docids = match fields_ids {
+                    Some(fields_ids) => ctx.word_fields_docids(word, false, fields_ids)?,
+                    None => ctx.word_docids(word)?.unwrap_or_default(),
+                };
                 if *typo == 0 {
                     current_docids |= ctx.exact_word_docids(word)?.unwrap_or_default()
                 }
@@ -826,6 +931,23 @@ pub mod test {
             Ok(self.word_docids.get(&word.to_string()).cloned())
         }
 
+        fn typo_fields_ids(
+            &self,
+            _typo: u8,
+            _original_word_len: u8,
+        ) -> Result<Option<Vec<FieldId>>> {
+            Ok(None)
+        }
+
+        fn word_fields_docids(
+            &self,
+            word: &str,
+            _in_prefix_cache: bool,
+            _fields_ids: &[FieldId],
+        ) -> heed::Result<RoaringBitmap> {
+            Ok(self.word_docids(word)?.unwrap_or_default())
+        }
+
         fn exact_word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
             Ok(self.exact_word_docids.get(&word.to_string()).cloned())
         }
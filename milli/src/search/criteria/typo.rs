@@ -208,9 +208,10 @@ fn alterate_query_tree(
                     if number_typos == 0 {
                         *operation = Operation::Query(Query {
                             prefix: q.prefix,
-                            kind: QueryKind::Exact { original_typo: 0, word: word.clone() },
+                            kind: QueryKind::exact(word.clone()),
                         });
                     } else {
+                        let original_word_len = word.chars().count().min(u8::MAX as usize) as u8;
                         let typo = *typo.min(&number_typos);
                         let words = word_derivations(word, q.prefix, typo, words_fst, wdcache)?;
                         let queries = words
@@ -220,6 +221,7 @@ fn alterate_query_tree(
                                     prefix: false,
                                     kind: QueryKind::Exact {
                                         original_typo: *typo,
+                                        original_word_len,
                                         word: word.to_string(),
                                     },
                                 })
@@ -4,18 +4,28 @@ use std::ops::Bound::{self, Excluded, Included};
 
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Span, Token};
+use heed::types::ByteSlice;
 use roaring::RoaringBitmap;
+use serde_json::Value;
 
 use super::facet_range_search;
 use crate::error::{Error, UserError};
+use crate::facet::date_to_timestamp;
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
+use crate::heed_codec::StrRefCodec;
 use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
 
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
 
+/// The maximum number of distinct facet strings of a field a `CONTAINS` filter scans.
+///
+/// Substrings can't be looked up in the facet database, every level 0 facet string of
+/// the field must be read and compared, we refuse to do that for unbounded fields.
+const MAX_CONTAINS_SCANNED_VALUES: usize = 100_000;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Filter<'a> {
     condition: FilterCondition<'a>,
@@ -29,6 +39,7 @@ enum FilterError<'a> {
     BadGeoLng(f64),
     Reserved(&'a str),
     TooDeep,
+    TooManyValuesForContains,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
 
@@ -57,6 +68,10 @@ impl<'a> Display for FilterError<'a> {
                 "Too many filter conditions, can't process more than {} filters.",
                 MAX_FILTER_DEPTH
             ),
+            Self::TooManyValuesForContains => write!(f,
+                "The `CONTAINS` operator can't be used on an attribute with more than {} distinct values. Use the `STARTS WITH` operator instead.",
+                MAX_CONTAINS_SCANNED_VALUES
+            ),
             Self::Reserved(keyword) => write!(
                 f,
                 "`{}` is a reserved keyword and thus can't be used as a filter expression.",
@@ -127,6 +142,50 @@ impl<'a> Filter<'a> {
         Ok(Some(Self { condition: and }))
     }
 
+    /// Parses a filter given as a JSON string or as an array of strings and arrays of
+    /// strings, the way filters are sent to the search and document routes.
+    pub fn from_json(facets: &'a Value) -> Result<Option<Self>> {
+        match facets {
+            Value::String(expr) => Self::from_str(expr),
+            Value::Array(arr) => Self::parse_filter_array(arr),
+            v => Err(UserError::InvalidFilterExpression(&["String", "Array"], v.clone()).into()),
+        }
+    }
+
+    fn parse_filter_array(arr: &'a [Value]) -> Result<Option<Self>> {
+        let mut ands = Vec::new();
+        for value in arr {
+            match value {
+                Value::String(s) => ands.push(Either::Right(s.as_str())),
+                Value::Array(arr) => {
+                    let mut ors = Vec::new();
+                    for value in arr {
+                        match value {
+                            Value::String(s) => ors.push(s.as_str()),
+                            v => {
+                                return Err(UserError::InvalidFilterExpression(
+                                    &["String"],
+                                    v.clone(),
+                                )
+                                .into())
+                            }
+                        }
+                    }
+                    ands.push(Either::Left(ors));
+                }
+                v => {
+                    return Err(UserError::InvalidFilterExpression(
+                        &["String", "[String]"],
+                        v.clone(),
+                    )
+                    .into())
+                }
+            }
+        }
+
+        Self::from_array(ands)
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(expression: &'a str) -> Result<Option<Self>> {
         let condition = match FilterCondition::parse(expression) {
@@ -158,6 +217,7 @@ impl<'a> Filter<'a> {
         rtxn: &heed::RoTxn,
         index: &Index,
         field_id: FieldId,
+        date_field: bool,
         operator: &Condition<'a>,
     ) -> Result<RoaringBitmap> {
         let numbers_db = index.facet_id_f64_docids;
@@ -169,22 +229,32 @@ impl<'a> Filter<'a> {
 
         let (left, right) = match operator {
             Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+                (Excluded(parse_number(val, date_field)?), Included(f64::MAX))
             }
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
+                (Included(parse_number(val, date_field)?), Included(f64::MAX))
+            }
+            Condition::LowerThan(val) => {
+                (Included(f64::MIN), Excluded(parse_number(val, date_field)?))
             }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
             Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+                (Included(f64::MIN), Included(parse_number(val, date_field)?))
             }
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(parse_number(from, date_field)?), Included(parse_number(to, date_field)?))
             }
             Condition::Exists => {
                 let exist = index.exists_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(exist);
             }
+            Condition::Null => {
+                let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
+                return Ok(is_null);
+            }
+            Condition::Empty => {
+                let is_empty = index.empty_faceted_documents_ids(rtxn, field_id)?;
+                return Ok(is_empty);
+            }
             Condition::Equal(val) => {
                 let string_docids = strings_db
                     .get(
@@ -197,7 +267,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let number = parse_number(val, date_field).ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -209,10 +279,38 @@ impl<'a> Filter<'a> {
             }
             Condition::NotEqual(val) => {
                 let operator = Condition::Equal(val.clone());
-                let docids = Self::evaluate_operator(rtxn, index, field_id, &operator)?;
+                let docids = Self::evaluate_operator(rtxn, index, field_id, date_field, &operator)?;
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
+            Condition::Contains(val) => {
+                // There is no way to look for a substring in the facet database, we must
+                // read and compare every facet string of the field, up to a limit.
+                let substring = val.value().to_lowercase();
+                return Self::explore_facet_string_level_zero(
+                    rtxn,
+                    index,
+                    field_id,
+                    "",
+                    MAX_CONTAINS_SCANNED_VALUES,
+                    |value| value.contains(&substring),
+                )?
+                .ok_or_else(|| {
+                    val.as_external_error(FilterError::TooManyValuesForContains).into()
+                });
+            }
+            Condition::StartsWith(val) => {
+                let prefix = val.value().to_lowercase();
+                return Self::explore_facet_string_level_zero(
+                    rtxn,
+                    index,
+                    field_id,
+                    &prefix,
+                    usize::MAX,
+                    |_| true,
+                )
+                .map(Option::unwrap_or_default);
+            }
         };
 
         let mut output = RoaringBitmap::new();
@@ -220,6 +318,44 @@ impl<'a> Filter<'a> {
         Ok(output)
     }
 
+    /// Aggregates the documents ids of the level 0 facet strings of the field that start
+    /// with the given prefix and that are accepted by the `accept` function.
+    ///
+    /// The facet strings are stored normalized and ordered, the prefix is used to bound
+    /// the range of keys that are scanned. Returns `None` when more than `max_scanned`
+    /// facet strings would have to be read.
+    fn explore_facet_string_level_zero(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        field_id: FieldId,
+        prefix: &str,
+        max_scanned: usize,
+        accept: impl Fn(&str) -> bool,
+    ) -> Result<Option<RoaringBitmap>> {
+        let mut level0_prefix = Vec::new();
+        level0_prefix.extend_from_slice(&field_id.to_be_bytes());
+        level0_prefix.push(0);
+        level0_prefix.extend_from_slice(prefix.as_bytes());
+
+        let iter = index
+            .facet_id_string_docids
+            .remap_key_type::<ByteSlice>()
+            .prefix_iter(rtxn, &level0_prefix)?
+            .remap_key_type::<FacetGroupKeyCodec<StrRefCodec>>();
+
+        let mut output = RoaringBitmap::new();
+        for (i, result) in iter.enumerate() {
+            if i == max_scanned {
+                return Ok(None);
+            }
+            let (key, value) = result?;
+            if accept(key.left_bound) {
+                output |= value.bitmap;
+            }
+        }
+        Ok(Some(output))
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -265,13 +401,15 @@ impl<'a> Filter<'a> {
             FilterCondition::In { fid, els } => {
                 if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let date_field = index.date_fields(rtxn)?.contains(fid.value());
 
                     if let Some(fid) = field_ids_map.id(fid.value()) {
                         let mut bitmap = RoaringBitmap::new();
 
                         for el in els {
                             let op = Condition::Equal(el.clone());
-                            let el_bitmap = Self::evaluate_operator(rtxn, index, fid, &op)?;
+                            let el_bitmap =
+                                Self::evaluate_operator(rtxn, index, fid, date_field, &op)?;
                             bitmap |= el_bitmap;
                         }
                         Ok(bitmap)
@@ -288,8 +426,9 @@ impl<'a> Filter<'a> {
             FilterCondition::Condition { fid, op } => {
                 if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let date_field = index.date_fields(rtxn)?.contains(fid.value());
                     if let Some(fid) = field_ids_map.id(fid.value()) {
-                        Self::evaluate_operator(rtxn, index, fid, op)
+                        Self::evaluate_operator(rtxn, index, fid, date_field, op)
                     } else {
                         Ok(RoaringBitmap::new())
                     }
@@ -389,6 +528,15 @@ impl<'a> Filter<'a> {
     }
 }
 
+/// Parses the token as a finite float or, for the date fields only, as an RFC 3339 or
+/// ISO 8601 date converted into a Unix timestamp, the way date fields are indexed.
+fn parse_number<'t>(token: &'t Token, date_field: bool) -> std::result::Result<f64, FPError<'t>> {
+    match token.parse_finite_float() {
+        Err(error) if date_field => date_to_timestamp(token.value()).ok_or(error),
+        result => result,
+    }
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
@@ -405,6 +553,8 @@ mod tests {
     use maplit::hashset;
     use roaring::RoaringBitmap;
 
+    use super::MAX_CONTAINS_SCANNED_VALUES;
+    use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
     use crate::Filter;
 
@@ -529,6 +679,18 @@ mod tests {
         assert!(error.to_string().starts_with(
             "Attribute `name` is not filterable. Available filterable attributes are: `title`."
         ));
+
+        let filter = Filter::from_str("name CONTAINS kebab").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `name` is not filterable. Available filterable attributes are: `title`."
+        ));
+
+        let filter = Filter::from_str("name STARTS WITH kebab").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `name` is not filterable. Available filterable attributes are: `title`."
+        ));
     }
 
     #[test]
@@ -835,4 +997,196 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_null_and_empty() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("colour") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "colour": null },
+                { "id": 1, "colour": [] },
+                { "id": 2, "colour": "" },
+                { "id": 3, "colour": "blue" },
+                { "id": 4, "colour": [null] },
+                { "id": 5 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("colour IS NULL").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        let filter = Filter::from_str("colour IS NOT NULL").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3, 4, 5]));
+
+        let filter = Filter::from_str("colour IS EMPTY").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("colour IS NOT EMPTY").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 3, 4, 5]));
+
+        let filter =
+            Filter::from_str("colour EXISTS AND NOT colour IS NULL AND NOT colour IS EMPTY")
+                .unwrap()
+                .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([3, 4]));
+        drop(rtxn);
+
+        // the deleted documents must not be returned anymore
+        index.delete_document("0");
+        index.delete_document("1");
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("colour IS NULL OR colour IS EMPTY").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+    }
+
+    #[test]
+    fn filter_contains_and_starts_with() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("title") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "Kebab" },
+                { "id": 1, "title": "Super Kebab" },
+                { "id": 2, "title": ["Salad", "kebabs and fries"] },
+                { "id": 3, "title": "Pizza" },
+                { "id": 4, "title": 42 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("title STARTS WITH keb").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+
+        let filter = Filter::from_str("title STARTS WITH 'Super K'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1]));
+
+        let filter = Filter::from_str("title NOT STARTS WITH keb").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 3, 4]));
+
+        let filter = Filter::from_str("title CONTAINS KEBAB").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+
+        let filter = Filter::from_str("title CONTAINS 'and fr'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+
+        let filter = Filter::from_str("title NOT CONTAINS z").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2, 4]));
+
+        let filter = Filter::from_str("title CONTAINS 4").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn filter_contains_too_many_values() {
+        let mut index = TempIndex::new_with_map_size(4096 * 100_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("title") }))
+            .unwrap();
+
+        let documents = (0..=MAX_CONTAINS_SCANNED_VALUES)
+            .map(|i| {
+                serde_json::json!({ "title": format!("title {i}") }).as_object().unwrap().clone()
+            })
+            .collect::<Vec<_>>();
+        index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("title CONTAINS 42").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(&format!(
+            "The `CONTAINS` operator can't be used on an attribute with more than {} distinct values. Use the `STARTS WITH` operator instead.",
+            MAX_CONTAINS_SCANNED_VALUES
+        )));
+
+        // the prefixes can still be looked up.
+        let filter = Filter::from_str("title STARTS WITH 'title 99999'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn filter_date() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("published_at"), S("label") });
+                settings.set_date_fields(hashset! { S("published_at") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "published_at": "2022-12-31T23:59:59Z" },
+                { "id": 1, "published_at": "2023-01-01", "label": "2023-01-01" },
+                { "id": 2, "published_at": "2023-01-01T12:00:00+02:00" },
+                { "id": 3, "published_at": "2023-02-15T08:30:00Z" },
+                { "id": 4, "published_at": "not a date" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("published_at >= 2023-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3]));
+
+        let filter = Filter::from_str("published_at < '2023-01-01T00:00:00Z'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        let filter = Filter::from_str("published_at 2023-01-01 TO 2023-01-31").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("published_at = '2023-01-01T10:00:00Z'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+
+        let filter = Filter::from_str("published_at = 'not a date'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4]));
+
+        // dates are only parsed for the date fields
+        let filter = Filter::from_str("label = '2023-01-01T00:00:00Z'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(result.is_empty());
+
+        let filter = Filter::from_str("label = 2023-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1]));
+    }
 }
@@ -6,7 +6,6 @@ use std::result::Result as StdResult;
 use std::str::Utf8Error;
 use std::time::Instant;
 
-use charabia::TokenizerBuilder;
 use distinct::{Distinct, DocIter, FacetDistinct, NoopDistinct};
 use fst::automaton::Str;
 use fst::{Automaton, IntoStreamer, Streamer};
@@ -22,9 +21,10 @@ pub use self::matches::{
 };
 use self::query_tree::QueryTreeBuilder;
 use crate::error::UserError;
+use crate::localized_attributes_rules::locales_allow_list;
 use crate::search::criteria::r#final::{Final, FinalResult};
 use crate::search::criteria::InitialCandidates;
-use crate::{AscDesc, Criterion, DocumentId, Index, Member, Result};
+use crate::{AscDesc, Criterion, DocumentId, Index, Locale, Member, Result};
 
 // Building these factories is not free.
 static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
@@ -50,6 +50,7 @@ pub struct Search<'a> {
     words_limit: usize,
     exhaustive_number_hits: bool,
     criterion_implementation_strategy: CriterionImplementationStrategy,
+    locales: Option<Vec<Locale>>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -67,6 +68,7 @@ impl<'a> Search<'a> {
             exhaustive_number_hits: false,
             words_limit: 10,
             criterion_implementation_strategy: CriterionImplementationStrategy::default(),
+            locales: None,
             rtxn,
             index,
         }
@@ -77,6 +79,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Forces the languages used to tokenize the query.
+    pub fn locales(&mut self, locales: Vec<Locale>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
     pub fn offset(&mut self, offset: usize) -> &mut Search<'a> {
         self.offset = offset;
         self
@@ -146,11 +154,16 @@ impl<'a> Search<'a> {
                 builder.words_limit(self.words_limit);
                 // We make sure that the analyzer is aware of the stop words
                 // this ensures that the query builder is able to properly remove them.
-                let mut tokbuilder = TokenizerBuilder::new();
+                let tokenizer_words = self.index.tokenizer_words(self.rtxn)?;
+                let mut tokbuilder = tokenizer_words.tokenizer_builder();
                 let stop_words = self.index.stop_words(self.rtxn)?;
                 if let Some(ref stop_words) = stop_words {
                     tokbuilder.stop_words(stop_words);
                 }
+                let allow_list = self.locales.as_deref().map(locales_allow_list);
+                if let Some(ref allow_list) = allow_list {
+                    tokbuilder.allow_list(allow_list);
+                }
 
                 let tokenizer = tokbuilder.build();
                 let tokens = tokenizer.tokenize(query);
@@ -301,6 +314,7 @@ impl fmt::Debug for Search<'_> {
             words_limit,
             exhaustive_number_hits,
             criterion_implementation_strategy,
+            locales,
             rtxn: _,
             index: _,
         } = self;
@@ -315,6 +329,7 @@ impl fmt::Debug for Search<'_> {
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("criterion_implementation_strategy", criterion_implementation_strategy)
             .field("words_limit", words_limit)
+            .field("locales", locales)
             .finish()
     }
 }
@@ -13,6 +13,7 @@ use slice_group_by::GroupBy;
 
 use crate::search::matches::matching_words::{MatchingWord, PrimitiveWordId};
 use crate::search::TermsMatchingStrategy;
+use crate::stemming::Stemmers;
 use crate::{CboRoaringBitmapLenCodec, Index, MatchingWords, Result};
 
 type IsOptionalWord = bool;
@@ -107,13 +108,23 @@ pub struct Query {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum QueryKind {
-    Tolerant { typo: u8, word: String },
-    Exact { original_typo: u8, word: String },
+    Tolerant {
+        typo: u8,
+        word: String,
+    },
+    /// `original_word_len` is the length of the query word this word has been derived from,
+    /// it decides on which attributes the derivation is allowed when `original_typo` is not zero.
+    Exact {
+        original_typo: u8,
+        original_word_len: u8,
+        word: String,
+    },
 }
 
 impl QueryKind {
     pub fn exact(word: String) -> Self {
-        QueryKind::Exact { original_typo: 0, word }
+        let original_word_len = word.chars().count().min(u8::MAX as usize) as u8;
+        QueryKind::Exact { original_typo: 0, original_word_len, word }
     }
 
     pub fn tolerant(typo: u8, word: String) -> Self {
@@ -163,7 +174,16 @@ trait Context {
     }
     /// Returns the minimum word len for 1 and 2 typos.
     fn min_word_len_for_typo(&self) -> heed::Result<(u8, u8)>;
+    /// Returns the lowest minimum word len for 1 and 2 typos of the index and of its attributes,
+    /// the words derived with typos are then only searched in the attributes allowing them.
+    fn lowest_min_word_len_for_typo(&self) -> heed::Result<(u8, u8)> {
+        self.min_word_len_for_typo()
+    }
     fn exact_words(&self) -> Option<&fst::Set<Cow<[u8]>>>;
+    /// Returns `true` if the words made of digits only must be matched without typos.
+    fn disable_typos_on_numbers(&self) -> bool;
+    /// Returns the stems of the word in the stemming languages of the index.
+    fn stems(&self, word: &str) -> Vec<String>;
     fn word_pair_frequency(
         &self,
         left_word: &str,
@@ -180,6 +200,8 @@ pub struct QueryTreeBuilder<'a> {
     authorize_typos: bool,
     words_limit: Option<usize>,
     exact_words: Option<fst::Set<Cow<'a, [u8]>>>,
+    disable_typos_on_numbers: bool,
+    stemmers: Stemmers,
 }
 
 impl<'a> Context for QueryTreeBuilder<'a> {
@@ -201,10 +223,30 @@ impl<'a> Context for QueryTreeBuilder<'a> {
         Ok((one, two))
     }
 
+    fn lowest_min_word_len_for_typo(&self) -> heed::Result<(u8, u8)> {
+        let (mut one, mut two) = self.min_word_len_for_typo()?;
+        // The attributes can have lower lengths than the index-wide ones, the derivations are
+        // then restricted to the allowed attributes when the documents ids are resolved.
+        for (attr_one, attr_two) in self.index.fields_ids_min_word_len_for_typo(self.rtxn)?.values()
+        {
+            one = one.min(*attr_one);
+            two = two.min(*attr_two);
+        }
+        Ok((one, two))
+    }
+
     fn exact_words(&self) -> Option<&fst::Set<Cow<[u8]>>> {
         self.exact_words.as_ref()
     }
 
+    fn disable_typos_on_numbers(&self) -> bool {
+        self.disable_typos_on_numbers
+    }
+
+    fn stems(&self, word: &str) -> Vec<String> {
+        self.stemmers.stems(word)
+    }
+
     fn word_pair_frequency(
         &self,
         left_word: &str,
@@ -230,6 +272,8 @@ impl<'a> QueryTreeBuilder<'a> {
             authorize_typos: true,
             words_limit: None,
             exact_words: index.exact_words(rtxn)?,
+            disable_typos_on_numbers: index.disable_typos_on_numbers(rtxn)?,
+            stemmers: Stemmers::new(&index.stemming_languages(rtxn)?),
         })
     }
 
@@ -268,9 +312,9 @@ impl<'a> QueryTreeBuilder<'a> {
     /// - if `authorize_typos` is set to `false` the query tree will be generated
     ///   forcing all query words to match documents without any typo
     ///   (the criterion `typo` will be ignored)
-    pub fn build<A: AsRef<[u8]>>(
+    pub fn build(
         &self,
-        query: NormalizedTokenIter<A>,
+        query: NormalizedTokenIter,
     ) -> Result<Option<(Operation, PrimitiveQuery, MatchingWords)>> {
         let primitive_query = create_primitive_query(query, self.words_limit);
         if !primitive_query.is_empty() {
@@ -316,12 +360,17 @@ pub struct TypoConfig<'a> {
     pub word_len_one_typo: u8,
     pub word_len_two_typo: u8,
     pub exact_words: Option<&'a fst::Set<Cow<'a, [u8]>>>,
+    pub disable_on_numbers: bool,
 }
 
 /// Return the `QueryKind` of a word depending on `authorize_typos`
 /// and the provided word length.
 fn typos(word: String, authorize_typos: bool, config: TypoConfig) -> QueryKind {
-    if authorize_typos && !config.exact_words.map_or(false, |s| s.contains(&word)) {
+    let is_number = word.chars().all(|c| c.is_ascii_digit());
+    if authorize_typos
+        && !config.exact_words.map_or(false, |s| s.contains(&word))
+        && !(config.disable_on_numbers && is_number)
+    {
         let count = word.chars().count().min(u8::MAX as usize) as u8;
         if count < config.word_len_one_typo {
             QueryKind::exact(word)
@@ -372,8 +421,9 @@ fn create_query_tree(
         match part {
             // 1. try to split word in 2
             // 2. try to fetch synonyms
-            // 3. create an operation containing the word
-            // 4. wrap all in an OR operation
+            // 3. fetch the stems of the word
+            // 4. create an operation containing the word
+            // 5. wrap all in an OR operation
             PrimitiveQueryPart::Word(word, prefix) => {
                 let mut children = synonyms(ctx, &[&word])?.unwrap_or_default();
                 if let Some((left, right)) = split_best_frequency(ctx, &word)? {
@@ -382,10 +432,22 @@ fn create_query_tree(
                         Some(right.to_string()),
                     ]));
                 }
-                let (word_len_one_typo, word_len_two_typo) = ctx.min_word_len_for_typo()?;
+                for stem in ctx.stems(&word) {
+                    children.push(Operation::Query(Query {
+                        prefix: false,
+                        kind: QueryKind::exact(stem),
+                    }));
+                }
+                let (word_len_one_typo, word_len_two_typo) = ctx.lowest_min_word_len_for_typo()?;
                 let exact_words = ctx.exact_words();
-                let config =
-                    TypoConfig { max_typos: 2, word_len_one_typo, word_len_two_typo, exact_words };
+                let disable_on_numbers = ctx.disable_typos_on_numbers();
+                let config = TypoConfig {
+                    max_typos: 2,
+                    word_len_one_typo,
+                    word_len_two_typo,
+                    exact_words,
+                    disable_on_numbers,
+                };
                 children.push(Operation::Query(Query {
                     prefix,
                     kind: typos(word, authorize_typos, config),
@@ -437,13 +499,14 @@ fn create_query_tree(
                             let mut operations = synonyms(ctx, &words)?.unwrap_or_default();
                             let concat = words.concat();
                             let (word_len_one_typo, word_len_two_typo) =
-                                ctx.min_word_len_for_typo()?;
+                                ctx.lowest_min_word_len_for_typo()?;
                             let exact_words = ctx.exact_words();
                             let config = TypoConfig {
                                 max_typos: 1,
                                 word_len_one_typo,
                                 word_len_two_typo,
                                 exact_words,
+                                disable_on_numbers: ctx.disable_typos_on_numbers(),
                             };
                             let query = Query {
                                 prefix: is_prefix,
@@ -613,8 +676,14 @@ fn create_matching_words(
 
                 let (word_len_one_typo, word_len_two_typo) = ctx.min_word_len_for_typo()?;
                 let exact_words = ctx.exact_words();
-                let config =
-                    TypoConfig { max_typos: 2, word_len_one_typo, word_len_two_typo, exact_words };
+                let disable_on_numbers = ctx.disable_typos_on_numbers();
+                let config = TypoConfig {
+                    max_typos: 2,
+                    word_len_one_typo,
+                    word_len_two_typo,
+                    exact_words,
+                    disable_on_numbers,
+                };
 
                 let matching_word = match typos(word, authorize_typos, config) {
                     QueryKind::Exact { word, .. } => matching_word_cache.insert(word, 0, prefix),
@@ -711,6 +780,7 @@ fn create_matching_words(
                                 word_len_one_typo,
                                 word_len_two_typo,
                                 exact_words,
+                                disable_on_numbers: ctx.disable_typos_on_numbers(),
                             };
                             let matching_word = match typos(word, authorize_typos, config) {
                                 QueryKind::Exact { word, .. } => {
@@ -777,13 +847,10 @@ impl PrimitiveQueryPart {
 
 /// Create primitive query from tokenized query string,
 /// the primitive query is an intermediate state to build the query tree.
-fn create_primitive_query<A>(
-    query: NormalizedTokenIter<A>,
+fn create_primitive_query(
+    query: NormalizedTokenIter,
     words_limit: Option<usize>,
-) -> PrimitiveQuery
-where
-    A: AsRef<[u8]>,
-{
+) -> PrimitiveQuery {
     let mut primitive_query = Vec::new();
     let mut phrase = Vec::new();
     let mut quoted = false;
@@ -887,12 +954,12 @@ mod test {
     }
 
     impl TestContext {
-        fn build<A: AsRef<[u8]>>(
+        fn build(
             &self,
             terms_matching_strategy: TermsMatchingStrategy,
             authorize_typos: bool,
             words_limit: Option<usize>,
-            query: NormalizedTokenIter<A>,
+            query: NormalizedTokenIter,
         ) -> Result<Option<(Operation, PrimitiveQuery)>> {
             let primitive_query = create_primitive_query(query, words_limit);
             if !primitive_query.is_empty() {
@@ -927,6 +994,14 @@ mod test {
             self.exact_words.as_ref()
         }
 
+        fn disable_typos_on_numbers(&self) -> bool {
+            false
+        }
+
+        fn stems(&self, _word: &str) -> Vec<String> {
+            Vec::new()
+        }
+
         fn word_pair_frequency(
             &self,
             left_word: &str,
@@ -1350,6 +1425,7 @@ mod test {
             word_len_one_typo: 5,
             word_len_two_typo: 7,
             exact_words: Some(&exact_words),
+            disable_on_numbers: false,
         };
 
         assert_eq!(
@@ -1368,6 +1444,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_disable_typos_on_numbers() {
+        let config = TypoConfig {
+            max_typos: 2,
+            word_len_one_typo: 5,
+            word_len_two_typo: 9,
+            exact_words: None,
+            disable_on_numbers: true,
+        };
+
+        assert_eq!(
+            typos("123456".to_string(), true, config.clone()),
+            QueryKind::exact("123456".to_string())
+        );
+
+        assert_eq!(
+            typos("abc123".to_string(), true, config.clone()),
+            QueryKind::Tolerant { typo: 1, word: "abc123".to_string() }
+        );
+
+        let config = TypoConfig { disable_on_numbers: false, ..config };
+        assert_eq!(
+            typos("123456".to_string(), true, config),
+            QueryKind::Tolerant { typo: 1, word: "123456".to_string() }
+        );
+    }
+
     #[test]
     fn test_dont_create_matching_word_for_long_words() {
         let index = TempIndex::new();
@@ -0,0 +1,105 @@
+use deserr::DeserializeFromValue;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+
+/// A language for which the words can be reduced to their stem, e.g. `running` and `runs` to `run`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum StemmingLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl StemmingLanguage {
+    fn algorithm(self) -> Algorithm {
+        match self {
+            StemmingLanguage::Arabic => Algorithm::Arabic,
+            StemmingLanguage::Danish => Algorithm::Danish,
+            StemmingLanguage::Dutch => Algorithm::Dutch,
+            StemmingLanguage::English => Algorithm::English,
+            StemmingLanguage::Finnish => Algorithm::Finnish,
+            StemmingLanguage::French => Algorithm::French,
+            StemmingLanguage::German => Algorithm::German,
+            StemmingLanguage::Greek => Algorithm::Greek,
+            StemmingLanguage::Hungarian => Algorithm::Hungarian,
+            StemmingLanguage::Italian => Algorithm::Italian,
+            StemmingLanguage::Norwegian => Algorithm::Norwegian,
+            StemmingLanguage::Portuguese => Algorithm::Portuguese,
+            StemmingLanguage::Romanian => Algorithm::Romanian,
+            StemmingLanguage::Russian => Algorithm::Russian,
+            StemmingLanguage::Spanish => Algorithm::Spanish,
+            StemmingLanguage::Swedish => Algorithm::Swedish,
+            StemmingLanguage::Tamil => Algorithm::Tamil,
+            StemmingLanguage::Turkish => Algorithm::Turkish,
+        }
+    }
+}
+
+/// Reduces the already normalized words to their stems, in every configured language.
+pub struct Stemmers {
+    stemmers: Vec<Stemmer>,
+}
+
+impl Stemmers {
+    pub fn new(languages: &[StemmingLanguage]) -> Self {
+        Stemmers {
+            stemmers: languages
+                .iter()
+                .map(|language| Stemmer::create(language.algorithm()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stemmers.is_empty()
+    }
+
+    /// Returns the distinct stems of the word, the word itself is never part of them.
+    pub fn stems(&self, word: &str) -> Vec<String> {
+        let mut stems: Vec<String> = Vec::new();
+        for stemmer in &self.stemmers {
+            let stem = stemmer.stem(word);
+            if !stem.is_empty() && stem != word && !stems.iter().any(|s| *s == stem) {
+                stems.push(stem.into_owned());
+            }
+        }
+        stems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems() {
+        let stemmers = Stemmers::new(&[]);
+        assert!(stemmers.is_empty());
+        assert!(stemmers.stems("running").is_empty());
+
+        let stemmers = Stemmers::new(&[StemmingLanguage::English]);
+        assert_eq!(stemmers.stems("running"), vec!["run"]);
+        assert_eq!(stemmers.stems("runs"), vec!["run"]);
+        assert!(stemmers.stems("run").is_empty());
+
+        let stemmers = Stemmers::new(&[StemmingLanguage::English, StemmingLanguage::English]);
+        assert_eq!(stemmers.stems("running"), vec!["run"]);
+    }
+}
@@ -33,9 +33,15 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_exists_docids,
+            facet_id_is_null_docids,
+            facet_id_is_empty_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             documents,
+            // The versions are kept as tombstones, a document that is added back
+            // continues from its last version instead of starting again from 1.
+            documents_versions: _,
+            documents_expiration,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -84,10 +90,13 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         word_prefix_position_docids.clear(self.wtxn)?;
         facet_id_f64_docids.clear(self.wtxn)?;
         facet_id_exists_docids.clear(self.wtxn)?;
+        facet_id_is_null_docids.clear(self.wtxn)?;
+        facet_id_is_empty_docids.clear(self.wtxn)?;
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
         documents.clear(self.wtxn)?;
+        documents_expiration.clear(self.wtxn)?;
 
         Ok(number_of_documents)
     }
@@ -137,6 +146,9 @@ mod tests {
         assert!(index.word_prefix_pair_proximity_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_exists_docids.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_is_null_docids.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_is_empty_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_strings.is_empty(&rtxn).unwrap());
         assert!(index.documents.is_empty(&rtxn).unwrap());
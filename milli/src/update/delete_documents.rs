@@ -9,14 +9,15 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::facet::delete::FacetsDelete;
+use super::index_documents::obkv_document_expiration;
 use super::ClearDocuments;
 use crate::error::InternalError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FieldDocIdFacetCodec;
 use crate::heed_codec::CboRoaringBitmapCodec;
 use crate::{
-    ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec,
-    SmallString32, BEU32,
+    DocumentId, ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result,
+    RoaringBitmapCodec, SmallString32, BEU32, BEU64,
 };
 
 pub struct DeleteDocuments<'t, 'u, 'i> {
@@ -156,6 +157,8 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
 
         let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
         let mut field_distribution = self.index.field_distribution(self.wtxn)?;
+        let expires_at_field_id = fields_ids_map.id("_expiresAt");
+        let mut deleted_expirations = Vec::new();
 
         // we update the field distribution
         for docid in self.to_delete_docids.iter() {
@@ -164,6 +167,8 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
                 self.index.documents.get(self.wtxn, &key)?.ok_or(
                     InternalError::DatabaseMissingEntry { db_name: "documents", key: None },
                 )?;
+            let expiration = obkv_document_expiration(expires_at_field_id, document)?;
+            deleted_expirations.extend(expiration.map(|date| (docid, date)));
             for (fid, _value) in document.iter() {
                 let field_name =
                     fields_ids_map.name(fid).ok_or(FieldIdMapMissingEntry::FieldId {
@@ -182,6 +187,9 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
 
         self.index.put_field_distribution(self.wtxn, &field_distribution)?;
 
+        // Deleted documents, even soft deleted ones, can't expire anymore.
+        remove_docids_from_documents_expiration(self.wtxn, self.index, deleted_expirations)?;
+
         soft_deleted_docids |= &self.to_delete_docids;
 
         // We always soft-delete the documents, even if they will be permanently
@@ -243,8 +251,12 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             facet_id_string_docids: _,
             field_id_docid_facet_f64s: _,
             field_id_docid_facet_strings: _,
-            facet_id_exists_docids,
+            facet_id_exists_docids: _,
+            facet_id_is_null_docids: _,
+            facet_id_is_empty_docids: _,
             documents,
+            documents_versions: _,
+            documents_expiration: _,
         } = self.index;
 
         // Retrieve the words contained in the documents.
@@ -452,59 +464,7 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
 
         drop(iter);
 
-        if let Some(mut rtree) = self.index.geo_rtree(self.wtxn)? {
-            let mut geo_faceted_doc_ids = self.index.geo_faceted_documents_ids(self.wtxn)?;
-
-            let (points_to_remove, docids_to_remove): (Vec<_>, RoaringBitmap) = rtree
-                .iter()
-                .filter(|&point| self.to_delete_docids.contains(point.data.0))
-                .cloned()
-                .map(|point| (point, point.data.0))
-                .unzip();
-            points_to_remove.iter().for_each(|point| {
-                rtree.remove(point);
-            });
-            geo_faceted_doc_ids -= docids_to_remove;
-
-            self.index.put_geo_rtree(self.wtxn, &rtree)?;
-            self.index.put_geo_faceted_documents_ids(self.wtxn, &geo_faceted_doc_ids)?;
-        }
-
-        for facet_type in [FacetType::Number, FacetType::String] {
-            let mut affected_facet_values = HashMap::new();
-            for field_id in self.index.faceted_fields_ids(self.wtxn)? {
-                // Remove docids from the number faceted documents ids
-                let mut docids =
-                    self.index.faceted_documents_ids(self.wtxn, field_id, facet_type)?;
-                docids -= &self.to_delete_docids;
-                self.index.put_faceted_documents_ids(self.wtxn, field_id, facet_type, &docids)?;
-
-                let facet_values = remove_docids_from_field_id_docid_facet_value(
-                    self.index,
-                    self.wtxn,
-                    facet_type,
-                    field_id,
-                    &self.to_delete_docids,
-                )?;
-                if !facet_values.is_empty() {
-                    affected_facet_values.insert(field_id, facet_values);
-                }
-            }
-            FacetsDelete::new(
-                self.index,
-                facet_type,
-                affected_facet_values,
-                &self.to_delete_docids,
-            )
-            .execute(self.wtxn)?;
-        }
-
-        // We delete the documents ids that are under the facet field id values.
-        remove_docids_from_facet_id_exists_docids(
-            self.wtxn,
-            facet_id_exists_docids,
-            &self.to_delete_docids,
-        )?;
+        remove_docids_from_facets(self.wtxn, self.index, &self.to_delete_docids)?;
 
         self.index.put_soft_deleted_documents_ids(self.wtxn, &RoaringBitmap::new())?;
 
@@ -574,6 +534,78 @@ fn remove_from_word_docids(
     Ok(())
 }
 
+/// Removes the given documents ids from the expiration dates they are stored under.
+pub(crate) fn remove_docids_from_documents_expiration(
+    wtxn: &mut heed::RwTxn,
+    index: &Index,
+    expirations: impl IntoIterator<Item = (DocumentId, u64)>,
+) -> Result<()> {
+    for (docid, date) in expirations {
+        let date = BEU64::new(date);
+        if let Some(mut docids) = index.documents_expiration.get(wtxn, &date)? {
+            docids.remove(docid);
+            if docids.is_empty() {
+                index.documents_expiration.delete(wtxn, &date)?;
+            } else {
+                index.documents_expiration.put(wtxn, &date, &docids)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes the given documents ids from all the facet databases.
+pub(crate) fn remove_docids_from_facets(
+    wtxn: &mut heed::RwTxn,
+    index: &Index,
+    to_remove: &RoaringBitmap,
+) -> Result<()> {
+    // The geo points are extracted from the `_geo` facet, they are removed with it.
+    if let Some(mut rtree) = index.geo_rtree(wtxn)? {
+        let mut geo_faceted_doc_ids = index.geo_faceted_documents_ids(wtxn)?;
+
+        let (points_to_remove, docids_to_remove): (Vec<_>, RoaringBitmap) = rtree
+            .iter()
+            .filter(|&point| to_remove.contains(point.data.0))
+            .cloned()
+            .map(|point| (point, point.data.0))
+            .unzip();
+        points_to_remove.iter().for_each(|point| {
+            rtree.remove(point);
+        });
+        geo_faceted_doc_ids -= docids_to_remove;
+
+        index.put_geo_rtree(wtxn, &rtree)?;
+        index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_doc_ids)?;
+    }
+
+    for facet_type in [FacetType::Number, FacetType::String] {
+        let mut affected_facet_values = HashMap::new();
+        for field_id in index.faceted_fields_ids(wtxn)? {
+            // Remove docids from the number faceted documents ids
+            let mut docids = index.faceted_documents_ids(wtxn, field_id, facet_type)?;
+            docids -= to_remove;
+            index.put_faceted_documents_ids(wtxn, field_id, facet_type, &docids)?;
+
+            let facet_values = remove_docids_from_field_id_docid_facet_value(
+                index, wtxn, facet_type, field_id, to_remove,
+            )?;
+            if !facet_values.is_empty() {
+                affected_facet_values.insert(field_id, facet_values);
+            }
+        }
+        FacetsDelete::new(index, facet_type, affected_facet_values, to_remove).execute(wtxn)?;
+    }
+
+    // We delete the documents ids that are under the facet field id values.
+    remove_docids_from_facet_id_docids(wtxn, &index.facet_id_exists_docids, to_remove)?;
+    remove_docids_from_facet_id_docids(wtxn, &index.facet_id_is_null_docids, to_remove)?;
+    remove_docids_from_facet_id_docids(wtxn, &index.facet_id_is_empty_docids, to_remove)?;
+
+    Ok(())
+}
+
 fn remove_docids_from_field_id_docid_facet_value(
     index: &'_ Index,
     wtxn: &'_ mut heed::RwTxn,
@@ -608,7 +640,7 @@ fn remove_docids_from_field_id_docid_facet_value(
     Ok(all_affected_facet_values)
 }
 
-fn remove_docids_from_facet_id_exists_docids<'a, C>(
+fn remove_docids_from_facet_id_docids<'a, C>(
     wtxn: &'a mut heed::RwTxn,
     db: &heed::Database<C, CboRoaringBitmapCodec>,
     to_remove: &RoaringBitmap,
@@ -2,13 +2,17 @@ use std::io::{Read, Seek};
 use std::result::Result as StdResult;
 use std::{fmt, iter};
 
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 use crate::documents::{DocumentsBatchIndex, DocumentsBatchReader, EnrichedDocumentsBatchReader};
 use crate::error::{GeoError, InternalError, UserError};
+use crate::schema::{coerce_document_fields, schema_covers_field};
 use crate::update::index_documents::{obkv_to_object, writer_into_reader};
-use crate::{FieldId, Index, Object, Result};
+use crate::{composite_document_id, FieldId, Index, Object, Result, Schema};
 
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
@@ -20,7 +24,14 @@ const DEFAULT_PRIMARY_KEY: &str = "id";
 ///  - we can infer a primary key,
 ///  - all the documents id exist and are extracted,
 ///  - the validity of them but also,
-///  - the validity of the `_geo` field depending on the settings.
+///  - the validity of the `_geo` field depending on the settings,
+///  - the validity of the `_version` field used as a precondition,
+///  - the validity of the `_expiresAt` field,
+///  - the type of the fields declared in the schema of the index.
+///
+/// When `skip_invalid_documents` is set, the documents that don't pass these checks
+/// are left out of the returned reader and returned along with it, instead of failing
+/// the whole batch.
 ///
 /// # Panics
 ///
@@ -29,8 +40,9 @@ pub fn enrich_documents_batch<R: Read + Seek>(
     rtxn: &heed::RoTxn,
     index: &Index,
     autogenerate_docids: bool,
+    skip_invalid_documents: bool,
     reader: DocumentsBatchReader<R>,
-) -> Result<StdResult<EnrichedDocumentsBatchReader<R>, UserError>> {
+) -> Result<StdResult<(EnrichedDocumentsBatchReader<R>, Vec<InvalidDocument>), UserError>> {
     let (mut cursor, mut documents_batch_index) = reader.into_cursor_and_fields_index();
 
     let mut external_ids = tempfile::tempfile().map(grenad::Writer::new)?;
@@ -38,25 +50,31 @@ pub fn enrich_documents_batch<R: Read + Seek>(
 
     // The primary key *field id* that has already been set for this index or the one
     // we will guess by searching for the first key that contains "id" as a substring.
-    let primary_key = match index.primary_key(rtxn)? {
-        Some(primary_key) if primary_key.contains(PRIMARY_KEY_SPLIT_SYMBOL) => {
+    let index_primary_key = index.primary_key(rtxn)?;
+    let primary_key = match &index_primary_key {
+        Some(crate::PrimaryKey::Composite(names)) => PrimaryKey::composite(names),
+        Some(crate::PrimaryKey::Field(primary_key))
+            if primary_key.contains(PRIMARY_KEY_SPLIT_SYMBOL) =>
+        {
             PrimaryKey::nested(primary_key)
         }
-        Some(primary_key) => match documents_batch_index.id(primary_key) {
-            Some(id) => PrimaryKey::flat(primary_key, id),
-            None if autogenerate_docids => {
-                PrimaryKey::flat(primary_key, documents_batch_index.insert(primary_key))
-            }
-            None => {
-                return match cursor.next_document()? {
-                    Some(first_document) => Ok(Err(UserError::MissingDocumentId {
-                        primary_key: primary_key.to_string(),
-                        document: obkv_to_object(&first_document, &documents_batch_index)?,
-                    })),
-                    None => unreachable!("Called with reader.is_empty()"),
-                };
+        Some(crate::PrimaryKey::Field(primary_key)) => {
+            match documents_batch_index.id(primary_key) {
+                Some(id) => PrimaryKey::flat(primary_key, id),
+                None if autogenerate_docids => {
+                    PrimaryKey::flat(primary_key, documents_batch_index.insert(primary_key))
+                }
+                None => {
+                    return match cursor.next_document()? {
+                        Some(first_document) => Ok(Err(UserError::MissingDocumentId {
+                            primary_key: primary_key.to_string(),
+                            document: obkv_to_object(&first_document, &documents_batch_index)?,
+                        })),
+                        None => unreachable!("Called with reader.is_empty()"),
+                    };
+                }
             }
-        },
+        }
         None => {
             let mut guesses: Vec<(u16, &str)> = documents_batch_index
                 .iter()
@@ -102,7 +120,21 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         _otherwise => None,
     };
 
+    let version_field_id = documents_batch_index.id("_version");
+    let expires_at_field_id = documents_batch_index.id("_expiresAt");
+    let schema = index.schema(rtxn)?;
+
+    // The values of the declared fields are coerced once here and stored for the transform.
+    let mut coerced_values = match schema.is_empty() {
+        true => None,
+        false => Some(tempfile::tempfile().map(grenad::Writer::new)?),
+    };
+    let mut coerced_buffer = Vec::new();
+
     let mut count = 0;
+    let mut offset = 0;
+    let mut skipped_offsets = RoaringBitmap::new();
+    let mut invalid_documents = Vec::new();
     while let Some(document) = cursor.next_document()? {
         let document_id = match fetch_or_generate_document_id(
             &document,
@@ -112,31 +144,125 @@ pub fn enrich_documents_batch<R: Read + Seek>(
             &mut uuid_buffer,
             count,
         )? {
-            Ok(document_id) => document_id,
-            Err(user_error) => return Ok(Err(user_error)),
+            Ok(document_id) => validate_document_fields(
+                &document,
+                &documents_batch_index,
+                document_id,
+                geo_field_id,
+                version_field_id,
+                expires_at_field_id,
+                &schema,
+            )?,
+            Err(user_error) => Err((None, user_error)),
         };
 
-        if let Some(geo_value) = geo_field_id.and_then(|fid| document.get(fid)) {
-            if let Err(user_error) = validate_geo_from_json(&document_id, geo_value)? {
-                return Ok(Err(UserError::from(user_error)));
+        match document_id {
+            Ok((document_id, coerced_document)) => {
+                let document_id =
+                    serde_json::to_vec(&document_id).map_err(InternalError::SerdeJson)?;
+                external_ids.insert(count.to_be_bytes(), document_id)?;
+                if let Some((writer, coerced_document)) =
+                    coerced_values.as_mut().zip(coerced_document)
+                {
+                    coerced_buffer.clear();
+                    let mut values = obkv::KvWriter::new(&mut coerced_buffer);
+                    for (field_id, _) in document.iter() {
+                        let name = documents_batch_index.name(field_id);
+                        if let Some(name) = name.filter(|name| schema_covers_field(&schema, name)) {
+                            let value = serde_json::to_vec(&coerced_document[name])
+                                .map_err(InternalError::SerdeJson)?;
+                            values.insert(field_id, value)?;
+                        }
+                    }
+                    values.finish()?;
+                    writer.insert(count.to_be_bytes(), &coerced_buffer)?;
+                }
+                count += 1;
             }
+            Err((document_id, error)) if skip_invalid_documents => {
+                skipped_offsets.insert(offset);
+                invalid_documents.push(InvalidDocument { offset, document_id, error });
+            }
+            Err((_, error)) => return Ok(Err(error)),
         }
 
-        let document_id = serde_json::to_vec(&document_id).map_err(InternalError::SerdeJson)?;
-        external_ids.insert(count.to_be_bytes(), document_id)?;
-
-        count += 1;
+        offset += 1;
     }
 
     let external_ids = writer_into_reader(external_ids)?;
-    let primary_key_name = primary_key.name().to_string();
+    let coerced_values = coerced_values.map(writer_into_reader).transpose()?;
+    let primary_key = primary_key.to_index_primary_key();
     let reader = EnrichedDocumentsBatchReader::new(
         DocumentsBatchReader::new(cursor, documents_batch_index),
-        primary_key_name,
+        primary_key,
         external_ids,
+        coerced_values,
+        skipped_offsets,
     )?;
 
-    Ok(Ok(reader))
+    Ok(Ok((reader, invalid_documents)))
+}
+
+/// A document of a batch that was left out because it is invalid.
+#[derive(Debug)]
+pub struct InvalidDocument {
+    /// The position of the document in its batch.
+    pub offset: u32,
+    /// The id of the document, when it could be retrieved.
+    pub document_id: Option<String>,
+    pub error: UserError,
+}
+
+/// Checks the fields of a document whose id is valid, returning the id of the document
+/// along with the `UserError` if one of its fields is invalid.
+///
+/// When the index has a schema, the document is returned with its declared fields coerced.
+fn validate_document_fields(
+    document: &obkv::KvReader<FieldId>,
+    documents_batch_index: &DocumentsBatchIndex,
+    document_id: DocumentId,
+    geo_field_id: Option<FieldId>,
+    version_field_id: Option<FieldId>,
+    expires_at_field_id: Option<FieldId>,
+    schema: &Schema,
+) -> Result<StdResult<(DocumentId, Option<Object>), (Option<String>, UserError)>> {
+    let invalid = |document_id: &DocumentId, user_error| {
+        Ok(Err((Some(document_id.value().to_string()), user_error)))
+    };
+
+    if let Some(geo_value) = geo_field_id.and_then(|fid| document.get(fid)) {
+        if let Err(user_error) = validate_geo_from_json(&document_id, geo_value)? {
+            return invalid(&document_id, UserError::from(user_error));
+        }
+    }
+
+    if let Some(version) = version_field_id.and_then(|fid| document.get(fid)) {
+        if let Err(user_error) = validate_document_version(&document_id, version)? {
+            return invalid(&document_id, user_error);
+        }
+    }
+
+    if let Some(expires_at) = expires_at_field_id.and_then(|fid| document.get(fid)) {
+        if let Err(user_error) = validate_document_expiration(&document_id, expires_at)? {
+            return invalid(&document_id, user_error);
+        }
+    }
+
+    if schema.is_empty() {
+        return Ok(Ok((document_id, None)));
+    }
+
+    let mut object = obkv_to_object(document, documents_batch_index)?;
+    match coerce_document_fields(schema, &mut object) {
+        Ok(()) => Ok(Ok((document_id, Some(object)))),
+        Err(mismatch) => {
+            let user_error = UserError::InvalidDocumentSchema {
+                document_id: Value::from(document_id.debug()),
+                mismatch,
+            };
+            invalid(&document_id, user_error)
+        }
+    }
 }
 
 /// Retrieve the document id after validating it, returning a `UserError`
@@ -170,9 +296,9 @@ fn fetch_or_generate_document_id(
                 })),
             }
         }
-        nested @ PrimaryKey::Nested { .. } => {
+        PrimaryKey::Nested { name } => {
             let mut matching_documents_ids = Vec::new();
-            for (first_level_name, right) in nested.possible_level_names() {
+            for (first_level_name, right) in possible_level_names(name) {
                 if let Some(field_id) = documents_batch_index.id(first_level_name) {
                     if let Some(value_bytes) = document.get(field_id) {
                         let object = serde_json::from_slice(value_bytes)
@@ -181,7 +307,7 @@ fn fetch_or_generate_document_id(
 
                         if matching_documents_ids.len() >= 2 {
                             return Ok(Err(UserError::TooManyDocumentIds {
-                                primary_key: nested.name().to_string(),
+                                primary_key: name.to_string(),
                                 document: obkv_to_object(document, documents_batch_index)?,
                             }));
                         }
@@ -195,20 +321,68 @@ fn fetch_or_generate_document_id(
                     Err(user_error) => Ok(Err(user_error)),
                 },
                 None => Ok(Err(UserError::MissingDocumentId {
-                    primary_key: nested.name().to_string(),
+                    primary_key: name.to_string(),
                     document: obkv_to_object(document, documents_batch_index)?,
                 })),
             }
         }
+        PrimaryKey::Composite { names } => {
+            let mut components = Vec::with_capacity(names.len());
+            for name in names {
+                // Every field of a composite primary key is validated like a primary key of its own.
+                let component = match fetch_or_generate_document_id(
+                    document,
+                    documents_batch_index,
+                    PrimaryKey::nested(name),
+                    false,
+                    uuid_buffer,
+                    count,
+                )? {
+                    Ok(component) => component,
+                    Err(user_error) => return Ok(Err(user_error)),
+                };
+                components.push(component);
+            }
+            let document_id = composite_document_id(components.iter().map(DocumentId::value));
+            Ok(Ok(DocumentId::retrieved(document_id)))
+        }
     }
 }
 
+/// Returns the id of a document that has already been validated, whether its primary key
+/// is flat, nested or composite, or `None` if the document doesn't have a valid id.
+///
+/// The `get_field` function must return the value of the given top-level field of the document.
+pub fn extract_external_document_id(
+    primary_key: &crate::PrimaryKey,
+    mut get_field: impl FnMut(&str) -> Result<Option<Value>>,
+) -> Result<Option<String>> {
+    let mut components = Vec::with_capacity(primary_key.fields().len());
+    for name in primary_key.fields() {
+        let mut matching_documents_ids = Vec::new();
+        for (first_level_name, right) in possible_level_names(name) {
+            if let Some(value) = get_field(first_level_name)? {
+                fetch_matching_values(value, right, &mut matching_documents_ids);
+            }
+        }
+
+        match matching_documents_ids.pop() {
+            Some(Value::String(component)) => components.push(component),
+            Some(Value::Number(component)) => components.push(component.to_string()),
+            _ => return Ok(None),
+        }
+    }
+
+    Ok(Some(composite_document_id(components)))
+}
+
 /// A type that represent the type of primary key that has been set
-/// for this index, a classic flat one or a nested one.
+/// for this index, a classic flat one, a nested one or a composite one.
 #[derive(Debug, Clone, Copy)]
 enum PrimaryKey<'a> {
     Flat { name: &'a str, field_id: FieldId },
     Nested { name: &'a str },
+    Composite { names: &'a [String] },
 }
 
 impl PrimaryKey<'_> {
@@ -220,21 +394,27 @@ impl PrimaryKey<'_> {
         PrimaryKey::Nested { name }
     }
 
-    fn name(&self) -> &str {
+    fn composite(names: &[String]) -> PrimaryKey {
+        PrimaryKey::Composite { names }
+    }
+
+    /// Returns the primary key to store in the index.
+    fn to_index_primary_key(self) -> crate::PrimaryKey {
         match self {
-            PrimaryKey::Flat { name, .. } => name,
-            PrimaryKey::Nested { name } => name,
+            PrimaryKey::Flat { name, .. } | PrimaryKey::Nested { name } => {
+                crate::PrimaryKey::Field(name.to_string())
+            }
+            PrimaryKey::Composite { names } => crate::PrimaryKey::Composite(names.to_vec()),
         }
     }
+}
 
-    /// Returns an `Iterator` that gives all the possible fields names the primary key
-    /// can have depending of the first level name and deepnes of the objects.
-    fn possible_level_names(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
-        let name = self.name();
-        name.match_indices(PRIMARY_KEY_SPLIT_SYMBOL)
-            .map(move |(i, _)| (&name[..i], &name[i + PRIMARY_KEY_SPLIT_SYMBOL.len_utf8()..]))
-            .chain(iter::once((name, "")))
-    }
+/// Returns an `Iterator` that gives all the possible fields names a nested primary key
+/// can have depending of the first level name and deepnes of the objects.
+fn possible_level_names(name: &str) -> impl Iterator<Item = (&str, &str)> + '_ {
+    name.match_indices(PRIMARY_KEY_SPLIT_SYMBOL)
+        .map(move |(i, _)| (&name[..i], &name[i + PRIMARY_KEY_SPLIT_SYMBOL.len_utf8()..]))
+        .chain(iter::once((name, "")))
 }
 
 /// A type that represents a document id that has been retrieved from a document or auto-generated.
@@ -365,6 +545,63 @@ pub fn extract_finite_float_from_value(value: Value) -> StdResult<f64, Value> {
     }
 }
 
+/// Parses the Json encoded `_version` of a document, returning a user error
+/// when it is not a positive integer.
+pub fn validate_document_version(
+    id: &DocumentId,
+    bytes: &[u8],
+) -> Result<StdResult<u64, UserError>> {
+    match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+        Value::Number(number) if number.is_u64() => Ok(Ok(number.as_u64().unwrap())),
+        version => Ok(Err(UserError::InvalidDocumentVersion {
+            document_id: Value::from(id.debug()),
+            version,
+        })),
+    }
+}
+
+/// Parses the Json encoded `_expiresAt` of a document, returning a user error
+/// when it is neither an RFC 3339 date nor a Unix timestamp.
+pub fn validate_document_expiration(
+    id: &DocumentId,
+    bytes: &[u8],
+) -> Result<StdResult<u64, UserError>> {
+    let expires_at = serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)?;
+    match parse_document_expiration(&expires_at) {
+        Some(expiration) => Ok(Ok(expiration)),
+        None => Ok(Err(UserError::InvalidDocumentExpiresAt {
+            document_id: Value::from(id.debug()),
+            expires_at,
+        })),
+    }
+}
+
+/// Returns the expiration date described by the `_expiresAt` value of a document,
+/// in seconds since the Unix epoch. Dates before the epoch are clamped to it.
+pub fn parse_document_expiration(expires_at: &Value) -> Option<u64> {
+    let timestamp = match expires_at {
+        Value::Number(number) => number.as_i64()?,
+        Value::String(date) => OffsetDateTime::parse(date, &Rfc3339).ok()?.unix_timestamp(),
+        _ => return None,
+    };
+    Some(timestamp.max(0) as u64)
+}
+
+/// Returns the expiration date of a document stored in the index,
+/// when it has a valid `_expiresAt` field.
+pub fn obkv_document_expiration(
+    expires_at_field_id: Option<FieldId>,
+    document: obkv::KvReaderU16,
+) -> Result<Option<u64>> {
+    match expires_at_field_id.and_then(|fid| document.get(fid)) {
+        Some(bytes) => {
+            let expires_at = serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)?;
+            Ok(parse_document_expiration(&expires_at))
+        }
+        None => Ok(None),
+    }
+}
+
 pub fn validate_geo_from_json(id: &DocumentId, bytes: &[u8]) -> Result<StdResult<(), GeoError>> {
     use GeoError::*;
     let debug_id = || Value::from(id.debug());
@@ -1,31 +1,44 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::{io, mem, str};
 
-use charabia::{SeparatorKind, Token, TokenKind, TokenizerBuilder};
+use charabia::{Language, Script, SeparatorKind, Token, TokenKind, Tokenizer, TokenizerBuilder};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, GrenadParameters};
 use crate::error::{InternalError, SerializationError};
+use crate::localized_attributes_rules::locales_allow_list;
+use crate::stemming::Stemmers;
 use crate::{
-    absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
+    absolute_from_relative_position, FieldId, Locale, Result, StemmingLanguage,
+    MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
 };
 
 /// Extracts the word and positions where this word appear and
 /// prefixes it by the document id.
 ///
-/// Returns the generated internal documents ids and a grenad reader
-/// with the list of extracted words from the given chunk of documents.
+/// The stems of the words are extracted at the same positions than the original words,
+/// these ones are kept so that the exactness criterion can favor them. The stems are
+/// extracted apart from the words, they must not be used to compute the proximities
+/// and the word counts as they would be counted as additional words of the documents.
+///
+/// Returns the generated internal documents ids and two grenad readers with the list of
+/// extracted words and the list of extracted stems from the given chunk of documents.
+#[allow(clippy::too_many_arguments)]
 #[logging_timer::time]
 pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
     stop_words: Option<&fst::Set<&[u8]>>,
+    allowed_separators: Option<&[&str]>,
+    dictionary: Option<&[&str]>,
+    localized_fields: &HashMap<FieldId, Vec<Locale>>,
+    stemming_languages: &[StemmingLanguage],
     max_positions_per_attributes: Option<u32>,
-) -> Result<(RoaringBitmap, grenad::Reader<File>)> {
+) -> Result<(RoaringBitmap, grenad::Reader<File>, grenad::Reader<File>)> {
     let max_positions_per_attributes = max_positions_per_attributes
         .map_or(MAX_POSITION_PER_ATTRIBUTE, |max| max.min(MAX_POSITION_PER_ATTRIBUTE));
     let max_memory = indexer.max_memory_by_thread();
@@ -39,14 +52,39 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         indexer.max_nb_chunks,
         max_memory,
     );
+    let mut docid_stem_positions_sorter = create_sorter(
+        grenad::SortAlgorithm::Stable,
+        concat_u32s_array,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+    );
 
     let mut key_buffer = Vec::new();
     let mut field_buffer = String::new();
-    let mut builder = TokenizerBuilder::new();
-    if let Some(stop_words) = stop_words {
-        builder.stop_words(stop_words);
-    }
+    let mut builder = tokenizer_builder(stop_words, allowed_separators, dictionary, None);
     let tokenizer = builder.build();
+    let stemmers = Stemmers::new(stemming_languages);
+
+    // The fields with locales have their own tokenizer, only allowed
+    // to detect the languages of these locales.
+    let allow_lists: HashMap<_, _> = localized_fields
+        .iter()
+        .map(|(field_id, locales)| (*field_id, locales_allow_list(locales)))
+        .collect();
+    let mut localized_builders: HashMap<_, _> = allow_lists
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let builder =
+                tokenizer_builder(stop_words, allowed_separators, dictionary, Some(allow_list));
+            (*field_id, builder)
+        })
+        .collect();
+    let localized_tokenizers: HashMap<FieldId, Tokenizer> = localized_builders
+        .iter_mut()
+        .map(|(field_id, builder)| (*field_id, builder.build()))
+        .collect();
 
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
@@ -66,21 +104,29 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
                 field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut field_buffer) {
+                    let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(&tokenizer);
                     let tokens = process_tokens(tokenizer.tokenize(field))
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                     for (index, token) in tokens {
                         let token = token.lemma().trim();
                         if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
-                            key_buffer.truncate(mem::size_of::<u32>());
-                            key_buffer.extend_from_slice(token.as_bytes());
-
                             let position: u16 = index
                                 .try_into()
                                 .map_err(|_| SerializationError::InvalidNumberSerialization)?;
                             let position = absolute_from_relative_position(field_id, position);
+
+                            key_buffer.truncate(mem::size_of::<u32>());
+                            key_buffer.extend_from_slice(token.as_bytes());
                             docid_word_positions_sorter
                                 .insert(&key_buffer, position.to_ne_bytes())?;
+
+                            for stem in stemmers.stems(token) {
+                                key_buffer.truncate(mem::size_of::<u32>());
+                                key_buffer.extend_from_slice(stem.as_bytes());
+                                docid_stem_positions_sorter
+                                    .insert(&key_buffer, position.to_ne_bytes())?;
+                            }
                         }
                     }
                 }
@@ -88,7 +134,33 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         }
     }
 
-    sorter_into_reader(docid_word_positions_sorter, indexer).map(|reader| (documents_ids, reader))
+    let docid_word_positions = sorter_into_reader(docid_word_positions_sorter, indexer)?;
+    let docid_stem_positions = sorter_into_reader(docid_stem_positions_sorter, indexer)?;
+
+    Ok((documents_ids, docid_word_positions, docid_stem_positions))
+}
+
+/// Creates a tokenizer builder using the stop words, separators and dictionary of the index.
+fn tokenizer_builder<'a>(
+    stop_words: Option<&'a fst::Set<&[u8]>>,
+    allowed_separators: Option<&'a [&str]>,
+    dictionary: Option<&'a [&str]>,
+    allow_list: Option<&'a HashMap<Script, Vec<Language>>>,
+) -> TokenizerBuilder<'a, &'a [u8]> {
+    let mut builder = TokenizerBuilder::new();
+    if let Some(stop_words) = stop_words {
+        builder.stop_words(stop_words);
+    }
+    if let Some(separators) = allowed_separators {
+        builder.separators(separators);
+    }
+    if let Some(dictionary) = dictionary {
+        builder.words_dict(dictionary);
+    }
+    if let Some(allow_list) = allow_list {
+        builder.allow_list(allow_list);
+    }
+    builder
 }
 
 /// Transform a JSON value into a string that can be indexed.
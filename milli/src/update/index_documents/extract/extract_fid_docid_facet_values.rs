@@ -12,20 +12,35 @@ use serde_json::Value;
 
 use super::helpers::{create_sorter, keep_first, sorter_into_reader, GrenadParameters};
 use crate::error::InternalError;
+use crate::facet::date_to_timestamp;
 use crate::facet::value_encoding::f64_into_bytes;
 use crate::update::index_documents::{create_writer, writer_into_reader};
 use crate::{CboRoaringBitmapCodec, DocumentId, FieldId, Result, BEU32, MAX_FACET_VALUE_LENGTH};
 
+/// The extracted facet values stored in grenad files by type.
+pub struct ExtractedFacetValues {
+    pub docid_fid_facet_numbers_chunk: grenad::Reader<File>,
+    pub docid_fid_facet_strings_chunk: grenad::Reader<File>,
+    pub fid_facet_is_null_docids_chunk: grenad::Reader<File>,
+    pub fid_facet_is_empty_docids_chunk: grenad::Reader<File>,
+    pub fid_facet_exists_docids_chunk: grenad::Reader<File>,
+}
+
 /// Extracts the facet values of each faceted field of each document.
 ///
 /// Returns the generated grenad reader containing the docid the fid and the orginal value as key
 /// and the normalized value as value extracted from the given chunk of documents.
+/// It also returns the docids of the documents for which the facet value is null or empty.
+///
+/// The string values of the date fields that can be parsed as dates are extracted as
+/// facet numbers, the Unix timestamp of the date, instead of facet strings.
 #[logging_timer::time]
 pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     faceted_fields: &HashSet<FieldId>,
-) -> Result<(grenad::Reader<File>, grenad::Reader<File>, grenad::Reader<File>)> {
+    date_fields: &HashSet<FieldId>,
+) -> Result<ExtractedFacetValues> {
     let max_memory = indexer.max_memory_by_thread();
 
     let mut fid_docid_facet_numbers_sorter = create_sorter(
@@ -47,6 +62,8 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     );
 
     let mut facet_exists_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
+    let mut facet_is_null_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
+    let mut facet_is_empty_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
 
     let mut key_buffer = Vec::new();
     let mut cursor = obkv_documents.into_cursor()?;
@@ -73,7 +90,14 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                 let value =
                     serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
 
-                let (numbers, strings) = extract_facet_values(&value);
+                if value.is_null() {
+                    facet_is_null_docids.entry(field_id).or_default().insert(document);
+                } else if is_empty_value(&value) {
+                    facet_is_empty_docids.entry(field_id).or_default().insert(document);
+                }
+
+                let is_date = date_fields.contains(&field_id);
+                let (numbers, strings) = extract_facet_values(&value, is_date);
 
                 // insert facet numbers in sorter
                 for number in numbers {
@@ -102,28 +126,47 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
         }
     }
 
-    let mut facet_exists_docids_writer = create_writer(
+    Ok(ExtractedFacetValues {
+        docid_fid_facet_numbers_chunk: sorter_into_reader(fid_docid_facet_numbers_sorter, indexer)?,
+        docid_fid_facet_strings_chunk: sorter_into_reader(fid_docid_facet_strings_sorter, indexer)?,
+        fid_facet_is_null_docids_chunk: fid_docids_into_reader(facet_is_null_docids, indexer)?,
+        fid_facet_is_empty_docids_chunk: fid_docids_into_reader(facet_is_empty_docids, indexer)?,
+        fid_facet_exists_docids_chunk: fid_docids_into_reader(facet_exists_docids, indexer)?,
+    })
+}
+
+/// Writes the docids of each field id in a grenad file, the keys being encoded like the `FieldIdCodec`.
+fn fid_docids_into_reader(
+    fid_docids: BTreeMap<FieldId, RoaringBitmap>,
+    indexer: GrenadParameters,
+) -> Result<grenad::Reader<File>> {
+    let mut writer = create_writer(
         indexer.chunk_compression_type,
         indexer.chunk_compression_level,
         tempfile::tempfile()?,
     );
-    for (fid, bitmap) in facet_exists_docids.into_iter() {
+    for (fid, bitmap) in fid_docids.into_iter() {
         let bitmap_bytes = CboRoaringBitmapCodec::bytes_encode(&bitmap).unwrap();
-        facet_exists_docids_writer.insert(fid.to_be_bytes(), &bitmap_bytes)?;
+        writer.insert(fid.to_be_bytes(), &bitmap_bytes)?;
     }
-    let facet_exists_docids_reader = writer_into_reader(facet_exists_docids_writer)?;
+    writer_into_reader(writer)
+}
 
-    Ok((
-        sorter_into_reader(fid_docid_facet_numbers_sorter, indexer)?,
-        sorter_into_reader(fid_docid_facet_strings_sorter, indexer)?,
-        facet_exists_docids_reader,
-    ))
+/// Returns `true` if the value is an empty string, an empty array or an empty object.
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        Value::Null | Value::Bool(_) | Value::Number(_) => false,
+    }
 }
 
-fn extract_facet_values(value: &Value) -> (Vec<f64>, Vec<(String, String)>) {
+fn extract_facet_values(value: &Value, is_date: bool) -> (Vec<f64>, Vec<(String, String)>) {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
+        is_date: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
     ) {
@@ -136,6 +179,12 @@ fn extract_facet_values(value: &Value) -> (Vec<f64>, Vec<(String, String)>) {
                 }
             }
             Value::String(original) => {
+                if is_date {
+                    if let Some(timestamp) = date_to_timestamp(original) {
+                        output_numbers.push(timestamp);
+                        return;
+                    }
+                }
                 let normalized = CompatibilityDecompositionNormalizer
                     .normalize_str(original.trim())
                     .to_lowercase();
@@ -144,7 +193,13 @@ fn extract_facet_values(value: &Value) -> (Vec<f64>, Vec<(String, String)>) {
             Value::Array(values) => {
                 if can_recurse {
                     for value in values {
-                        inner_extract_facet_values(value, false, output_numbers, output_strings);
+                        inner_extract_facet_values(
+                            value,
+                            false,
+                            is_date,
+                            output_numbers,
+                            output_strings,
+                        );
                     }
                 }
             }
@@ -154,7 +209,13 @@ fn extract_facet_values(value: &Value) -> (Vec<f64>, Vec<(String, String)>) {
 
     let mut facet_number_values = Vec::new();
     let mut facet_string_values = Vec::new();
-    inner_extract_facet_values(value, true, &mut facet_number_values, &mut facet_string_values);
+    inner_extract_facet_values(
+        value,
+        true,
+        is_date,
+        &mut facet_number_values,
+        &mut facet_string_values,
+    );
 
     (facet_number_values, facet_string_values)
 }
@@ -7,7 +7,7 @@ use serde_json::Value;
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
 use crate::error::GeoError;
 use crate::update::index_documents::extract_finite_float_from_value;
-use crate::{FieldId, InternalError, Result};
+use crate::{composite_document_id, FieldId, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
@@ -16,7 +16,7 @@ use crate::{FieldId, InternalError, Result};
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
-    primary_key_id: FieldId,
+    primary_key_ids: &[FieldId],
     (lat_fid, lng_fid): (FieldId, FieldId),
 ) -> Result<grenad::Reader<File>> {
     let mut writer = create_writer(
@@ -29,10 +29,19 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     while let Some((docid_bytes, value)) = cursor.move_on_next()? {
         let obkv = obkv::KvReader::new(value);
         // since we only needs the primary key when we throw an error we create this getter to
-        // lazily get it when needed
+        // lazily get it when needed, the values of a composite primary key are joined.
         let document_id = || -> Value {
-            let document_id = obkv.get(primary_key_id).unwrap();
-            serde_json::from_slice(document_id).unwrap()
+            let mut values = primary_key_ids.iter().map(|fid| match obkv.get(*fid) {
+                Some(value) => serde_json::from_slice(value).unwrap(),
+                None => Value::Null,
+            });
+            match primary_key_ids {
+                [_] => values.next().unwrap(),
+                _ => Value::String(composite_document_id(values.map(|value| match value {
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                }))),
+            }
         };
 
         // first we get the two fields
@@ -3,9 +3,12 @@ use std::io;
 use std::result::Result as StdResult;
 
 use roaring::RoaringBitmap;
+use serde_json::Value;
 
 use super::read_u32_ne_bytes;
+use crate::error::InternalError;
 use crate::heed_codec::CboRoaringBitmapCodec;
+use crate::update::ArrayMergeStrategy;
 use crate::Result;
 
 pub type MergeFn = for<'a> fn(&[u8], &[Cow<'a, [u8]>]) -> Result<Cow<'a, [u8]>>;
@@ -88,6 +91,107 @@ pub fn merge_two_obkvs(base: obkv::KvReaderU16, update: obkv::KvReaderU16, buffe
     writer.finish().unwrap();
 }
 
+/// Deep merge all the obkvs in the order we see them, arrays are replaced.
+pub fn deep_merge_obkvs_replace_arrays<'a>(
+    _key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    deep_merge_obkvs(obkvs, ArrayMergeStrategy::Replace)
+}
+
+/// Deep merge all the obkvs in the order we see them, arrays are concatenated.
+pub fn deep_merge_obkvs_append_arrays<'a>(
+    _key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    deep_merge_obkvs(obkvs, ArrayMergeStrategy::Append)
+}
+
+/// Deep merge all the obkvs in the order we see them, arrays are concatenated without duplicates.
+pub fn deep_merge_obkvs_union_arrays<'a>(
+    _key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    deep_merge_obkvs(obkvs, ArrayMergeStrategy::Union)
+}
+
+fn deep_merge_obkvs<'a>(
+    obkvs: &[Cow<'a, [u8]>],
+    strategy: ArrayMergeStrategy,
+) -> Result<Cow<'a, [u8]>> {
+    let mut acc = obkvs[0].clone();
+    for current in &obkvs[1..] {
+        let first = obkv::KvReader::new(&acc);
+        let second = obkv::KvReader::new(current);
+        let mut buffer = Vec::new();
+        deep_merge_two_obkvs(first, second, strategy, &mut buffer)?;
+        acc = Cow::from(buffer);
+    }
+    Ok(acc)
+}
+
+/// Merge two obkvs, the values of the fields present in both of them are merged
+/// recursively when they are both objects, or both arrays, according to the strategy.
+pub fn deep_merge_two_obkvs(
+    base: obkv::KvReaderU16,
+    update: obkv::KvReaderU16,
+    strategy: ArrayMergeStrategy,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    buffer.clear();
+
+    let mut writer = obkv::KvWriter::new(buffer);
+    for eob in merge_join_by(base.iter(), update.iter(), |(b, _), (u, _)| b.cmp(u)) {
+        match eob {
+            Both((k, left), (_, right)) => {
+                let left = serde_json::from_slice(left).map_err(InternalError::SerdeJson)?;
+                let right = serde_json::from_slice(right).map_err(InternalError::SerdeJson)?;
+                let value = deep_merge_values(left, right, strategy);
+                let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
+                writer.insert(k, value)?;
+            }
+            Left((k, v)) | Right((k, v)) => writer.insert(k, v)?,
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+fn deep_merge_values(base: Value, update: Value, strategy: ArrayMergeStrategy) -> Value {
+    match (base, update) {
+        (Value::Object(mut base), Value::Object(update)) => {
+            for (key, value) in update {
+                let value = match base.remove(&key) {
+                    Some(previous) => deep_merge_values(previous, value, strategy),
+                    None => value,
+                };
+                base.insert(key, value);
+            }
+            Value::Object(base)
+        }
+        (Value::Array(mut base), Value::Array(update)) => match strategy {
+            ArrayMergeStrategy::Replace => Value::Array(update),
+            ArrayMergeStrategy::Append => {
+                base.extend(update);
+                Value::Array(base)
+            }
+            ArrayMergeStrategy::Union => {
+                for value in update {
+                    if !base.contains(&value) {
+                        base.push(value);
+                    }
+                }
+                Value::Array(base)
+            }
+        },
+        (_, update) => update,
+    }
+}
+
 pub fn merge_cbo_roaring_bitmaps<'a>(
     _key: &[u8],
     values: &[Cow<'a, [u8]>],
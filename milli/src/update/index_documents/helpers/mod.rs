@@ -13,8 +13,9 @@ pub use grenad_helpers::{
     GrenadParameters, MergeableReader,
 };
 pub use merge_functions::{
-    concat_u32s_array, keep_first, keep_latest_obkv, merge_cbo_roaring_bitmaps, merge_obkvs,
-    merge_roaring_bitmaps, merge_two_obkvs, roaring_bitmap_from_u32s_array,
+    concat_u32s_array, deep_merge_obkvs_append_arrays, deep_merge_obkvs_replace_arrays,
+    deep_merge_obkvs_union_arrays, keep_first, keep_latest_obkv, merge_cbo_roaring_bitmaps,
+    merge_obkvs, merge_roaring_bitmaps, merge_two_obkvs, roaring_bitmap_from_u32s_array,
     serialize_roaring_bitmap, MergeFn,
 };
 
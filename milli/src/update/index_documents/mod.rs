@@ -4,13 +4,14 @@ mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::io::{Cursor, Read, Seek};
 use std::iter::FromIterator;
 use std::num::NonZeroU32;
 use std::result::Result as StdResult;
 
 use crossbeam_channel::{Receiver, Sender};
+use deserr::DeserializeFromValue;
 use heed::types::Str;
 use heed::Database;
 use log::debug;
@@ -21,8 +22,9 @@ use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
 
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{
-    extract_finite_float_from_value, validate_document_id, validate_document_id_value,
-    validate_geo_from_json, DocumentId,
+    extract_external_document_id, extract_finite_float_from_value, obkv_document_expiration,
+    parse_document_expiration, validate_document_id, validate_document_id_value,
+    validate_geo_from_json, DocumentId, InvalidDocument,
 };
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
@@ -38,18 +40,21 @@ use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
     WordPrefixDocids, WordPrefixPositionDocids, WordsPrefixesFst,
 };
-use crate::{Index, Result, RoaringBitmapCodec};
+use crate::{Index, Result, RoaringBitmapCodec, BEU64};
 
 static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 5;
 static TOTAL_POSTING_DATABASE_COUNT: usize = MERGED_DATABASE_COUNT + PREFIX_DATABASE_COUNT;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug)]
 pub struct DocumentAdditionResult {
     /// The number of documents that were indexed during the update
     pub indexed_documents: u64,
     /// The total number of documents in the index after the update
     pub number_of_documents: u64,
+    /// The documents that were left out once merged because they miss a required field,
+    /// along with the position of the batch their last version comes from.
+    pub invalid_documents: Vec<(usize, InvalidDocument)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -62,6 +67,10 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Merge the previous version of the document with the new version, recursively
+    /// merging the nested objects and merging the arrays according to the strategy.
+    DeepMergeDocuments(ArrayMergeStrategy),
 }
 
 impl Default for IndexDocumentsMethod {
@@ -70,6 +79,24 @@ impl Default for IndexDocumentsMethod {
     }
 }
 
+/// How the arrays present in both versions of a document are merged by a deep merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, DeserializeFromValue)]
+#[deserr(rename_all = camelCase)]
+pub enum ArrayMergeStrategy {
+    /// The array of the new version replaces the previous one.
+    Replace,
+    /// The values of the new array are appended to the previous one.
+    Append,
+    /// The values of the new array that are not in the previous one are appended to it.
+    Union,
+}
+
+impl Default for ArrayMergeStrategy {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
 pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     wtxn: &'t mut heed::RwTxn<'i, 'u>,
     index: &'i Index,
@@ -79,6 +106,8 @@ pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     progress: FP,
     should_abort: FA,
     added_documents: u64,
+    added_batches: usize,
+    invalid_documents: Vec<InvalidDocument>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -90,6 +119,38 @@ pub struct IndexDocumentsConfig {
     pub update_method: IndexDocumentsMethod,
     pub deletion_strategy: DeletionStrategy,
     pub autogenerate_docids: bool,
+    /// Leave the invalid documents out of the batches instead of failing them.
+    ///
+    /// The required fields of the schema are only checked once the documents are merged,
+    /// the documents missing one of them are left out by [`IndexDocuments::execute`] and
+    /// returned in the [`DocumentAdditionResult`].
+    pub skip_invalid_documents: bool,
+}
+
+/// Creates the sandboxed engine running the document edition functions, it is limited so that
+/// a function can neither run forever nor exhaust the memory.
+fn document_edition_engine() -> rhai::Engine {
+    let mut engine = rhai::Engine::new();
+    engine.set_optimization_level(rhai::OptimizationLevel::Full);
+    engine.set_max_operations(1_000_000);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1024 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.disable_symbol("eval");
+    // the functions must not be able to write anything on the server output.
+    engine.on_print(|_| ());
+    engine.on_debug(|_, _, _| ());
+    engine
+}
+
+/// Makes sure a document edition function compiles, to refuse it before it is processed.
+pub fn validate_document_edition_function(function: &str) -> StdResult<(), UserError> {
+    match document_edition_engine().compile(function) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(UserError::DocumentEditionCompilationError(e)),
+    }
 }
 
 impl<'t, 'u, 'i, 'a, FP, FA> IndexDocuments<'t, 'u, 'i, 'a, FP, FA>
@@ -122,6 +183,8 @@ where
             wtxn,
             index,
             added_documents: 0,
+            added_batches: 0,
+            invalid_documents: Vec::new(),
         })
     }
 
@@ -130,11 +193,18 @@ where
     /// Since the documents are progressively added to the writer, a failure will cause only
     /// return an error and not the `IndexDocuments` struct as it is invalid to use it afterward.
     ///
+    /// The `_version` field of a document is reserved: it is the version the document must
+    /// currently have for the addition to be applied, see [`Index::document_version`]. It is
+    /// never stored in the document and must be an unsigned integer.
+    ///
     /// Returns the number of documents added to the builder.
     pub fn add_documents<R: Read + Seek>(
         mut self,
         reader: DocumentsBatchReader<R>,
     ) -> Result<(Self, StdResult<u64, UserError>)> {
+        let batch = self.added_batches;
+        self.added_batches += 1;
+
         // Early return when there is no document to add
         if reader.is_empty() {
             return Ok((self, Ok(0)));
@@ -147,15 +217,21 @@ where
             self.wtxn,
             self.index,
             self.config.autogenerate_docids,
+            self.config.skip_invalid_documents,
             reader,
         )? {
-            Ok(reader) => reader,
+            Ok((reader, invalid_documents)) => {
+                self.invalid_documents = invalid_documents;
+                reader
+            }
             Err(user_error) => return Ok((self, Err(user_error))),
         };
 
+        let skippable_batch = self.config.skip_invalid_documents.then_some(batch);
         let indexed_documents =
             self.transform.as_mut().expect("Invalid document addition state").read_documents(
                 enriched_documents_reader,
+                skippable_batch,
                 self.wtxn,
                 &self.progress,
                 &self.should_abort,
@@ -166,13 +242,80 @@ where
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the external ids of the documents of the last batch added with
+    /// [`add_documents`](Self::add_documents) that were not applied because their
+    /// `_version` didn't match the current version of the document.
+    pub fn take_conflicting_documents(&mut self) -> Vec<String> {
+        self.transform
+            .as_mut()
+            .map_or_else(Vec::new, |transform| transform.take_conflicting_documents())
+    }
+
+    /// Returns the external ids that were generated, when [`IndexDocumentsConfig::autogenerate_docids`]
+    /// is enabled, for the documents of the last batch added with [`add_documents`](Self::add_documents)
+    /// that were missing a primary key.
+    pub fn take_generated_documents_ids(&mut self) -> Vec<String> {
+        self.transform
+            .as_mut()
+            .map_or_else(Vec::new, |transform| transform.take_generated_documents_ids())
+    }
+
+    /// Returns the documents of the last batch added with [`add_documents`](Self::add_documents)
+    /// that were left out because they are invalid, when [`IndexDocumentsConfig::skip_invalid_documents`]
+    /// is enabled.
+    pub fn take_invalid_documents(&mut self) -> Vec<InvalidDocument> {
+        std::mem::take(&mut self.invalid_documents)
+    }
+
+    /// Sets whether the invalid documents of the next batches added with
+    /// [`add_documents`](Self::add_documents) are left out instead of failing the batch.
+    pub fn set_skip_invalid_documents(&mut self, skip_invalid_documents: bool) {
+        self.config.skip_invalid_documents = skip_invalid_documents;
+    }
+
+    /// Edits the given documents with a function written in the Rhai scripting language.
+    ///
+    /// The function receives each document in the `doc` variable and modifies it in place,
+    /// only the documents that were modified by the function are reindexed.
+    ///
+    /// Returns the number of edited documents.
+    pub fn edit_documents(
+        mut self,
+        documents: &RoaringBitmap,
+        function: &str,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
+        let engine = document_edition_engine();
+        let ast = match engine.compile(function) {
+            Ok(ast) => ast,
+            Err(e) => return Ok((self, Err(UserError::DocumentEditionCompilationError(e)))),
+        };
+
+        let edited_documents =
+            self.transform.as_mut().expect("Invalid document addition state").edit_documents(
+                self.wtxn,
+                documents,
+                &engine,
+                &ast,
+                &self.progress,
+                &self.should_abort,
+            )? as u64;
+
+        self.added_documents += edited_documents;
+
+        Ok((self, Ok(edited_documents)))
+    }
+
     #[logging_timer::time("IndexDocuments::{}")]
     pub fn execute(mut self) -> Result<DocumentAdditionResult> {
         if self.added_documents == 0 {
             let number_of_documents = self.index.number_of_documents(self.wtxn)?;
-            return Ok(DocumentAdditionResult { indexed_documents: 0, number_of_documents });
+            return Ok(DocumentAdditionResult {
+                indexed_documents: 0,
+                number_of_documents,
+                invalid_documents: Vec::new(),
+            });
         }
-        let output = self
+        let mut output = self
             .transform
             .take()
             .expect("Invalid document addition state")
@@ -193,10 +336,11 @@ where
             )?;
         }
 
-        let indexed_documents = output.documents_count as u64;
+        let invalid_documents = std::mem::take(&mut output.invalid_documents);
+        let indexed_documents = (output.documents_count - invalid_documents.len()) as u64;
         let number_of_documents = self.execute_raw(output)?;
 
-        Ok(DocumentAdditionResult { indexed_documents, number_of_documents })
+        Ok(DocumentAdditionResult { indexed_documents, number_of_documents, invalid_documents })
     }
 
     /// Returns the total number of documents in the index after the update.
@@ -213,7 +357,13 @@ where
             mut external_documents_ids,
             new_documents_ids,
             replaced_documents_ids,
+            updated_facets_documents_ids,
+            facets_only,
+            documents_versions,
+            documents_expirations,
+            outdated_documents_expirations,
             documents_count,
+            invalid_documents: _,
             original_documents,
             flattened_documents,
         } = output;
@@ -250,14 +400,20 @@ where
             Receiver<Result<TypedChunk>>,
         ) = crossbeam_channel::unbounded();
 
-        // get the primary key field id
-        let primary_key_id = fields_ids_map.id(&primary_key).unwrap();
+        // get the ids of the fields of the primary key
+        let primary_key_ids: Vec<_> =
+            primary_key.fields().iter().filter_map(|name| fields_ids_map.id(name)).collect();
 
-        // get searchable fields for word databases
-        let searchable_fields =
-            self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+        // get searchable fields for word databases, none when only the facets are reindexed
+        let searchable_fields = if facets_only {
+            Some(HashSet::new())
+        } else {
+            self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter)
+        };
         // get filterable fields for facet databases
         let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
+        // get the date fields whose values must be extracted as timestamps
+        let date_fields = self.index.date_fields_ids(self.wtxn)?;
         // get the fid of the `_geo.lat` and `_geo.lng` fields.
         let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
             Some(gfid) => {
@@ -280,6 +436,12 @@ where
         };
 
         let stop_words = self.index.stop_words(self.wtxn)?;
+        let separators = self.index.allowed_separators(self.wtxn)?;
+        let dictionary: Option<Vec<_>> =
+            self.index.dictionary(self.wtxn)?.map(|words| words.into_iter().collect());
+        // get the languages forced by the localized attributes rules for each field
+        let localized_fields = self.index.localized_fields_ids(self.wtxn)?;
+        let stemming_languages = self.index.stemming_languages(self.wtxn)?;
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
 
         let pool_params = GrenadParameters {
@@ -312,9 +474,14 @@ where
                     lmdb_writer_sx.clone(),
                     searchable_fields,
                     faceted_fields,
-                    primary_key_id,
+                    date_fields,
+                    &primary_key_ids,
                     geo_fields_ids,
                     stop_words,
+                    separators.as_deref(),
+                    dictionary.as_deref(),
+                    localized_fields,
+                    &stemming_languages,
                     max_positions_per_attributes,
                     exact_attributes,
                 )
@@ -342,6 +509,17 @@ where
             }
         }
 
+        // The documents updated in place keep their docid, only their old facet values and
+        // geo points are removed before the new ones are written.
+        if !updated_facets_documents_ids.is_empty() {
+            debug!("documents facets to reindex {:?}", updated_facets_documents_ids);
+            update::delete_documents::remove_docids_from_facets(
+                self.wtxn,
+                self.index,
+                &updated_facets_documents_ids,
+            )?;
+        }
+
         let index_documents_ids = self.index.documents_ids(self.wtxn)?;
         let index_is_empty = index_documents_ids.is_empty();
         let mut final_documents_ids = RoaringBitmap::new();
@@ -418,6 +596,29 @@ where
         let all_documents_ids = index_documents_ids | new_documents_ids;
         self.index.put_documents_ids(self.wtxn, &all_documents_ids)?;
 
+        // We write the new versions of the documents, after the replaced documents were deleted.
+        for (external_id, version) in documents_versions {
+            self.index.documents_versions.put(self.wtxn, &external_id, &BEU64::new(version))?;
+        }
+
+        // We replace the expiration dates of the documents updated in place and write the new ones.
+        update::delete_documents::remove_docids_from_documents_expiration(
+            self.wtxn,
+            self.index,
+            outdated_documents_expirations,
+        )?;
+        let mut expirations: BTreeMap<u64, RoaringBitmap> = BTreeMap::new();
+        for (docid, date) in documents_expirations {
+            expirations.entry(date).or_default().insert(docid);
+        }
+        for (date, docids) in expirations {
+            let date = BEU64::new(date);
+            let mut expiring =
+                self.index.documents_expiration.get(self.wtxn, &date)?.unwrap_or_default();
+            expiring |= docids;
+            self.index.documents_expiration.put(self.wtxn, &date, &expiring)?;
+        }
+
         self.execute_prefix_databases(
             word_docids,
             exact_word_docids,
@@ -633,8 +834,8 @@ mod tests {
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
     use crate::search::TermsMatchingStrategy;
-    use crate::update::DeleteDocuments;
-    use crate::{db_snap, BEU16};
+    use crate::update::{ClearDocuments, DeleteDocuments};
+    use crate::{db_snap, PrimaryKey, BEU16};
 
     #[test]
     fn simple_document_replacement() {
@@ -740,6 +941,409 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn edit_documents_with_function() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset!(S("stock")));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "KEYBOARD", "stock": 2 },
+                { "id": 2, "name": "mouse", "stock": 10 },
+                { "id": 3, "name": "SCREEN", "stock": 0 },
+            ]))
+            .unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let function = r#"
+            if doc.stock < 5 { doc.stock += 5; }
+            doc.name = doc.name.to_lower();
+        "#;
+        let (builder, edited) =
+            builder.edit_documents(&RoaringBitmap::from_iter([0, 1, 2]), function).unwrap();
+        // the second document was already lowercased and in stock.
+        assert_eq!(edited.unwrap(), 2);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let mut documents: Vec<_> = index
+            .all_documents(&rtxn)
+            .unwrap()
+            .map(|result| crate::all_obkv_to_json(result.unwrap().1, &fields_ids_map).unwrap())
+            .map(serde_json::Value::Object)
+            .collect();
+        documents.sort_by_key(|document| document["id"].as_u64());
+        assert_eq!(
+            documents,
+            vec![
+                serde_json::json!({ "id": 1, "name": "keyboard", "stock": 7 }),
+                serde_json::json!({ "id": 2, "name": "mouse", "stock": 10 }),
+                serde_json::json!({ "id": 3, "name": "screen", "stock": 5 }),
+            ]
+        );
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("stock < 5").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert!(documents_ids.is_empty());
+        drop(rtxn);
+
+        // the primary key of the documents can't be modified.
+        let mut wtxn = index.write_txn().unwrap();
+        let documents_ids = index.documents_ids(&wtxn).unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let result = builder.edit_documents(&documents_ids, "doc.id = 12");
+        assert!(matches!(
+            result,
+            Err(Error::UserError(UserError::DocumentEditionCannotModifyPrimaryKey))
+        ));
+        drop(wtxn);
+
+        // an invalid function is returned as a user error.
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (_builder, edited) = builder.edit_documents(&RoaringBitmap::new(), "doc.id =").unwrap();
+        assert!(matches!(edited, Err(UserError::DocumentEditionCompilationError(_))));
+    }
+
+    #[test]
+    fn update_documents_in_place_when_only_non_searchable_fields_change() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("name")]);
+                settings.set_filterable_fields(hashset!(S("price")));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "keyboard", "price": 20, "color": "black" },
+                { "id": 2, "name": "mouse", "price": 10, "color": "white" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let keyboard = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let mouse = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        drop(rtxn);
+
+        // only a faceted field of the keyboard and a non-indexed field of the mouse change.
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": 25 },
+                { "id": 2, "color": "grey" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(external_documents_ids.get("1"), Some(keyboard));
+        assert_eq!(external_documents_ids.get("2"), Some(mouse));
+        assert_eq!(
+            index.documents_ids(&rtxn).unwrap(),
+            RoaringBitmap::from_iter([keyboard, mouse])
+        );
+
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_, document) = index.documents(&rtxn, Some(mouse)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(document["color"], "grey");
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("price = 25").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![keyboard]);
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("price = 20").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert!(documents_ids.is_empty());
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.query("keyboard");
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![keyboard]);
+        drop(rtxn);
+
+        // a change of a searchable field still reindexes the document under a new docid.
+        index.add_documents(documents!([{ "id": 2, "name": "trackball" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let trackball = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        assert_ne!(trackball, mouse);
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.query("trackball");
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![trackball]);
+    }
+
+    #[test]
+    fn update_facets_in_place_with_geo_filterable() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("name")]);
+                settings.set_filterable_fields(hashset!(S("price"), S("_geo")));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "bakery", "price": 5, "_geo": { "lat": 45.4777599, "lng": 9.1967508 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let bakery = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        drop(rtxn);
+
+        // the facets of the document are updated in place twice
+        index.add_documents(documents!([{ "id": 1, "price": 6 }])).unwrap();
+        index.add_documents(documents!([{ "id": 1, "price": 7 }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.external_documents_ids(&rtxn).unwrap().get("1"), Some(bakery));
+
+        // the geo point of the document is only indexed once
+        let rtree = index.geo_rtree(&rtxn).unwrap().unwrap();
+        let points: Vec<_> = rtree.iter().map(|point| point.data.0).collect();
+        assert_eq!(points, vec![bakery]);
+        assert_eq!(
+            index.geo_faceted_documents_ids(&rtxn).unwrap(),
+            RoaringBitmap::from_iter([bakery])
+        );
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(
+            crate::Filter::from_str("price = 7 AND _geoRadius(45.4777599, 9.1967508, 10)")
+                .unwrap()
+                .unwrap(),
+        );
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![bakery]);
+    }
+
+    #[test]
+    fn documents_versions_preconditions() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kefir" },
+                { "id": 2, "name": "intel" },
+            ]))
+            .unwrap();
+        index.add_documents(documents!([{ "id": 1, "name": "kefir", "age": 2 }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, "1").unwrap(), 2);
+        assert_eq!(index.document_version(&rtxn, "2").unwrap(), 1);
+        assert_eq!(index.document_version(&rtxn, "3").unwrap(), 0);
+        drop(rtxn);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (mut builder, added) = builder
+            .add_documents(documents!([
+                { "id": 1, "name": "KEFIR", "_version": 2 },
+                { "id": 2, "name": "INTEL", "_version": 3 },
+                { "id": 3, "name": "vénus", "_version": 0 },
+            ]))
+            .unwrap();
+        assert_eq!(added.unwrap(), 2);
+        assert_eq!(builder.take_conflicting_documents(), vec![S("2")]);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, "1").unwrap(), 3);
+        assert_eq!(index.document_version(&rtxn, "2").unwrap(), 1);
+        assert_eq!(index.document_version(&rtxn, "3").unwrap(), 1);
+
+        // the `_version` is never stored in the documents.
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(document),
+            serde_json::json!({ "id": 1, "name": "KEFIR" })
+        );
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(document),
+            serde_json::json!({ "id": 2, "name": "intel" })
+        );
+        drop(rtxn);
+
+        // the version of a deleted document is kept and continues when the document is added back.
+        index.delete_document("3");
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, "3").unwrap(), 1);
+        drop(rtxn);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (mut builder, added) = builder
+            .add_documents(documents!([{ "id": 3, "name": "vénus", "_version": 0 }]))
+            .unwrap();
+        assert_eq!(added.unwrap(), 0);
+        assert_eq!(builder.take_conflicting_documents(), vec![S("3")]);
+        let (builder, added) = builder
+            .add_documents(documents!([{ "id": 3, "name": "vénus", "_version": 1 }]))
+            .unwrap();
+        assert_eq!(added.unwrap(), 1);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, "3").unwrap(), 2);
+
+        // clearing the documents keeps the versions too.
+        drop(rtxn);
+        let mut wtxn = index.write_txn().unwrap();
+        ClearDocuments::new(&mut wtxn, &index).execute().unwrap();
+        wtxn.commit().unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, "1").unwrap(), 3);
+    }
+
+    #[test]
+    fn deep_merge_documents() {
+        let mut index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset!(S("price"), S("tags")));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": { "eur": 10, "usd": 12 }, "tags": ["red", "blue"] }
+            ]))
+            .unwrap();
+
+        index.index_documents_config.update_method =
+            IndexDocumentsMethod::DeepMergeDocuments(ArrayMergeStrategy::Union);
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": { "eur": 11 }, "tags": ["blue", "green"] }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let documents: Vec<_> = index
+            .all_documents(&rtxn)
+            .unwrap()
+            .map(|result| crate::all_obkv_to_json(result.unwrap().1, &fields_ids_map).unwrap())
+            .collect();
+        assert_eq!(
+            documents,
+            vec![serde_json::json!({
+                "id": 1,
+                "price": { "eur": 11, "usd": 12 },
+                "tags": ["red", "blue", "green"]
+            })
+            .as_object()
+            .unwrap()
+            .clone()]
+        );
+
+        // the flattened version of the document must also be merged.
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("price.usd = 12").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+
+        search.filter(crate::Filter::from_str("price.eur = 10").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert!(documents_ids.is_empty());
+
+        search.filter(crate::Filter::from_str("tags = red AND tags = green").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+        drop(rtxn);
+
+        // a value replaces an object and the arrays are appended.
+        index.index_documents_config.update_method =
+            IndexDocumentsMethod::DeepMergeDocuments(ArrayMergeStrategy::Append);
+        index.add_documents(documents!([ { "id": 1, "price": 9, "tags": ["red"] } ])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_, document) = index.all_documents(&rtxn).unwrap().next().unwrap().unwrap();
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(document["price"], serde_json::json!(9));
+        assert_eq!(document["tags"], serde_json::json!(["red", "blue", "green", "red"]));
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.filter(crate::Filter::from_str("price.usd = 12").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert!(documents_ids.is_empty());
+
+        search.filter(crate::Filter::from_str("price = 9").unwrap().unwrap());
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids.len(), 1);
+    }
+
     #[test]
     fn not_auto_generated_documents_ids() {
         let index = TempIndex::new();
@@ -834,6 +1438,51 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn returned_auto_generated_documents_ids() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (mut builder, added) = builder
+            .add_documents(documents!([
+                { "id": "kevin", "name": "kevin" },
+                { "name": "kevina" },
+                { "name": "benoit" },
+            ]))
+            .unwrap();
+        assert_eq!(added.unwrap(), 3);
+        let generated_ids = builder.take_generated_documents_ids();
+        assert_eq!(generated_ids.len(), 2);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        // The generated ids are the ones registered in the external documents ids.
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert!(external_documents_ids.get("kevin").is_some());
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        for (generated_id, name) in generated_ids.iter().zip(["kevina", "benoit"]) {
+            assert_eq!(generated_id.len(), 36);
+            let docid = external_documents_ids.get(generated_id).unwrap();
+            let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+            let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+            assert_eq!(
+                serde_json::Value::Object(document),
+                serde_json::json!({ "id": generated_id, "name": name })
+            );
+        }
+    }
+
     #[test]
     fn empty_update() {
         let index = TempIndex::new();
@@ -1068,7 +1717,7 @@ mod tests {
             ]))
             .unwrap();
         let mut wtxn = index.write_txn().unwrap();
-        assert_eq!(index.primary_key(&wtxn).unwrap(), Some("objectId"));
+        assert_eq!(index.primary_key(&wtxn).unwrap(), Some("objectId".into()));
 
         // Delete not all of the documents but some of them.
         let mut builder = DeleteDocuments::new(&mut wtxn, &index).unwrap();
@@ -1357,6 +2006,312 @@ mod tests {
         assert_eq!(documents_ids, vec![3]);
     }
 
+    #[test]
+    fn index_documents_with_composite_primary_key() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings
+                    .set_primary_key(PrimaryKey::Composite(vec![S("tenant_id"), S("product.sku")]));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "product": { "sku": 1 }, "title": "hammer" },
+                { "tenant_id": "acme", "product": { "sku": 2 }, "title": "nails" },
+                { "tenant_id": "globex", "product.sku": 1, "title": "screwdriver" },
+            ]))
+            .unwrap();
+
+        // replacing a document requires the same value for every field of the primary key.
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "product": { "sku": 1 }, "title": "sledgehammer" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 3);
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        // the primary key is not registered as a field of its own.
+        assert!(fields_ids_map.names().all(|name| !name.contains(',')));
+        let docid = external_documents_ids.get("acme:1").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(document["title"], serde_json::json!("sledgehammer"));
+        assert!(external_documents_ids.get("acme:2").is_some());
+        assert!(external_documents_ids.get("globex:1").is_some());
+        drop(rtxn);
+
+        // every field of the primary key must be present and valid.
+        let error =
+            index.add_documents(documents!([{ "tenant_id": "acme", "title": "saw" }])).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::MissingDocumentId { ref primary_key, .. }) if primary_key == "product.sku"
+        ));
+        let error = index
+            .add_documents(documents!([{ "tenant_id": "ac:me", "product": { "sku": 3 } }]))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentId { .. })));
+
+        index.delete_document("acme:2");
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        assert!(index.external_documents_ids(&rtxn).unwrap().get("acme:2").is_none());
+    }
+
+    #[test]
+    fn index_documents_with_comma_in_primary_key() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key(S("tenant,id"));
+            })
+            .unwrap();
+
+        index.add_documents(documents!([{ "tenant,id": 1, "tenant": "acme", "id": 2 }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.primary_key(&rtxn).unwrap(), Some(PrimaryKey::from("tenant,id")));
+        assert!(index.external_documents_ids(&rtxn).unwrap().get("1").is_some());
+    }
+
+    #[test]
+    fn index_documents_with_expiration_dates() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "_expiresAt": "2000-01-01T00:00:00Z" },
+                { "id": 2, "_expiresAt": 4102444800i64 },
+                { "id": 3 },
+                { "id": 4, "_expiresAt": 946684800 },
+            ]))
+            .unwrap();
+
+        let docid = |rtxn: &heed::RoTxn, id: &str| {
+            index.external_documents_ids(rtxn).unwrap().get(id).unwrap()
+        };
+        let now = time::macros::datetime!(2023-01-01 00:00 UTC);
+
+        let rtxn = index.read_txn().unwrap();
+        let expected = RoaringBitmap::from_iter([docid(&rtxn, "1"), docid(&rtxn, "4")]);
+        assert_eq!(index.expired_documents_ids(&rtxn, now).unwrap(), expected);
+        drop(rtxn);
+
+        // replacing a document replaces its expiration date.
+        index.add_documents(documents!([{ "id": 1 }])).unwrap();
+        // deleted documents don't expire anymore.
+        index.delete_document("4");
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.expired_documents_ids(&rtxn, now).unwrap().is_empty());
+        let later = time::macros::datetime!(2100-01-01 00:00 UTC);
+        let expected = RoaringBitmap::from_iter([docid(&rtxn, "2")]);
+        assert_eq!(index.expired_documents_ids(&rtxn, later).unwrap(), expected);
+        drop(rtxn);
+
+        let error =
+            index.add_documents(documents!([{ "id": 5, "_expiresAt": "tomorrow" }])).unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentExpiresAt { .. })));
+        let error = index.add_documents(documents!([{ "id": 5, "_expiresAt": 1.5 }])).unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentExpiresAt { .. })));
+    }
+
+    #[test]
+    fn index_documents_with_schema() {
+        let mut index = TempIndex::new();
+
+        let schema: crate::Schema = serde_json::from_value(serde_json::json!({
+            "name": { "type": "string", "required": true },
+            "stock": { "type": "number" },
+            "details.available": { "type": "boolean" },
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_schema(schema.clone())).unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": 42, "stock": "7", "details": { "available": "true" } },
+                { "id": 2, "name": "mouse", "stock": null },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let mut documents: Vec<_> = index
+            .all_documents(&rtxn)
+            .unwrap()
+            .map(|result| crate::all_obkv_to_json(result.unwrap().1, &fields_ids_map).unwrap())
+            .map(serde_json::Value::Object)
+            .collect();
+        documents.sort_by_key(|document| document["id"].as_u64());
+        assert_eq!(
+            documents,
+            vec![
+                serde_json::json!({ "id": 1, "name": "42", "stock": 7, "details": { "available": true } }),
+                serde_json::json!({ "id": 2, "name": "mouse", "stock": null }),
+            ]
+        );
+        drop(rtxn);
+
+        let error = index
+            .add_documents(documents!([{ "id": 3, "name": "pad", "stock": "a few" }]))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentSchema { .. })));
+        let error = index.add_documents(documents!([{ "id": 3, "stock": 3 }])).unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentSchema { .. })));
+
+        // the required fields already stored don't have to be repeated.
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index.add_documents(documents!([{ "id": 2, "stock": "10" }])).unwrap();
+    }
+
+    #[test]
+    fn update_schema_of_existing_documents() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "keyboard", "stock": "7" },
+                { "id": 2, "stock": 3 },
+            ]))
+            .unwrap();
+
+        // the documents already indexed are coerced to the new schema.
+        let schema: crate::Schema = serde_json::from_value(serde_json::json!({
+            "stock": { "type": "number" },
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_schema(schema.clone())).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(document["stock"], serde_json::json!(7));
+        drop(rtxn);
+
+        // a schema the documents already indexed don't match is rejected.
+        let schema: crate::Schema = serde_json::from_value(serde_json::json!({
+            "name": { "type": "string", "required": true },
+        }))
+        .unwrap();
+        let error =
+            index.update_settings(|settings| settings.set_schema(schema.clone())).unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentSchema { .. })));
+        let rtxn = index.read_txn().unwrap();
+        assert!(!index.schema(&rtxn).unwrap().contains_key("name"));
+        drop(rtxn);
+
+        // the reserved fields and the primary key can't be declared.
+        for name in ["", "a..b", ".a", "_geo", "_geo.lat", "id"] {
+            let schema: crate::Schema = serde_json::from_value(serde_json::json!({
+                name: { "type": "string" },
+            }))
+            .unwrap();
+            let error =
+                index.update_settings(|settings| settings.set_schema(schema.clone())).unwrap_err();
+            assert!(
+                matches!(error, Error::UserError(UserError::InvalidSchemaField { .. })),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn skip_invalid_documents() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| settings.set_sortable_fields(hashset!(S("_geo"))))
+            .unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let config = IndexDocumentsConfig { skip_invalid_documents: true, ..Default::default() };
+        let builder =
+            IndexDocuments::new(&mut wtxn, &index, &index.indexer_config, config, |_| (), || false)
+                .unwrap();
+        let documents = documents!([
+            { "id": 1, "name": "kevin" },
+            { "id": "invalid id", "name": "kevina" },
+            { "id": 3, "name": "benoit", "_geo": { "lat": "north", "lng": 0 } },
+            { "id": 4, "name": "bernie", "_expiresAt": "tomorrow" },
+            { "id": 5, "name": "ben" },
+        ]);
+        let (mut builder, user_error) = builder.add_documents(documents).unwrap();
+        assert_eq!(user_error.unwrap(), 2);
+        let invalid_documents: Vec<_> = builder
+            .take_invalid_documents()
+            .into_iter()
+            .map(|invalid| (invalid.offset, invalid.document_id))
+            .collect();
+        assert_eq!(invalid_documents, vec![(1, None), (2, Some(S("3"))), (3, Some(S("4")))]);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        assert!(external_documents_ids.get("1").is_some());
+        assert!(external_documents_ids.get("3").is_none());
+        assert!(external_documents_ids.get("5").is_some());
+    }
+
+    #[test]
+    fn skip_merged_documents_missing_required_fields() {
+        let index = TempIndex::new();
+        let schema: crate::Schema = serde_json::from_value(serde_json::json!({
+            "name": { "type": "string", "required": true },
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_schema(schema.clone())).unwrap();
+        index.add_documents(documents!([{ "id": 1, "name": "kevin" }])).unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let config = IndexDocumentsConfig {
+            update_method: IndexDocumentsMethod::UpdateDocuments,
+            skip_invalid_documents: true,
+            ..Default::default()
+        };
+        let builder =
+            IndexDocuments::new(&mut wtxn, &index, &index.indexer_config, config, |_| (), || false)
+                .unwrap();
+        let documents = documents!([
+            { "id": 1, "name": null },
+            { "id": 2, "stock": 1 },
+            { "id": 3, "name": "ben" },
+        ]);
+        let (builder, user_error) = builder.add_documents(documents).unwrap();
+        assert_eq!(user_error.unwrap(), 3);
+        let addition = builder.execute().unwrap();
+        let mut invalid_documents: Vec<_> = addition
+            .invalid_documents
+            .into_iter()
+            .map(|(batch, invalid)| (batch, invalid.offset, invalid.document_id))
+            .collect();
+        invalid_documents.sort();
+        assert_eq!(invalid_documents, vec![(0, 0, Some(S("1"))), (0, 1, Some(S("2")))]);
+        assert_eq!(addition.indexed_documents, 1);
+        wtxn.commit().unwrap();
+
+        // the document that was going to be replaced is kept as it is.
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let document = crate::all_obkv_to_json(document, &fields_ids_map).unwrap();
+        assert_eq!(document["name"], serde_json::json!("kevin"));
+        assert!(index.external_documents_ids(&rtxn).unwrap().get("2").is_none());
+        assert_eq!(index.field_distribution(&rtxn).unwrap()["name"], 2);
+    }
+
     #[test]
     fn retrieve_a_b_nested_document_id() {
         let index = TempIndex::new();
@@ -1660,7 +2615,7 @@ mod tests {
 
         {
             let mut wtxn = index.write_txn().unwrap();
-            index.put_primary_key(&mut wtxn, "id").unwrap();
+            index.put_primary_key(&mut wtxn, &"id".into()).unwrap();
             wtxn.commit().unwrap();
         }
 
@@ -1867,7 +2822,7 @@ mod tests {
 
         let txn = index.read_txn().unwrap();
 
-        assert_eq!(index.primary_key(&txn).unwrap().unwrap(), "id");
+        assert_eq!(index.primary_key(&txn).unwrap(), Some("id".into()));
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryInto;
 use std::fs::File;
 use std::io::{Read, Seek};
 
@@ -12,25 +13,47 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 use smartstring::SmartString;
 
-use super::helpers::{create_sorter, create_writer, keep_latest_obkv, merge_obkvs, MergeFn};
-use super::{IndexDocumentsMethod, IndexerConfig};
+use super::helpers::{
+    create_sorter, create_writer, deep_merge_obkvs_append_arrays, deep_merge_obkvs_replace_arrays,
+    deep_merge_obkvs_union_arrays, keep_first, keep_latest_obkv, merge_obkvs, sorter_into_reader,
+    GrenadParameters, MergeFn,
+};
+use super::{
+    extract_external_document_id, obkv_document_expiration, ArrayMergeStrategy,
+    IndexDocumentsMethod, IndexerConfig, InvalidDocument,
+};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
-use crate::error::{Error, InternalError, UserError};
+use crate::error::{Error, InternalError, SerializationError, UserError};
 use crate::index::{db_name, main_key};
+use crate::schema::{check_required_fields, coerce_document_fields, schema_covers_field};
 use crate::update::{AvailableDocumentsIds, ClearDocuments, UpdateIndexingStep};
 use crate::{
-    ExternalDocumentsIds, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index,
-    Result, BEU32,
+    all_obkv_to_json, ExternalDocumentsIds, FieldDistribution, FieldId, FieldIdMapMissingEntry,
+    FieldsIdsMap, Index, Object, PrimaryKey, Result, BEU32,
 };
 
 pub struct TransformOutput {
-    pub primary_key: String,
+    pub primary_key: PrimaryKey,
     pub fields_ids_map: FieldsIdsMap,
     pub field_distribution: FieldDistribution,
     pub external_documents_ids: ExternalDocumentsIds<'static>,
     pub new_documents_ids: RoaringBitmap,
     pub replaced_documents_ids: RoaringBitmap,
+    /// The documents updated in place whose facet values must be reindexed.
+    pub updated_facets_documents_ids: RoaringBitmap,
+    /// Only the facets of the flattened documents must be extracted,
+    /// the documents themselves and their words are already indexed.
+    pub facets_only: bool,
+    /// The new version of the added, updated or edited documents, indexed by their external id.
+    pub documents_versions: HashMap<String, u64>,
+    /// The expiration date of the added, updated or edited documents, by internal id.
+    pub documents_expirations: Vec<(u32, u64)>,
+    /// The previous expiration date of the documents updated in place.
+    pub outdated_documents_expirations: Vec<(u32, u64)>,
     pub documents_count: usize,
+    /// The merged documents left out because they miss a required field of the schema,
+    /// along with the position of the batch their last version comes from.
+    pub invalid_documents: Vec<(usize, InvalidDocument)>,
     pub original_documents: File,
     pub flattened_documents: File,
 }
@@ -53,9 +76,20 @@ pub struct Transform<'a, 'i> {
     original_sorter: grenad::Sorter<MergeFn>,
     flattened_sorter: grenad::Sorter<MergeFn>,
     replaced_documents_ids: RoaringBitmap,
+    // The original docid of the replaced documents, indexed by the docid of their new version.
+    replaced_docids: FxHashMap<u32, u32>,
     new_documents_ids: RoaringBitmap,
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
+    // The new version of the documents, indexed by their external id.
+    documents_versions: HashMap<String, u64>,
+    // The external ids of the documents whose `_version` precondition failed.
+    conflicting_documents: Vec<String>,
+    // The external ids that were generated for the documents missing a primary key.
+    generated_documents_ids: Vec<String>,
+    // The batch and the offset of the last version of the documents that can be left out
+    // once merged, when the index has required fields, indexed by their docid.
+    skippable_documents: FxHashMap<u32, (usize, u32)>,
     documents_count: usize,
 }
 
@@ -82,6 +116,51 @@ fn create_fields_mapping(
         .collect()
 }
 
+/// The fields deciding whether a replaced document can be updated in place.
+struct InPlaceUpdateFields {
+    searchable: Vec<String>,
+    faceted: HashSet<String>,
+}
+
+/// What must be reindexed when a document is replaced by a new version of itself.
+enum DocumentChanges {
+    /// A searchable or geo field changed, the document is entirely reindexed under a new docid.
+    Searchable,
+    /// Only faceted fields changed, holds the flattened faceted fields to reindex.
+    Facets(Vec<u8>),
+    /// Only the stored document changed, none of the databases must be updated.
+    StoredOnly,
+}
+
+/// Merges a file of documents with the documents updated in place, they never share a key.
+fn merge_in_place_documents(
+    indexer_settings: &IndexerConfig,
+    documents: File,
+    in_place_documents: grenad::Sorter<MergeFn>,
+) -> Result<File> {
+    let params = GrenadParameters {
+        chunk_compression_type: indexer_settings.chunk_compression_type,
+        chunk_compression_level: indexer_settings.chunk_compression_level,
+        max_memory: indexer_settings.max_memory,
+        max_nb_chunks: indexer_settings.max_nb_chunks,
+    };
+    let in_place_documents = sorter_into_reader(in_place_documents, params)?;
+
+    let mut documents = documents;
+    documents.rewind()?;
+    let mut builder = grenad::MergerBuilder::new(keep_first as MergeFn);
+    builder.push(grenad::Reader::new(documents)?.into_cursor()?);
+    builder.push(in_place_documents.into_cursor()?);
+
+    let mut writer = create_writer(
+        indexer_settings.chunk_compression_type,
+        indexer_settings.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    builder.build().write_into_stream_writer(&mut writer)?;
+    Ok(writer.into_inner()?)
+}
+
 impl<'a, 'i> Transform<'a, 'i> {
     pub fn new(
         wtxn: &mut heed::RwTxn,
@@ -95,6 +174,11 @@ impl<'a, 'i> Transform<'a, 'i> {
         let merge_function = match index_documents_method {
             IndexDocumentsMethod::ReplaceDocuments => keep_latest_obkv,
             IndexDocumentsMethod::UpdateDocuments => merge_obkvs,
+            IndexDocumentsMethod::DeepMergeDocuments(strategy) => match strategy {
+                ArrayMergeStrategy::Replace => deep_merge_obkvs_replace_arrays,
+                ArrayMergeStrategy::Append => deep_merge_obkvs_append_arrays,
+                ArrayMergeStrategy::Union => deep_merge_obkvs_union_arrays,
+            },
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -132,15 +216,25 @@ impl<'a, 'i> Transform<'a, 'i> {
             flattened_sorter,
             index_documents_method,
             replaced_documents_ids: RoaringBitmap::new(),
+            replaced_docids: FxHashMap::default(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
+            documents_versions: HashMap::new(),
+            conflicting_documents: Vec::new(),
+            generated_documents_ids: Vec::new(),
+            skippable_documents: FxHashMap::default(),
             documents_count: 0,
         })
     }
 
+    /// Reads the documents of a batch into the sorters.
+    ///
+    /// When the invalid documents of the batch must be skipped, `skippable_batch` is the position
+    /// of the batch, it is used to report the documents that are left out once merged.
     pub fn read_documents<R, FP, FA>(
         &mut self,
         reader: EnrichedDocumentsBatchReader<R>,
+        skippable_batch: Option<usize>,
         wtxn: &mut heed::RwTxn,
         progress_callback: FP,
         should_abort: FA,
@@ -156,21 +250,48 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
 
-        let primary_key = cursor.primary_key().to_string();
-        let primary_key_id =
-            self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        let primary_key = cursor.primary_key().clone();
+        // A composite primary key is never stored as a field of its own, only the ids
+        // of the documents whose primary key is a single field can be generated.
+        let primary_key_id = match &primary_key {
+            PrimaryKey::Field(name) => {
+                Some(self.fields_ids_map.insert(name).ok_or(UserError::AttributeLimitReached)?)
+            }
+            PrimaryKey::Composite(_) => None,
+        };
+        // The `_version` of a document is a precondition and is never stored in the document.
+        let version_field_id = fields_index.id("_version");
+        // The required fields can only be checked once the documents are merged.
+        let has_required_fields = self.index.schema(wtxn)?.values().any(|field| field.required);
 
         let mut obkv_buffer = Vec::new();
         let mut documents_count = 0;
         let mut docid_buffer: Vec<u8> = Vec::new();
         let mut field_buffer: Vec<(u16, Cow<[u8]>)> = Vec::new();
         while let Some(enriched_document) = cursor.next_enriched_document()? {
-            let EnrichedDocument { document, document_id } = enriched_document;
+            let EnrichedDocument { document, document_id, offset, coerced_values } =
+                enriched_document;
 
             if should_abort() {
                 return Err(Error::InternalError(InternalError::AbortedIndexation));
             }
 
+            let external_id = document_id.value();
+            let current_version = match self.documents_versions.get(external_id) {
+                Some(version) => *version,
+                None => self.index.document_version(wtxn, external_id)?,
+            };
+
+            // The document is only applied if its `_version` matches the current one.
+            if let Some(version) = version_field_id.and_then(|fid| document.get(fid)) {
+                let version: u64 =
+                    serde_json::from_slice(version).map_err(InternalError::SerdeJson)?;
+                if version != current_version {
+                    self.conflicting_documents.push(external_id.to_string());
+                    continue;
+                }
+            }
+
             // drop_and_reuse is called instead of .clear() to communicate to the compiler that field_buffer
             // does not keep references from the cursor between loop iterations
             let mut field_buffer_cache = drop_and_reuse(field_buffer);
@@ -182,16 +303,18 @@ impl<'a, 'i> Transform<'a, 'i> {
 
             // When the document id has been auto-generated by the `enrich_documents_batch`
             // we must insert this document id into the remaped document.
-            let external_id = document_id.value();
-            if document_id.is_generated() {
+            if let Some(primary_key_id) = primary_key_id.filter(|_| document_id.is_generated()) {
                 serde_json::to_writer(&mut docid_buffer, external_id)
                     .map_err(InternalError::SerdeJson)?;
                 field_buffer_cache.push((primary_key_id, Cow::from(&docid_buffer)));
+                self.generated_documents_ids.push(external_id.to_string());
             }
 
-            for (k, v) in document.iter() {
+            for (k, v) in document.iter().filter(|(k, _)| Some(*k) != version_field_id) {
                 let mapped_id =
                     *mapping.get(&k).ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
+                // The values of the fields declared in the schema are stored once coerced.
+                let v = coerced_values.as_ref().and_then(|values| values.get(k)).unwrap_or(v);
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
             }
 
@@ -247,26 +370,47 @@ impl<'a, 'i> Transform<'a, 'i> {
                     self.new_external_documents_ids_builder.remove(external_id);
                     skip_insertion = true;
                 } else {
+                    self.replaced_docids.insert(docid, original_docid);
                     // we associate the base document with the new key, everything will get merged later.
                     self.original_sorter.insert(docid.to_be_bytes(), base_obkv)?;
-                    match self.flatten_from_fields_ids_map(KvReader::new(base_obkv))? {
-                        Some(buffer) => {
-                            self.flattened_sorter.insert(docid.to_be_bytes(), &buffer)?
+                    if !self.flatten_after_merge() {
+                        match Self::flatten_from_fields_ids_map(
+                            &mut self.fields_ids_map,
+                            KvReader::new(base_obkv),
+                        )? {
+                            Some(buffer) => {
+                                self.flattened_sorter.insert(docid.to_be_bytes(), &buffer)?
+                            }
+                            None => self.flattened_sorter.insert(docid.to_be_bytes(), base_obkv)?,
                         }
-                        None => self.flattened_sorter.insert(docid.to_be_bytes(), base_obkv)?,
                     }
                 }
             }
 
             if !skip_insertion {
+                self.documents_versions.insert(external_id.to_string(), current_version + 1);
                 self.new_documents_ids.insert(docid);
+                // Whether the merged document can be left out depends on its last version.
+                if has_required_fields {
+                    match skippable_batch {
+                        Some(batch) => self.skippable_documents.insert(docid, (batch, offset)),
+                        None => self.skippable_documents.remove(&docid),
+                    };
+                }
                 // We use the extracted/generated user id as the key for this document.
                 self.original_sorter.insert(docid.to_be_bytes(), obkv_buffer.clone())?;
 
-                match self.flatten_from_fields_ids_map(KvReader::new(&obkv_buffer))? {
-                    Some(buffer) => self.flattened_sorter.insert(docid.to_be_bytes(), &buffer)?,
-                    None => {
-                        self.flattened_sorter.insert(docid.to_be_bytes(), obkv_buffer.clone())?
+                if !self.flatten_after_merge() {
+                    match Self::flatten_from_fields_ids_map(
+                        &mut self.fields_ids_map,
+                        KvReader::new(&obkv_buffer),
+                    )? {
+                        Some(buffer) => {
+                            self.flattened_sorter.insert(docid.to_be_bytes(), &buffer)?
+                        }
+                        None => self
+                            .flattened_sorter
+                            .insert(docid.to_be_bytes(), obkv_buffer.clone())?,
                     }
                 }
             }
@@ -293,9 +437,264 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_count)
     }
 
-    // Flatten a document from the fields ids map contained in self and insert the new
+    /// Runs the edition function over each of the given documents and inserts the documents
+    /// it modified in the sorters, the other documents are left untouched and won't be reindexed.
+    ///
+    /// Returns the number of edited documents.
+    pub fn edit_documents<FP, FA>(
+        &mut self,
+        wtxn: &mut heed::RwTxn,
+        documents: &RoaringBitmap,
+        engine: &rhai::Engine,
+        ast: &rhai::AST,
+        progress_callback: FP,
+        should_abort: FA,
+    ) -> Result<usize>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        if documents.is_empty() {
+            return Ok(0);
+        }
+
+        let primary_key = self.index.primary_key(wtxn)?.ok_or(Error::InternalError(
+            InternalError::DatabaseMissingEntry {
+                db_name: db_name::MAIN,
+                key: Some(main_key::PRIMARY_KEY_KEY),
+            },
+        ))?;
+        let schema = self.index.schema(wtxn)?;
+
+        let mut obkv_buffer = Vec::new();
+        let mut value_buffer = Vec::new();
+        let mut documents_seen = 0;
+        let mut documents_count = 0;
+        for docid in documents {
+            if should_abort() {
+                return Err(Error::InternalError(InternalError::AbortedIndexation));
+            }
+
+            if self.indexer_settings.log_every_n.map_or(false, |len| documents_seen % len == 0) {
+                progress_callback(UpdateIndexingStep::RemapDocumentAddition { documents_seen });
+            }
+            documents_seen += 1;
+
+            let base_obkv = self
+                .index
+                .documents
+                .remap_data_type::<heed::types::ByteSlice>()
+                .get(wtxn, &BEU32::new(docid))?
+                .ok_or(InternalError::DatabaseMissingEntry {
+                    db_name: db_name::DOCUMENTS,
+                    key: None,
+                })?;
+            let document = all_obkv_to_json(KvReader::new(base_obkv), &self.fields_ids_map)?;
+
+            let mut scope = rhai::Scope::new();
+            let doc = rhai::serde::to_dynamic(&document)
+                .map_err(UserError::DocumentEditionRuntimeError)?;
+            scope.push_dynamic("doc", doc);
+            engine
+                .run_ast_with_scope(&mut scope, ast)
+                .map_err(UserError::DocumentEditionRuntimeError)?;
+
+            let new_document = scope
+                .get_value::<rhai::Dynamic>("doc")
+                .ok_or(UserError::DocumentEditionDocumentMustBeObject)?;
+            let mut new_document: Object = rhai::serde::from_dynamic(&new_document)
+                .map_err(|_| UserError::DocumentEditionDocumentMustBeObject)?;
+
+            // the function didn't modify the document, there is nothing to reindex.
+            if new_document == document {
+                continue;
+            }
+
+            let old_external_id =
+                extract_external_document_id(&primary_key, |name| Ok(document.get(name).cloned()))?;
+            let new_external_id = extract_external_document_id(&primary_key, |name| {
+                Ok(new_document.get(name).cloned())
+            })?;
+            let external_id = match (old_external_id, new_external_id) {
+                (Some(old), Some(new)) if old == new => old,
+                _ => return Err(UserError::DocumentEditionCannotModifyPrimaryKey.into()),
+            };
+
+            if let Err(mismatch) = coerce_document_fields(&schema, &mut new_document)
+                .and_then(|()| check_required_fields(&schema, &mut new_document))
+            {
+                return Err(UserError::InvalidDocumentSchema {
+                    document_id: Value::from(external_id),
+                    mismatch,
+                }
+                .into());
+            }
+
+            // The fields of an obkv must be inserted ordered by field id.
+            let mut fields = Vec::with_capacity(new_document.len());
+            for (field, value) in &new_document {
+                let field_id =
+                    self.fields_ids_map.insert(field).ok_or(UserError::AttributeLimitReached)?;
+                fields.push((field_id, value));
+            }
+            fields.sort_unstable_by_key(|(field_id, _)| *field_id);
+
+            let mut writer = obkv::KvWriter::new(&mut obkv_buffer);
+            for (field_id, value) in fields {
+                serde_json::to_writer(&mut value_buffer, value)
+                    .map_err(InternalError::SerdeJson)?;
+                writer.insert(field_id, &value_buffer)?;
+                value_buffer.clear();
+            }
+
+            let version = match self.documents_versions.get(&external_id) {
+                Some(version) => *version,
+                None => self.index.document_version(wtxn, &external_id)?,
+            };
+            self.documents_versions.insert(external_id.clone(), version + 1);
+
+            // The edited document is inserted under a new internal id like any replaced document.
+            let new_docid =
+                self.available_documents_ids.next().ok_or(UserError::DocumentLimitReached)?;
+            self.replaced_documents_ids.insert(docid);
+            self.replaced_docids.insert(new_docid, docid);
+            self.new_documents_ids.insert(new_docid);
+            self.new_external_documents_ids_builder.insert(external_id.into(), new_docid as u64);
+
+            self.original_sorter.insert(new_docid.to_be_bytes(), &obkv_buffer)?;
+            if !self.flatten_after_merge() {
+                match Self::flatten_from_fields_ids_map(
+                    &mut self.fields_ids_map,
+                    KvReader::new(&obkv_buffer),
+                )? {
+                    Some(buffer) => {
+                        self.flattened_sorter.insert(new_docid.to_be_bytes(), &buffer)?
+                    }
+                    None => self.flattened_sorter.insert(new_docid.to_be_bytes(), &obkv_buffer)?,
+                }
+            }
+            documents_count += 1;
+            obkv_buffer.clear();
+        }
+
+        progress_callback(UpdateIndexingStep::RemapDocumentAddition { documents_seen });
+
+        self.index.put_fields_ids_map(wtxn, &self.fields_ids_map)?;
+        self.documents_count += documents_count;
+        Ok(documents_count)
+    }
+
+    /// Returns the external ids of the documents that were not applied because their
+    /// `_version` precondition didn't match the current version of the document.
+    pub fn take_conflicting_documents(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.conflicting_documents)
+    }
+
+    /// Returns the external ids that were generated for the documents missing a primary key.
+    pub fn take_generated_documents_ids(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.generated_documents_ids)
+    }
+
+    /// When deep merging documents the flattened version of the documents can't be merged
+    /// field by field, a nested object may replace a value or an array may be appended to
+    /// another one. The flattened documents are therefore computed from the merged documents.
+    fn flatten_after_merge(&self) -> bool {
+        matches!(self.index_documents_method, IndexDocumentsMethod::DeepMergeDocuments(_))
+    }
+
+    /// Returns the fields that decide whether a replaced document can be updated in place,
+    /// `None` when every field is searchable and documents must always be reindexed.
+    fn in_place_update_fields(&self, rtxn: &RoTxn) -> Result<Option<InPlaceUpdateFields>> {
+        let searchable = match self.index.user_defined_searchable_fields(rtxn)? {
+            Some(fields) => fields.into_iter().map(String::from).collect(),
+            None => return Ok(None),
+        };
+        let mut faceted = self.index.user_defined_faceted_fields(rtxn)?;
+        faceted.extend(self.index.date_fields(rtxn)?);
+        Ok(Some(InPlaceUpdateFields { searchable, faceted }))
+    }
+
+    /// Creates a sorter for the documents updated in place, they are stored under their
+    /// original docid which is never shared with another document of the update.
+    fn in_place_sorter(&self) -> grenad::Sorter<MergeFn> {
+        create_sorter(
+            grenad::SortAlgorithm::Stable,
+            keep_first,
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            self.indexer_settings.max_nb_chunks,
+            self.indexer_settings.max_memory.map(|mem| mem / 2),
+        )
+    }
+
+    /// Computes what must be reindexed when the `base` document is replaced by the `new` one,
+    /// by comparing their flattened fields one by one.
+    fn document_changes(
+        fields_ids_map: &mut FieldsIdsMap,
+        base: KvReader<FieldId>,
+        new: KvReader<FieldId>,
+        fields: &InPlaceUpdateFields,
+    ) -> Result<DocumentChanges> {
+        let base = Self::flattened_fields(fields_ids_map, base)?;
+        let new = Self::flattened_fields(fields_ids_map, new)?;
+
+        let mut facets_changed = false;
+        for field_id in base.keys().chain(new.keys()) {
+            if base.get(field_id) == new.get(field_id) {
+                continue;
+            }
+            let name = fields_ids_map.name(*field_id).ok_or(FieldIdMapMissingEntry::FieldId {
+                field_id: *field_id,
+                process: "Comparing documents in transform.",
+            })?;
+            // The geo fields are indexed in the rtree which is only updated by a full reindex.
+            if crate::is_faceted_by(name, "_geo") || crate::is_faceted(name, &fields.searchable) {
+                return Ok(DocumentChanges::Searchable);
+            }
+            facets_changed |= crate::is_faceted(name, &fields.faceted);
+        }
+
+        if !facets_changed {
+            return Ok(DocumentChanges::StoredOnly);
+        }
+
+        // The facets are reindexed from the faceted fields only, none of the words are touched.
+        let mut buffer = Vec::new();
+        let mut writer = KvWriter::new(&mut buffer);
+        for (field_id, value) in new {
+            let name = fields_ids_map.name(field_id).ok_or(FieldIdMapMissingEntry::FieldId {
+                field_id,
+                process: "Comparing documents in transform.",
+            })?;
+            if crate::is_faceted(name, &fields.faceted) {
+                writer.insert(field_id, value)?;
+            }
+        }
+        writer.finish()?;
+        Ok(DocumentChanges::Facets(buffer))
+    }
+
+    /// Returns the fields of the flattened version of the document, ordered by field id.
+    fn flattened_fields(
+        fields_ids_map: &mut FieldsIdsMap,
+        obkv: KvReader<FieldId>,
+    ) -> Result<BTreeMap<FieldId, Vec<u8>>> {
+        let fields = match Self::flatten_from_fields_ids_map(fields_ids_map, obkv)? {
+            Some(buffer) => KvReader::<FieldId>::new(&buffer)
+                .iter()
+                .map(|(field_id, value)| (field_id, value.to_vec()))
+                .collect(),
+            None => obkv.iter().map(|(field_id, value)| (field_id, value.to_vec())).collect(),
+        };
+        Ok(fields)
+    }
+
+    // Flatten a document from the given fields ids map and insert the new
     // created fields. Returns `None` if the document doesn't need to be flattened.
-    fn flatten_from_fields_ids_map(&mut self, obkv: KvReader<FieldId>) -> Result<Option<Vec<u8>>> {
+    fn flatten_from_fields_ids_map(
+        fields_ids_map: &mut FieldsIdsMap,
+        obkv: KvReader<FieldId>,
+    ) -> Result<Option<Vec<u8>>> {
         if obkv
             .iter()
             .all(|(_, value)| !json_depth_checker::should_flatten_from_unchecked_slice(value))
@@ -316,7 +715,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // all the raw values get inserted directly in the `key_value` vec.
         for (key, value) in obkv.iter() {
             if json_depth_checker::should_flatten_from_unchecked_slice(value) {
-                let key = self.fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
+                let key = fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
                     field_id: key,
                     process: "Flatten from fields ids map.",
                 })?;
@@ -334,7 +733,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // Once we have the flattened version we insert all the new generated fields_ids
         // (if any) in the fields ids map and serialize the value.
         for (key, value) in flattened.into_iter() {
-            let fid = self.fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
+            let fid = fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
             let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
             key_value.push((fid, value.into()));
         }
@@ -449,21 +848,19 @@ impl<'a, 'i> Transform<'a, 'i> {
     /// format like CSV, JSON or JSON stream. This sorter must contain a key that is the document
     /// id for the user side and the value must be an obkv where keys are valid fields ids.
     pub(crate) fn output_from_sorter<F>(
-        self,
+        mut self,
         wtxn: &mut heed::RwTxn,
         progress_callback: F,
     ) -> Result<TransformOutput>
     where
         F: Fn(UpdateIndexingStep) + Sync,
     {
-        let primary_key = self
-            .index
-            .primary_key(wtxn)?
-            .ok_or(Error::InternalError(InternalError::DatabaseMissingEntry {
+        let primary_key = self.index.primary_key(wtxn)?.ok_or(Error::InternalError(
+            InternalError::DatabaseMissingEntry {
                 db_name: db_name::MAIN,
                 key: Some(main_key::PRIMARY_KEY_KEY),
-            }))?
-            .to_string();
+            },
+        ))?;
 
         let mut external_documents_ids = self.index.external_documents_ids(wtxn)?;
 
@@ -481,6 +878,34 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         self.remove_deleted_documents_from_field_distribution(wtxn, &mut field_distribution)?;
 
+        // When the flattened documents can only be computed once the documents are merged
+        // we write them in this writer at the same time we write the original documents.
+        let mut flattened_writer = if self.flatten_after_merge() {
+            Some(create_writer(
+                self.indexer_settings.chunk_compression_type,
+                self.indexer_settings.chunk_compression_level,
+                tempfile::tempfile()?,
+            ))
+        } else {
+            None
+        };
+
+        // The replaced documents in which only non-searchable fields changed are updated in place,
+        // under their original docid, so that only their facets are reindexed, if any.
+        let in_place_fields = self.in_place_update_fields(wtxn)?;
+        let mut in_place_original_sorter = self.in_place_sorter();
+        let mut in_place_flattened_sorter = self.in_place_sorter();
+        let mut abandoned_documents_ids = RoaringBitmap::new();
+        let mut updated_facets_documents_ids = RoaringBitmap::new();
+
+        let expires_at_field_id = self.fields_ids_map.id("_expiresAt");
+        let mut documents_expirations = Vec::new();
+        let mut outdated_documents_expirations = Vec::new();
+
+        let schema = self.index.schema(wtxn)?;
+        let has_required_fields = schema.values().any(|field| field.required);
+        let mut invalid_documents = Vec::new();
+
         // Here we are going to do the document count + field distribution + `write_into_stream_writer`
         let mut iter = self.original_sorter.into_stream_merger_iter()?;
         // used only for the callback
@@ -494,9 +919,81 @@ impl<'a, 'i> Transform<'a, 'i> {
                 total_documents: self.documents_count,
             });
 
-            // We increment all the field of the current document in the field distribution.
             let obkv = KvReader::new(val);
+            let docid = key
+                .try_into()
+                .map(u32::from_be_bytes)
+                .map_err(|_| SerializationError::InvalidNumberSerialization)?;
+
+            // The required fields are checked on the merged documents,
+            // a partial update doesn't have to repeat the fields already stored.
+            if has_required_fields {
+                let mut object = Object::new();
+                for (field_id, value) in obkv.iter() {
+                    let name = self.fields_ids_map.name(field_id).ok_or(
+                        FieldIdMapMissingEntry::FieldId {
+                            field_id,
+                            process: "Checking the required fields in transform.",
+                        },
+                    )?;
+                    if schema_covers_field(&schema, name) {
+                        let value =
+                            serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                        object.insert(name.to_string(), value);
+                    }
+                }
+                if let Err(mismatch) = check_required_fields(&schema, &mut object) {
+                    let fields_ids_map = &self.fields_ids_map;
+                    let external_id = extract_external_document_id(&primary_key, |name| {
+                        match fields_ids_map.id(name).and_then(|fid| obkv.get(fid)) {
+                            Some(value) => Ok(Some(
+                                serde_json::from_slice(value).map_err(InternalError::SerdeJson)?,
+                            )),
+                            None => Ok(None),
+                        }
+                    })?;
+                    let error = UserError::InvalidDocumentSchema {
+                        document_id: external_id.clone().map_or(Value::Null, Value::from),
+                        mismatch,
+                    };
+                    let (batch, offset) = match self.skippable_documents.get(&docid) {
+                        Some(&origin) => origin,
+                        None => return Err(error.into()),
+                    };
+
+                    // The document is left out, the one it was replacing is kept as it is.
+                    self.new_documents_ids.remove(docid);
+                    abandoned_documents_ids.insert(docid);
+                    if let Some(external_id) = external_id.as_ref() {
+                        self.documents_versions.remove(external_id);
+                    }
+                    if let Some(&original_docid) = self.replaced_docids.get(&docid) {
+                        self.replaced_documents_ids.remove(original_docid);
+                        let base_obkv =
+                            self.index.documents.get(wtxn, &BEU32::new(original_docid))?.ok_or(
+                                InternalError::DatabaseMissingEntry {
+                                    db_name: db_name::DOCUMENTS,
+                                    key: None,
+                                },
+                            )?;
+                        for (key, _) in base_obkv.iter() {
+                            let name = self.fields_ids_map.name(key).ok_or(
+                                FieldIdMapMissingEntry::FieldId {
+                                    field_id: key,
+                                    process: "Computing field distribution in transform.",
+                                },
+                            )?;
+                            *field_distribution.entry(name.to_string()).or_insert(0) += 1;
+                        }
+                    }
 
+                    let document_id = external_id;
+                    invalid_documents.push((batch, InvalidDocument { offset, document_id, error }));
+                    continue;
+                }
+            }
+
+            // We increment all the field of the current document in the field distribution.
             for (key, _) in obkv.iter() {
                 let name =
                     self.fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
@@ -505,10 +1002,72 @@ impl<'a, 'i> Transform<'a, 'i> {
                     })?;
                 *field_distribution.entry(name.to_string()).or_insert(0) += 1;
             }
+
+            let expiration = obkv_document_expiration(expires_at_field_id, obkv)?;
+
+            if let Some(in_place_fields) = in_place_fields.as_ref() {
+                if let Some(&original_docid) = self.replaced_docids.get(&docid) {
+                    let base_obkv = self
+                        .index
+                        .documents
+                        .remap_data_type::<heed::types::ByteSlice>()
+                        .get(wtxn, &BEU32::new(original_docid))?
+                        .ok_or(InternalError::DatabaseMissingEntry {
+                            db_name: db_name::DOCUMENTS,
+                            key: None,
+                        })?;
+
+                    match Self::document_changes(
+                        &mut self.fields_ids_map,
+                        KvReader::new(base_obkv),
+                        KvReader::new(val),
+                        in_place_fields,
+                    )? {
+                        DocumentChanges::Searchable => (),
+                        changes => {
+                            let original_key = original_docid.to_be_bytes();
+                            in_place_original_sorter.insert(original_key, val)?;
+                            if let DocumentChanges::Facets(facets) = changes {
+                                in_place_flattened_sorter.insert(original_key, facets)?;
+                                updated_facets_documents_ids.insert(original_docid);
+                            }
+                            let base_expiration = obkv_document_expiration(
+                                expires_at_field_id,
+                                KvReader::new(base_obkv),
+                            )?;
+                            outdated_documents_expirations
+                                .extend(base_expiration.map(|date| (original_docid, date)));
+                            documents_expirations
+                                .extend(expiration.map(|date| (original_docid, date)));
+                            // The original document is kept and the new docid is never used.
+                            self.replaced_documents_ids.remove(original_docid);
+                            self.new_documents_ids.remove(docid);
+                            abandoned_documents_ids.insert(docid);
+                            continue;
+                        }
+                    }
+                }
+            }
+
             writer.insert(key, val)?;
+            documents_expirations.extend(expiration.map(|date| (docid, date)));
+
+            if let Some(flattened_writer) = flattened_writer.as_mut() {
+                match Self::flatten_from_fields_ids_map(&mut self.fields_ids_map, obkv)? {
+                    Some(buffer) => flattened_writer.insert(key, &buffer)?,
+                    None => flattened_writer.insert(key, val)?,
+                }
+            }
         }
 
         let mut original_documents = writer.into_inner()?;
+        if !abandoned_documents_ids.is_empty() {
+            original_documents = merge_in_place_documents(
+                self.indexer_settings,
+                original_documents,
+                in_place_original_sorter,
+            )?;
+        }
         // We then extract the file and reset the seek to be able to read it again.
         original_documents.rewind()?;
 
@@ -518,14 +1077,40 @@ impl<'a, 'i> Transform<'a, 'i> {
             self.indexer_settings.chunk_compression_level,
             tempfile::tempfile()?,
         );
-        // Once we have written all the documents into the final sorter, we write the documents
-        // into this writer, extract the file and reset the seek to be able to read it again.
-        self.flattened_sorter.write_into_stream_writer(&mut writer)?;
-        let mut flattened_documents = writer.into_inner()?;
+        let mut flattened_documents = match flattened_writer {
+            Some(flattened_writer) => flattened_writer.into_inner()?,
+            None => {
+                // Once we have written all the documents into the final sorter, we write the
+                // documents into this writer, extract the file and reset the seek to be able
+                // to read it again. The documents updated in place are skipped.
+                let mut iter = self.flattened_sorter.into_stream_merger_iter()?;
+                while let Some((key, val)) = iter.next()? {
+                    let docid = key
+                        .try_into()
+                        .map(u32::from_be_bytes)
+                        .map_err(|_| SerializationError::InvalidNumberSerialization)?;
+                    if !abandoned_documents_ids.contains(docid) {
+                        writer.insert(key, val)?;
+                    }
+                }
+                writer.into_inner()?
+            }
+        };
+        if !updated_facets_documents_ids.is_empty() {
+            flattened_documents = merge_in_place_documents(
+                self.indexer_settings,
+                flattened_documents,
+                in_place_flattened_sorter,
+            )?;
+        }
         flattened_documents.rewind()?;
 
-        let mut new_external_documents_ids_builder: Vec<_> =
-            self.new_external_documents_ids_builder.into_iter().collect();
+        // The external ids of the documents updated in place still point to their original docid.
+        let mut new_external_documents_ids_builder: Vec<_> = self
+            .new_external_documents_ids_builder
+            .into_iter()
+            .filter(|(_, docid)| !abandoned_documents_ids.contains(*docid as u32))
+            .collect();
 
         new_external_documents_ids_builder
             .sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
@@ -543,7 +1128,13 @@ impl<'a, 'i> Transform<'a, 'i> {
             external_documents_ids: external_documents_ids.into_static(),
             new_documents_ids: self.new_documents_ids,
             replaced_documents_ids: self.replaced_documents_ids,
+            updated_facets_documents_ids,
+            facets_only: false,
+            documents_versions: self.documents_versions,
+            documents_expirations,
+            outdated_documents_expirations,
             documents_count: self.documents_count,
+            invalid_documents,
             original_documents,
             flattened_documents,
         })
@@ -560,14 +1151,11 @@ impl<'a, 'i> Transform<'a, 'i> {
         mut new_fields_ids_map: FieldsIdsMap,
     ) -> Result<TransformOutput> {
         // There already has been a document addition, the primary key should be set by now.
-        let primary_key = self
-            .index
-            .primary_key(wtxn)?
-            .ok_or(InternalError::DatabaseMissingEntry {
+        let primary_key =
+            self.index.primary_key(wtxn)?.ok_or(InternalError::DatabaseMissingEntry {
                 db_name: db_name::MAIN,
                 key: Some(main_key::PRIMARY_KEY_KEY),
-            })?
-            .to_string();
+            })?;
         let field_distribution = self.index.field_distribution(wtxn)?;
 
         // Delete the soft deleted document ids from the maps inside the external_document_ids structure
@@ -580,6 +1168,13 @@ impl<'a, 'i> Transform<'a, 'i> {
         let documents_ids = self.index.documents_ids(wtxn)?;
         let documents_count = documents_ids.len() as usize;
 
+        // The documents keep their docids, their expiration dates are written back the same way.
+        let mut documents_expirations = Vec::new();
+        for result in self.index.documents_expiration.iter(wtxn)? {
+            let (date, docids) = result?;
+            documents_expirations.extend(docids.into_iter().map(|docid| (docid, date.get())));
+        }
+
         // We create a final writer to write the new documents in order from the sorter.
         let mut original_writer = create_writer(
             self.indexer_settings.chunk_compression_type,
@@ -594,7 +1189,9 @@ impl<'a, 'i> Transform<'a, 'i> {
             tempfile::tempfile()?,
         );
 
+        let schema = self.index.schema(wtxn)?;
         let mut obkv_buffer = Vec::new();
+        let mut coerced_buffer = Vec::new();
         for result in self.index.all_documents(wtxn)? {
             let (docid, obkv) = result?;
 
@@ -609,7 +1206,6 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
 
             let buffer = obkv_writer.into_inner()?;
-            original_writer.insert(docid.to_be_bytes(), &buffer)?;
 
             // Once we have the document. We're going to flatten it
             // and insert it in the flattened sorter.
@@ -626,6 +1222,40 @@ impl<'a, 'i> Transform<'a, 'i> {
                 doc.insert(key.to_string(), value);
             }
 
+            if schema.is_empty() {
+                original_writer.insert(docid.to_be_bytes(), &buffer)?;
+            } else {
+                // The schema may have changed since the documents were added, they must
+                // still match it and their values are stored once coerced.
+                if let Err(mismatch) = coerce_document_fields(&schema, &mut doc)
+                    .and_then(|()| check_required_fields(&schema, &mut doc))
+                {
+                    let document_id = extract_external_document_id(&primary_key, |name| {
+                        Ok(doc.get(name).cloned())
+                    })?;
+                    return Err(UserError::InvalidDocumentSchema {
+                        document_id: document_id.map_or(Value::Null, Value::from),
+                        mismatch,
+                    }
+                    .into());
+                }
+
+                coerced_buffer.clear();
+                let mut writer = KvWriter::new(&mut coerced_buffer);
+                for (k, v) in reader.iter() {
+                    match new_fields_ids_map.name(k) {
+                        Some(name) if schema_covers_field(&schema, name) => {
+                            let value =
+                                serde_json::to_vec(&doc[name]).map_err(InternalError::SerdeJson)?;
+                            writer.insert(k, value)?;
+                        }
+                        _ => writer.insert(k, v)?,
+                    }
+                }
+                writer.finish()?;
+                original_writer.insert(docid.to_be_bytes(), &coerced_buffer)?;
+            }
+
             let flattened = flatten_serde_json::flatten(&doc);
 
             // Once we have the flattened version we can convert it back to obkv and
@@ -662,7 +1292,14 @@ impl<'a, 'i> Transform<'a, 'i> {
             external_documents_ids: new_external_documents_ids.into_static(),
             new_documents_ids: documents_ids,
             replaced_documents_ids: RoaringBitmap::default(),
+            updated_facets_documents_ids: RoaringBitmap::default(),
+            facets_only: false,
+            // The versions are not cleared with the documents, reindexing doesn't change them.
+            documents_versions: HashMap::new(),
+            documents_expirations,
+            outdated_documents_expirations: Vec::new(),
             documents_count,
+            invalid_documents: Vec::new(),
             original_documents,
             flattened_documents,
         };
@@ -675,6 +1312,85 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         Ok(output)
     }
+
+    /// Returns a `TransformOutput` with a file that contains the flattened documents of the index
+    /// reduced to the given fields, so that only the facets of these fields are reindexed.
+    pub fn prepare_for_facets_reindexing(
+        mut self,
+        wtxn: &mut heed::RwTxn<'i, '_>,
+        fields_ids: &HashSet<FieldId>,
+    ) -> Result<TransformOutput> {
+        // There already has been a document addition, the primary key should be set by now.
+        let primary_key =
+            self.index.primary_key(wtxn)?.ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::MAIN,
+                key: Some(main_key::PRIMARY_KEY_KEY),
+            })?;
+        let field_distribution = self.index.field_distribution(wtxn)?;
+        let external_documents_ids = self.index.external_documents_ids(wtxn)?;
+        let documents_count = self.index.number_of_documents(wtxn)? as usize;
+
+        // The documents themselves are left untouched.
+        let original_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let mut obkv_buffer = Vec::new();
+        for result in self.index.all_documents(wtxn)? {
+            let (docid, obkv) = result?;
+            let flattened = Self::flattened_fields(&mut self.fields_ids_map, obkv)?;
+            if !flattened.keys().any(|field_id| fields_ids.contains(field_id)) {
+                continue;
+            }
+
+            obkv_buffer.clear();
+            let mut writer = KvWriter::<_, FieldId>::new(&mut obkv_buffer);
+            for (field_id, value) in flattened {
+                if fields_ids.contains(&field_id) {
+                    writer.insert(field_id, value)?;
+                }
+            }
+            writer.finish()?;
+            flattened_writer.insert(docid.to_be_bytes(), &obkv_buffer)?;
+        }
+
+        let mut original_documents = original_writer.into_inner()?;
+        original_documents.rewind()?;
+
+        let mut flattened_documents = flattened_writer.into_inner()?;
+        flattened_documents.rewind()?;
+
+        let output = TransformOutput {
+            primary_key,
+            fields_ids_map: self.fields_ids_map,
+            field_distribution,
+            external_documents_ids: external_documents_ids.into_static(),
+            new_documents_ids: RoaringBitmap::default(),
+            replaced_documents_ids: RoaringBitmap::default(),
+            updated_facets_documents_ids: RoaringBitmap::default(),
+            facets_only: true,
+            documents_versions: HashMap::new(),
+            documents_expirations: Vec::new(),
+            outdated_documents_expirations: Vec::new(),
+            documents_count,
+            invalid_documents: Vec::new(),
+            original_documents,
+            flattened_documents,
+        };
+
+        let new_facets = output.compute_real_facets(wtxn, self.index)?;
+        self.index.put_faceted_fields(wtxn, &new_facets)?;
+
+        Ok(output)
+    }
 }
 
 /// Drops all the value of type `U` in vec, and reuses the allocation to create a `Vec<T>`.
@@ -4,7 +4,9 @@ pub use self::delete_documents::{DeleteDocuments, DeletionStrategy, DocumentDele
 pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
 pub use self::index_documents::{
+    extract_external_document_id, validate_document_edition_function, ArrayMergeStrategy,
     DocumentAdditionResult, DocumentId, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod,
+    InvalidDocument,
 };
 pub use self::indexer_config::IndexerConfig;
 pub use self::prefix_word_pairs::{
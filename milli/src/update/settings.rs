@@ -1,9 +1,12 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::result::Result as StdResult;
 
-use charabia::{Tokenizer, TokenizerBuilder};
+use charabia::Tokenizer;
 use deserr::{DeserializeError, DeserializeFromValue};
+use fst::IntoStreamer;
+use heed::types::{ByteSlice, DecodeIgnore};
 use itertools::Itertools;
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
@@ -11,10 +14,16 @@ use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
-use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+use crate::facet::FacetType;
+use crate::index::{
+    MinWordLenForTypo, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
+};
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, Result};
+use crate::{
+    CurationRule, FieldId, FieldsIdsMap, Filter, Index, LocalizedAttributesRule, PrimaryKey,
+    Result, Schema, StemmingLanguage, BEU16,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -113,19 +122,38 @@ pub struct Settings<'a, 't, 'u, 'i> {
     displayed_fields: Setting<Vec<String>>,
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
+    /// Attributes whose string values are parsed as RFC 3339 or ISO 8601 dates.
+    date_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    /// Tokens that must be considered as separators in addition to the default ones.
+    separator_tokens: Setting<BTreeSet<String>>,
+    /// Default separators that must not be considered as separators.
+    non_separator_tokens: Setting<BTreeSet<String>>,
+    /// Words that must never be split by the tokenizer.
+    dictionary: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
     synonyms: Setting<HashMap<String, Vec<String>>>,
-    primary_key: Setting<String>,
+    primary_key: Setting<PrimaryKey>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
     min_word_len_one_typo: Setting<u8>,
+    /// Minimum word lengths for typos overriding the index-wide ones on some attributes.
+    attributes_min_word_len_for_typo: Setting<BTreeMap<String, MinWordLenForTypo>>,
+    /// Whether the words made of digits only must be matched without typos.
+    disable_typos_on_numbers: Setting<bool>,
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
     pagination_max_total_hits: Setting<usize>,
+    curation_rules: Setting<Vec<CurationRule>>,
+    /// Rules forcing the languages used to tokenize the matching attributes.
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    /// Languages in which the words are also indexed and searched by their stem.
+    stemming_languages: Setting<Vec<StemmingLanguage>>,
+    /// The declared fields the added documents are validated against.
+    schema: Setting<Schema>,
 }
 
 impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
@@ -141,8 +169,12 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             displayed_fields: Setting::NotSet,
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
+            date_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
             primary_key: Setting::NotSet,
@@ -150,9 +182,15 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             exact_words: Setting::NotSet,
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
+            attributes_min_word_len_for_typo: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
             exact_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
+            curation_rules: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            stemming_languages: Setting::NotSet,
+            schema: Setting::NotSet,
             indexer_config,
         }
     }
@@ -189,6 +227,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.sortable_fields = Setting::Reset;
     }
 
+    pub fn set_date_fields(&mut self, names: HashSet<String>) {
+        self.date_fields = Setting::Set(names);
+    }
+
+    pub fn reset_date_fields(&mut self) {
+        self.date_fields = Setting::Reset;
+    }
+
     pub fn reset_criteria(&mut self) {
         self.criteria = Setting::Reset;
     }
@@ -206,6 +252,39 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
     }
 
+    pub fn set_separator_tokens(&mut self, separator_tokens: BTreeSet<String>) {
+        self.separator_tokens = if separator_tokens.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(separator_tokens)
+        }
+    }
+
+    pub fn reset_separator_tokens(&mut self) {
+        self.separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_non_separator_tokens(&mut self, non_separator_tokens: BTreeSet<String>) {
+        self.non_separator_tokens = if non_separator_tokens.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(non_separator_tokens)
+        }
+    }
+
+    pub fn reset_non_separator_tokens(&mut self) {
+        self.non_separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_dictionary(&mut self, dictionary: BTreeSet<String>) {
+        self.dictionary =
+            if dictionary.is_empty() { Setting::Reset } else { Setting::Set(dictionary) }
+    }
+
+    pub fn reset_dictionary(&mut self) {
+        self.dictionary = Setting::Reset;
+    }
+
     pub fn reset_distinct_field(&mut self) {
         self.distinct_field = Setting::Reset;
     }
@@ -226,8 +305,8 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.primary_key = Setting::Reset;
     }
 
-    pub fn set_primary_key(&mut self, primary_key: String) {
-        self.primary_key = Setting::Set(primary_key);
+    pub fn set_primary_key(&mut self, primary_key: impl Into<PrimaryKey>) {
+        self.primary_key = Setting::Set(primary_key.into());
     }
 
     pub fn set_autorize_typos(&mut self, val: bool) {
@@ -262,6 +341,25 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.exact_words = Setting::Reset;
     }
 
+    pub fn set_attributes_min_word_len_for_typo(
+        &mut self,
+        attributes: BTreeMap<String, MinWordLenForTypo>,
+    ) {
+        self.attributes_min_word_len_for_typo = Setting::Set(attributes);
+    }
+
+    pub fn reset_attributes_min_word_len_for_typo(&mut self) {
+        self.attributes_min_word_len_for_typo = Setting::Reset;
+    }
+
+    pub fn set_disable_typos_on_numbers(&mut self, val: bool) {
+        self.disable_typos_on_numbers = Setting::Set(val);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
     pub fn set_exact_attributes(&mut self, attrs: HashSet<String>) {
         self.exact_attributes = Setting::Set(attrs);
     }
@@ -286,6 +384,39 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.pagination_max_total_hits = Setting::Reset;
     }
 
+    pub fn set_curation_rules(&mut self, rules: Vec<CurationRule>) {
+        self.curation_rules = Setting::Set(rules);
+    }
+
+    pub fn reset_curation_rules(&mut self) {
+        self.curation_rules = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, rules: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(rules);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_stemming_languages(&mut self, languages: Vec<StemmingLanguage>) {
+        self.stemming_languages =
+            if languages.is_empty() { Setting::Reset } else { Setting::Set(languages) }
+    }
+
+    pub fn reset_stemming_languages(&mut self) {
+        self.stemming_languages = Setting::Reset;
+    }
+
+    pub fn set_schema(&mut self, schema: Schema) {
+        self.schema = if schema.is_empty() { Setting::Reset } else { Setting::Set(schema) }
+    }
+
+    pub fn reset_schema(&mut self) {
+        self.schema = Setting::Reset;
+    }
+
     fn reindex<FP, FA>(
         &mut self,
         progress_callback: &FP,
@@ -333,6 +464,65 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    /// Only reindexes the facets of the given fields: their facet values are removed and extracted
+    /// again according to the new settings, the words and the other facets are left untouched.
+    fn reindex_facets<FP, FA>(
+        &mut self,
+        progress_callback: &FP,
+        should_abort: &FA,
+        updated_fields: &HashSet<String>,
+    ) -> Result<()>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        // if the settings are set before any document update, the facets will be extracted
+        // during the first document addition.
+        if self.index.number_of_documents(self.wtxn)? == 0 {
+            return Ok(());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+        let mut fields_ids: HashSet<FieldId> = fields_ids_map
+            .iter()
+            .filter(|(_, name)| crate::is_faceted(name, updated_fields))
+            .map(|(field_id, _)| field_id)
+            .collect();
+        remove_facets_of_fields(self.wtxn, self.index, &fields_ids)?;
+
+        if updated_fields.contains("_geo") {
+            self.index.delete_geo_rtree(self.wtxn)?;
+            self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+            // the primary key is needed to report the documents with invalid geo points.
+            if let Some(primary_key) = self.index.primary_key(self.wtxn)? {
+                let primary_key_ids =
+                    primary_key.fields().iter().filter_map(|name| fields_ids_map.id(name));
+                fields_ids.extend(primary_key_ids);
+            }
+        }
+
+        let transform = Transform::new(
+            self.wtxn,
+            self.index,
+            self.indexer_config,
+            IndexDocumentsMethod::ReplaceDocuments,
+            false,
+        )?;
+        let output = transform.prepare_for_facets_reindexing(self.wtxn, &fields_ids)?;
+
+        let indexing_builder = IndexDocuments::new(
+            self.wtxn,
+            self.index,
+            self.indexer_config,
+            IndexDocumentsConfig::default(),
+            &progress_callback,
+            &should_abort,
+        )?;
+        indexing_builder.execute_raw(output)?;
+
+        Ok(())
+    }
+
     fn update_displayed(&mut self) -> Result<bool> {
         match self.displayed_fields {
             Setting::Set(ref fields) => {
@@ -436,10 +626,58 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_separator_tokens(&mut self) -> Result<bool> {
+        match self.separator_tokens {
+            Setting::Set(ref tokens) => {
+                let current = self.index.separator_tokens(self.wtxn)?;
+                if current != Some(tokens.iter().map(String::as_str).collect()) {
+                    self.index.put_separator_tokens(self.wtxn, tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_non_separator_tokens(&mut self) -> Result<bool> {
+        match self.non_separator_tokens {
+            Setting::Set(ref tokens) => {
+                let current = self.index.non_separator_tokens(self.wtxn)?;
+                if current != Some(tokens.iter().map(String::as_str).collect()) {
+                    self.index.put_non_separator_tokens(self.wtxn, tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_non_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_dictionary(&mut self) -> Result<bool> {
+        match self.dictionary {
+            Setting::Set(ref words) => {
+                let current = self.index.dictionary(self.wtxn)?;
+                if current != Some(words.iter().map(String::as_str).collect()) {
+                    self.index.put_dictionary(self.wtxn, words)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_dictionary(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_synonyms(&mut self) -> Result<bool> {
         match self.synonyms {
             Setting::Set(ref synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> Vec<String> {
+                fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
                     tokenizer
                         .tokenize(text)
                         .filter_map(|token| {
@@ -452,7 +690,9 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                         .collect::<Vec<_>>()
                 }
 
-                let mut builder = TokenizerBuilder::new();
+                // the words are normalized the way the documents are tokenized
+                let tokenizer_words = self.index.tokenizer_words(self.wtxn)?;
+                let mut builder = tokenizer_words.tokenizer_builder();
                 let stop_words = self.index.stop_words(self.wtxn)?;
                 if let Some(ref stop_words) = stop_words {
                     builder.stop_words(stop_words);
@@ -492,6 +732,33 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    /// Sets the exact words and the synonyms that are not updated to the stored ones,
+    /// they were normalized with the previous separators and dictionary and must be normalized again.
+    fn reload_exact_words_and_synonyms(&mut self) -> Result<()> {
+        if self.exact_words.is_not_set() {
+            if let Some(words) = self.index.exact_words(self.wtxn)? {
+                let words = words.into_stream().into_strs()?;
+                self.exact_words = Setting::Set(words.into_iter().collect());
+            }
+        }
+
+        if self.synonyms.is_not_set() {
+            let synonyms = self.index.synonyms(self.wtxn)?;
+            if !synonyms.is_empty() {
+                // the stored words were split on their separators, we put spaces between them
+                let synonyms = synonyms
+                    .into_iter()
+                    .map(|(word, synonyms)| {
+                        (word.join(" "), synonyms.iter().map(|synonym| synonym.join(" ")).collect())
+                    })
+                    .collect();
+                self.synonyms = Setting::Set(synonyms);
+            }
+        }
+
+        Ok(())
+    }
+
     fn update_exact_attributes(&mut self) -> Result<bool> {
         match self.exact_attributes {
             Setting::Set(ref attrs) => {
@@ -545,6 +812,54 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_date_fields(&mut self) -> Result<bool> {
+        match self.date_fields {
+            Setting::Set(ref fields) => {
+                let old_fields = self.index.date_fields(self.wtxn)?;
+                if fields != &old_fields {
+                    self.index.put_date_fields(self.wtxn, fields)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_date_fields(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                let old_rules = self.index.localized_attributes_rules(self.wtxn)?;
+                if rules != &old_rules {
+                    self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_stemming_languages(&mut self) -> Result<bool> {
+        match self.stemming_languages {
+            Setting::Set(ref languages) => {
+                let current = self.index.stemming_languages(self.wtxn)?;
+                if languages != &current {
+                    self.index.put_stemming_languages(self.wtxn, languages)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_stemming_languages(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
@@ -562,9 +877,12 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         match self.primary_key {
             Setting::Set(ref primary_key) => {
                 if self.index.number_of_documents(self.wtxn)? == 0 {
-                    let mut fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
-                    fields_ids_map.insert(primary_key).ok_or(UserError::AttributeLimitReached)?;
-                    self.index.put_fields_ids_map(self.wtxn, &fields_ids_map)?;
+                    // A composite primary key is never stored as a field of its own.
+                    if let PrimaryKey::Field(name) = primary_key {
+                        let mut fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+                        fields_ids_map.insert(name).ok_or(UserError::AttributeLimitReached)?;
+                        self.index.put_fields_ids_map(self.wtxn, &fields_ids_map)?;
+                    }
                     self.index.put_primary_key(self.wtxn, primary_key)?;
                     Ok(())
                 } else {
@@ -633,14 +951,50 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_attributes_min_word_len_for_typo(&mut self) -> Result<()> {
+        match self.attributes_min_word_len_for_typo {
+            Setting::Set(ref attributes) => {
+                let one = self.index.min_word_len_one_typo(self.wtxn)?;
+                let two = self.index.min_word_len_two_typos(self.wtxn)?;
+                for len in attributes.values() {
+                    let (one, two) = (len.one_typo.unwrap_or(one), len.two_typos.unwrap_or(two));
+                    if one > two {
+                        return Err(UserError::InvalidMinTypoWordLenSetting(one, two).into());
+                    }
+                }
+                self.index.put_attributes_min_word_len_for_typo(self.wtxn, attributes)?;
+            }
+            Setting::Reset => {
+                self.index.delete_attributes_min_word_len_for_typo(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => self.index.put_disable_typos_on_numbers(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_disable_typos_on_numbers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_exact_words(&mut self) -> Result<()> {
         match self.exact_words {
             Setting::Set(ref mut words) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> String {
+                fn normalize(tokenizer: &Tokenizer, text: &str) -> String {
                     tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
                 }
 
-                let mut builder = TokenizerBuilder::new();
+                // the words are normalized the way the documents are tokenized
+                let tokenizer_words = self.index.tokenizer_words(self.wtxn)?;
+                let mut builder = tokenizer_words.tokenizer_builder();
                 let stop_words = self.index.stop_words(self.wtxn)?;
                 if let Some(ref stop_words) = stop_words {
                     builder.stop_words(stop_words);
@@ -693,6 +1047,64 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    /// Returns `true` if the existing documents must be checked against the new schema.
+    fn update_schema(&mut self) -> Result<bool> {
+        match self.schema {
+            Setting::Set(ref schema) => {
+                let primary_key = self.index.primary_key(self.wtxn)?;
+                for name in schema.keys() {
+                    if let Some(reason) = invalid_schema_field_reason(name, primary_key.as_ref()) {
+                        return Err(UserError::InvalidSchemaField {
+                            field: name.to_string(),
+                            reason,
+                        }
+                        .into());
+                    }
+                }
+
+                let old_schema = self.index.schema(self.wtxn)?;
+                self.index.put_schema(self.wtxn, schema)?;
+                Ok(&old_schema != schema)
+            }
+            Setting::Reset => {
+                // The documents keep the values they were coerced to.
+                self.index.delete_schema(self.wtxn)?;
+                Ok(false)
+            }
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_curation_rules(&mut self) -> Result<()> {
+        match self.curation_rules {
+            Setting::Set(ref rules) => {
+                self.check_boost_filters(rules)?;
+                self.index.put_curation_rules(self.wtxn, rules)?;
+            }
+            Setting::Reset => {
+                self.index.delete_curation_rules(self.wtxn)?;
+            }
+            // the stored boost filters must still only use filterable attributes
+            Setting::NotSet if !self.filterable_fields.is_not_set() => {
+                let rules = self.index.curation_rules(self.wtxn)?;
+                self.check_boost_filters(&rules)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure the boost filters of the rules are valid and only use filterable attributes.
+    fn check_boost_filters(&self, rules: &[CurationRule]) -> Result<()> {
+        for boost in rules.iter().filter_map(|rule| rule.boost.as_deref()) {
+            if let Some(filter) = Filter::from_str(boost)? {
+                filter.evaluate(&*self.wtxn, self.index)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -701,6 +1113,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
 
         let old_faceted_fields = self.index.user_defined_faceted_fields(self.wtxn)?;
+        let old_date_fields = self.index.date_fields(self.wtxn)?;
         let old_fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
 
         self.update_displayed()?;
@@ -711,9 +1124,12 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
-        self.update_exact_words()?;
+        self.update_attributes_min_word_len_for_typo()?;
+        self.update_disable_typos_on_numbers()?;
         self.update_max_values_per_facet()?;
         self.update_pagination_max_total_hits()?;
+        self.update_curation_rules()?;
+        let schema_updated = self.update_schema()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
@@ -722,23 +1138,101 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
         let stop_words_updated = self.update_stop_words()?;
+        let separator_tokens_updated = self.update_separator_tokens()?;
+        let non_separator_tokens_updated = self.update_non_separator_tokens()?;
+        let dictionary_updated = self.update_dictionary()?;
+        if separator_tokens_updated || non_separator_tokens_updated || dictionary_updated {
+            self.reload_exact_words_and_synonyms()?;
+        }
+        // the exact words and synonyms are normalized with the new separators and dictionary
+        self.update_exact_words()?;
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
+        let date_fields_updated = self.update_date_fields()?;
+        let localized_attributes_updated = self.update_localized_attributes_rules()?;
+        let stemming_languages_updated = self.update_stemming_languages()?;
 
         if stop_words_updated
-            || faceted_updated
+            || separator_tokens_updated
+            || non_separator_tokens_updated
+            || dictionary_updated
             || synonyms_updated
             || searchable_updated
             || exact_attributes_updated
+            || localized_attributes_updated
+            || stemming_languages_updated
+            || schema_updated
         {
             self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
+        } else if faceted_updated || date_fields_updated {
+            // Only the facets of the fields that are added to or removed from the faceted
+            // fields, and of the fields whose values are now parsed differently, change.
+            let new_date_fields = self.index.date_fields(self.wtxn)?;
+            let mut updated_fields = &old_faceted_fields ^ &new_faceted_fields;
+            updated_fields.extend(&old_date_fields ^ &new_date_fields);
+            self.reindex_facets(&progress_callback, &should_abort, &updated_fields)?;
         }
 
         Ok(())
     }
 }
 
+/// Returns why the given field can't be declared in the schema, if it can't.
+fn invalid_schema_field_reason(
+    name: &str,
+    primary_key: Option<&PrimaryKey>,
+) -> Option<&'static str> {
+    let is_same_or_parent = |field: &str| {
+        field == name || field.strip_prefix(name).map_or(false, |rest| rest.starts_with('.'))
+    };
+
+    if name.split('.').any(str::is_empty) {
+        Some("its name can't be empty or contain empty levels")
+    } else if name == "_geo" || name.starts_with("_geo.") {
+        Some("it is reserved to the geosearch")
+    } else if primary_key.map_or(false, |pk| pk.fields().iter().any(|f| is_same_or_parent(f))) {
+        Some("it is part of the primary key")
+    } else {
+        None
+    }
+}
+
+/// Removes all the facet values of the given fields from the facet databases.
+fn remove_facets_of_fields(
+    wtxn: &mut heed::RwTxn,
+    index: &Index,
+    fields_ids: &HashSet<FieldId>,
+) -> Result<()> {
+    for &field_id in fields_ids {
+        let prefix = field_id.to_be_bytes();
+        for facet_type in [FacetType::Number, FacetType::String] {
+            index.put_faceted_documents_ids(wtxn, field_id, facet_type, &RoaringBitmap::new())?;
+        }
+
+        let databases = [
+            index.facet_id_f64_docids.remap_types::<ByteSlice, DecodeIgnore>(),
+            index.facet_id_string_docids.remap_types::<ByteSlice, DecodeIgnore>(),
+            index.field_id_docid_facet_f64s.remap_types::<ByteSlice, DecodeIgnore>(),
+            index.field_id_docid_facet_strings.remap_types::<ByteSlice, DecodeIgnore>(),
+        ];
+        for database in databases {
+            let mut iter = database.prefix_iter_mut(wtxn, &prefix)?;
+            while let Some(result) = iter.next() {
+                result?;
+                // safety: we don't keep references from inside the LMDB database.
+                unsafe { iter.del_current()? };
+            }
+        }
+
+        index.facet_id_exists_docids.delete(wtxn, &BEU16::new(field_id))?;
+        index.facet_id_is_null_docids.delete(wtxn, &BEU16::new(field_id))?;
+        index.facet_id_is_empty_docids.delete(wtxn, &BEU16::new(field_id))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use big_s::S;
@@ -983,6 +1477,58 @@ mod tests {
         assert_eq!(count, 4);
     }
 
+    #[test]
+    fn update_filterable_fields_only_reindexes_facets() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "kevin", "age": 23 },
+                { "id": 1, "name": "kevina", "age": 21 },
+                { "id": 2, "name": "benoit", "age": 34 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let words_fst = index.words_fst(&rtxn).unwrap().as_fst().as_bytes().to_vec();
+        let word_docids_count = index.word_docids.len(&rtxn).unwrap();
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("age") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let age = index.fields_ids_map(&rtxn).unwrap().id("age").unwrap();
+        assert_eq!(index.faceted_fields(&rtxn).unwrap(), hashset! { S("age") });
+        assert_eq!(index.words_fst(&rtxn).unwrap().as_fst().as_bytes(), &words_fst[..]);
+        assert_eq!(index.word_docids.len(&rtxn).unwrap(), word_docids_count);
+        let filter = Filter::from_str("age > 22").unwrap().unwrap();
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).filter(filter).execute().unwrap();
+        assert_eq!(documents_ids, vec![0, 2]);
+        assert_eq!(index.exists_faceted_documents_ids(&rtxn, age).unwrap().len(), 3);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_filterable_fields();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.faceted_fields(&rtxn).unwrap().is_empty());
+        assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
+        assert!(index.exists_faceted_documents_ids(&rtxn, age).unwrap().is_empty());
+        assert_eq!(index.word_docids.len(&rtxn).unwrap(), word_docids_count);
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("benoit").execute().unwrap();
+        assert_eq!(documents_ids, vec![2]);
+    }
+
     #[test]
     fn set_asc_desc_field() {
         let mut index = TempIndex::new();
@@ -1188,6 +1734,128 @@ mod tests {
         assert_eq!(result.documents_ids.len(), 1); // there is one benoit in our data
     }
 
+    #[test]
+    fn set_and_reset_separators_and_dictionary() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "text": "I love #rustlang" },
+                { "id": 1, "text": "redZZblue" },
+                { "id": 2, "text": "AB-123/X" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let result = index.search(&rtxn).query("rustlang").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        let result = index.search(&rtxn).query("blue").execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+        let result = index.search(&rtxn).query("123").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![2]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_non_separator_tokens(btreeset! { S("#") });
+                settings.set_separator_tokens(btreeset! { S("ZZ") });
+                settings.set_dictionary(btreeset! { S("AB-123") });
+                settings.set_synonyms(hashmap! { S("AB-123") => vec![S("widget")] });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let separators = index.allowed_separators(&rtxn).unwrap().unwrap();
+        assert!(separators.contains(&"ZZ"));
+        assert!(!separators.contains(&"#"));
+
+        // the synonyms are normalized with the new tokenization
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        assert_eq!(synonyms.get(&vec![S("ab-123")]), Some(&vec![vec![S("widget")]]));
+
+        // the documents have been reindexed with the new tokenization
+        let result = index.search(&rtxn).query("rustlang").execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+        let result = index.search(&rtxn).query("#rustlang").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        let result = index.search(&rtxn).query("blue").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1]);
+        let result = index.search(&rtxn).query("123").execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+        let result = index.search(&rtxn).query("AB-123").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![2]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_non_separator_tokens();
+                settings.reset_separator_tokens();
+                settings.reset_dictionary();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.allowed_separators(&rtxn).unwrap(), None);
+        assert_eq!(index.dictionary(&rtxn).unwrap(), None);
+        let result = index.search(&rtxn).query("rustlang").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+
+        // the stored synonyms are normalized again without the dictionary
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        assert_eq!(synonyms.get(&vec![S("ab"), S("123")]), Some(&vec![vec![S("widget")]]));
+    }
+
+    #[test]
+    fn set_and_reset_stemming_languages() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "text": "she runs every morning" },
+                { "id": 1, "text": "running shoes" },
+                { "id": 2, "text": "a long run" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let result = index.search(&rtxn).query("running").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_stemming_languages(vec![StemmingLanguage::English]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.stemming_languages(&rtxn).unwrap(), vec![StemmingLanguage::English]);
+        // the exact form is ranked first thanks to the exactness criterion
+        let result = index.search(&rtxn).query("running").execute().unwrap();
+        assert_eq!(result.documents_ids[0], 1);
+        let mut documents_ids = result.documents_ids;
+        documents_ids.sort_unstable();
+        assert_eq!(documents_ids, vec![0, 1, 2]);
+
+        // the stems are not part of the proximities between the words
+        let docids = index.word_pair_proximity_docids.get(&rtxn, &(1, "running", "shoes")).unwrap();
+        assert!(docids.is_some());
+        let docids = index.word_pair_proximity_docids.get(&rtxn, &(1, "run", "shoe")).unwrap();
+        assert!(docids.is_none());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_stemming_languages();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.stemming_languages(&rtxn).unwrap().is_empty());
+        let result = index.search(&rtxn).query("running").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1]);
+    }
+
     #[test]
     fn set_and_reset_synonyms() {
         let mut index = TempIndex::new();
@@ -1316,7 +1984,7 @@ mod tests {
                 settings.set_primary_key(S("mykey"));
             })
             .unwrap();
-        assert_eq!(index.primary_key(&wtxn).unwrap(), Some("mykey"));
+        assert_eq!(index.primary_key(&wtxn).unwrap(), Some("mykey".into()));
 
         // Then index some documents with the "mykey" primary key.
         index
@@ -1492,35 +2160,55 @@ mod tests {
                     displayed_fields,
                     filterable_fields,
                     sortable_fields,
+                    date_fields,
                     criteria,
                     stop_words,
+                    separator_tokens,
+                    non_separator_tokens,
+                    dictionary,
                     distinct_field,
                     synonyms,
                     primary_key,
                     authorize_typos,
                     min_word_len_two_typos,
                     min_word_len_one_typo,
+                    attributes_min_word_len_for_typo,
+                    disable_typos_on_numbers,
                     exact_words,
                     exact_attributes,
                     max_values_per_facet,
                     pagination_max_total_hits,
+                    curation_rules,
+                    localized_attributes_rules,
+                    stemming_languages,
+                    schema,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
+                assert!(matches!(date_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
+                assert!(matches!(separator_tokens, Setting::NotSet));
+                assert!(matches!(non_separator_tokens, Setting::NotSet));
+                assert!(matches!(dictionary, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_two_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
+                assert!(matches!(attributes_min_word_len_for_typo, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
+                assert!(matches!(curation_rules, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(stemming_languages, Setting::NotSet));
+                assert!(matches!(schema, Setting::NotSet));
             })
             .unwrap();
     }
@@ -29,6 +29,16 @@ impl UpdateIndexingStep {
         }
     }
 
+    /// A human readable name of the step.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            RemapDocumentAddition { .. } => "remapping the documents fields",
+            ComputeIdsAndMergeDocuments { .. } => "computing the documents ids and merging them",
+            IndexDocuments { .. } => "extracting the documents words and facets",
+            MergeDataIntoFinalDatabase { .. } => "merging the extracted data into the databases",
+        }
+    }
+
     pub const fn number_of_steps(&self) -> usize {
         4
     }
@@ -1,6 +1,8 @@
 use std::collections::BTreeSet;
 
 use heed::EnvOpenOptions;
+use maplit::btreemap;
+use milli::index::MinWordLenForTypo;
 use milli::update::{IndexDocuments, IndexDocumentsConfig, IndexerConfig, Settings};
 use milli::{Criterion, Index, Search, TermsMatchingStrategy};
 use serde_json::json;
@@ -206,3 +208,117 @@ fn test_disable_typo_on_attribute() {
     let result = search.execute().unwrap();
     assert_eq!(result.documents_ids.len(), 0);
 }
+
+#[test]
+fn test_typo_disabled_on_numbers() {
+    let index = index_with_documents(vec![
+        json!({ "id": 1usize, "data": "123456" }),
+        json!({ "id": 2usize, "data": "123457" }),
+    ]);
+
+    // basic typo search with default typo settings
+    {
+        let txn = index.read_txn().unwrap();
+
+        let mut search = Search::new(&txn, &index);
+        search.query("123456");
+        search.limit(10);
+        search.authorize_typos(true);
+        search.terms_matching_strategy(TermsMatchingStrategy::default());
+
+        let result = search.execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+    }
+
+    let mut txn = index.write_txn().unwrap();
+
+    let config = IndexerConfig::default();
+    let mut builder = Settings::new(&mut txn, &index, &config);
+    builder.set_disable_typos_on_numbers(true);
+    builder.execute(|_| (), || false).unwrap();
+
+    let mut search = Search::new(&txn, &index);
+    search.query("123456");
+    search.limit(10);
+    search.authorize_typos(true);
+    search.terms_matching_strategy(TermsMatchingStrategy::default());
+
+    let result = search.execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 1);
+}
+
+#[test]
+fn test_min_word_len_for_typo_per_attribute() {
+    let index = index_with_documents(vec![
+        json!({ "id": 1usize, "title": "zealand" }),
+        json!({ "id": 2usize, "sku": "zearand" }),
+        json!({ "id": 3usize, "sku": "zean" }),
+    ]);
+
+    let search_ids = |txn: &heed::RoTxn, query: &str| {
+        let mut search = Search::new(txn, &index);
+        search.query(query);
+        search.limit(10);
+        search.authorize_typos(true);
+        search.terms_matching_strategy(TermsMatchingStrategy::default());
+        let mut ids = search.execute().unwrap().documents_ids;
+        ids.sort_unstable();
+        ids
+    };
+
+    // basic typo search with default typo settings
+    {
+        let txn = index.read_txn().unwrap();
+        assert_eq!(search_ids(&txn, "zealand"), vec![0, 1]);
+        assert!(search_ids(&txn, "zeak").is_empty());
+    }
+
+    let mut txn = index.write_txn().unwrap();
+
+    let config = IndexerConfig::default();
+    let mut builder = Settings::new(&mut txn, &index, &config);
+    // typos are not allowed on 7 letters words in `sku` anymore
+    let sku = MinWordLenForTypo { one_typo: Some(8), two_typos: Some(12) };
+    builder.set_attributes_min_word_len_for_typo(btreemap! { "sku".to_string() => sku });
+    builder.execute(|_| (), || false).unwrap();
+
+    assert_eq!(search_ids(&txn, "zealand"), vec![0]);
+
+    let mut builder = Settings::new(&mut txn, &index, &config);
+    // one typo is allowed on 4 letters words in `sku`
+    let sku = MinWordLenForTypo { one_typo: Some(4), two_typos: None };
+    builder.set_attributes_min_word_len_for_typo(btreemap! { "sku".to_string() => sku });
+    builder.execute(|_| (), || false).unwrap();
+
+    // `title` still uses the index-wide minimum word lengths
+    assert_eq!(search_ids(&txn, "zeak"), vec![1, 2]);
+}
+
+fn index_with_documents(documents: Vec<serde_json::Value>) -> Index {
+    let tmp = tempdir().unwrap();
+    let mut options = EnvOpenOptions::new();
+    options.map_size(4096 * 100);
+    let index = Index::new(options, tmp.path()).unwrap();
+
+    let mut builder = milli::documents::DocumentsBatchBuilder::new(Vec::new());
+    for document in documents {
+        builder.append_json_object(document.as_object().unwrap()).unwrap();
+    }
+    let vector = builder.into_inner().unwrap();
+
+    let documents =
+        milli::documents::DocumentsBatchReader::from_reader(std::io::Cursor::new(vector)).unwrap();
+
+    let mut txn = index.write_txn().unwrap();
+    let config = IndexerConfig::default();
+    let indexing_config = IndexDocumentsConfig::default();
+    let builder =
+        IndexDocuments::new(&mut txn, &index, &config, indexing_config, |_| (), || false).unwrap();
+
+    let (builder, user_error) = builder.add_documents(documents).unwrap();
+    user_error.unwrap();
+    builder.execute().unwrap();
+    txn.commit().unwrap();
+
+    index
+}